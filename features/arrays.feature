@@ -346,6 +346,41 @@ Feature: Arrays
       - 2
       ```
 
+  Scenario: uniqueItems, contains and minItems together
+    Given a YAML schema:
+      ```
+      type: array
+      uniqueItems: true
+      contains:
+        type: number
+      minItems: 2
+      ```
+    Then it should accept:
+      ```
+      - 1
+      - a
+      ```
+    # Duplicate items, although it contains a number and has enough items
+    But it should NOT accept:
+      ```
+      - 1
+      - 1
+      ```
+    And the error message should be "[2:3] .: Duplicate array element: 1"
+    # No number, although the items are unique and there are enough of them
+    But it should NOT accept:
+      ```
+      - a
+      - b
+      ```
+    And the error message should be "[1:1] .: Array must contain at least 1 item(s) matching the contains schema, but only 0 matched"
+    # Too few items, although it contains a number and has no duplicates
+    But it should NOT accept:
+      ```
+      - 1
+      ```
+    And the error message should be "[1:1] .: Array has too few items (minimum 2, found 1)"
+
   Scenario: Contains
     # While the items schema must be valid for every item in the array, the `contains` only needs to
     # validate against one or more items in the array.
@@ -512,3 +547,66 @@ Feature: Arrays
       - 1
       - banana
       ```
+
+  Scenario: enum in items without a type
+    Given a YAML schema:
+      ```
+      type: array
+      items:
+        enum:
+          - a
+          - b
+      ```
+    Then it should accept:
+      ```
+      - a
+      - b
+      - a
+      ```
+    But it should NOT accept:
+      ```
+      - a
+      - b
+      - c
+      ```
+
+  Scenario: enum in prefixItems
+    Given a YAML schema:
+      ```
+      type: array
+      prefixItems:
+        - type: number
+        - enum:
+          - Street
+          - Avenue
+      ```
+    Then it should accept:
+      ```
+      - 1600
+      - Avenue
+      ```
+    But it should NOT accept:
+      ```
+      - 1600
+      - Road
+      ```
+
+  Scenario: enum in contains
+    Given a YAML schema:
+      ```
+      type: array
+      contains:
+        enum:
+          - admin
+      ```
+    Then it should accept:
+      ```
+      - user
+      - admin
+      ```
+    But it should NOT accept:
+      ```
+      - user
+      - guest
+      ```
+    And the error message should be "[1:1] .: Array must contain at least 1 item(s) matching the contains schema, but only 0 matched"
@@ -18,6 +18,46 @@ Feature: Constant values
       country: Canada
       ```
 
+  Scenario: const without type
+    Given a YAML schema:
+      ```
+      const: "hello"
+      ```
+    Then it should accept:
+      ```
+      hello
+      ```
+    But it should NOT accept:
+      ```
+      goodbye
+      ```
+    And the error message should be '[1:1] .: Value "goodbye" does not match const: "hello"'
+    And it should NOT accept:
+      ```
+      42
+      ```
+
+  Scenario: const with integer type
+    Given a YAML schema:
+      ```
+      type: integer
+      const: 5
+      ```
+    Then it should accept:
+      ```
+      5
+      ```
+    But it should NOT accept:
+      ```
+      6
+      ```
+    And the error message should be '[1:1] .: Value 6 does not match const: 5'
+    And it should NOT accept:
+      ```
+      "5"
+      ```
+    And the error message should be '[1:1] .: Expected a number, but got: "5" (string)'
+
   Scenario: const with array value
     Given a YAML schema:
       ```
@@ -49,6 +49,11 @@ Feature: Enumerated values
       ```
       0
       ```
+    And it should NOT accept:
+      ```
+      blue
+      ```
+    And the error message should be '[1:1] .: Value "blue" is not in the enum: ["red", "amber", "green", null, 42]'
 
   @enum
   Scenario: enum of other types
@@ -214,6 +214,33 @@ Feature: Format validation
       ```
       "not a uri"
       ```
+    And it should NOT accept:
+      ```
+      "/path"
+      ```
+    And it should NOT accept:
+      ```
+      "https://example.com/a b"
+      ```
+
+  Scenario: format "uri-reference" should accept absolute and relative references
+    Given a YAML schema:
+      ```
+      type: string
+      format: uri-reference
+      ```
+    Then it should accept:
+      ```
+      "https://a/b"
+      ```
+    And it should accept:
+      ```
+      "/path"
+      ```
+    But it should NOT accept:
+      ```
+      "has space"
+      ```
 
   Scenario: format "uuid" should accept valid UUIDs
     Given a YAML schema:
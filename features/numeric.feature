@@ -49,6 +49,14 @@ Feature: Numeric types
       23
       ```
 
+  Scenario: multipleOf must be greater than 0
+    Given a YAML schema:
+      ```
+      type: number
+      multipleOf: 0
+      ```
+    Then it should fail with "Error loading schema: [3, 12] 'multipleOf' must be greater than 0, but got: 0"
+
   Scenario: Range of numbers
     Given a YAML schema:
       ```
@@ -121,6 +129,33 @@ Feature: Numeric types
       101
       ```
 
+  Scenario: Inclusive minimum and maximum
+    Given a YAML schema:
+      ```
+      type: integer
+      minimum: 1
+      maximum: 10
+      ```
+    # `minimum` and `maximum` are inclusive
+    Then it should accept:
+      ```
+      1
+      ```
+    And it should accept:
+      ```
+      10
+      ```
+    But it should NOT accept:
+      ```
+      0
+      ```
+    And the error message should be '[1:1] .: Number must be greater than or equal to 1'
+    And it should NOT accept:
+      ```
+      11
+      ```
+    And the error message should be '[1:1] .: Number must be less than or equal to 10'
+
   Scenario: integer with enum
     Given a YAML schema:
       ```
@@ -593,7 +593,7 @@ Feature: Object types
         billing_address:
           type: string
       ```
-    But it should NOT accept:
+    Then it should NOT accept:
       ```
       credit_card: "4111"
       ```
@@ -640,7 +640,7 @@ Feature: Object types
         email:
           type: string
       ```
-    But it should NOT accept:
+    Then it should NOT accept:
       ```
       opt_in: true
       ```
@@ -649,3 +649,42 @@ Feature: Object types
       opt_in: true
       email: "a@example.com"
       ```
+
+  Scenario: dependentSchemas requiring card_number when type is card
+    Given a YAML schema:
+      ```
+      type: object
+      properties:
+        type:
+          type: string
+        card_number:
+          type: string
+      dependentSchemas:
+        type:
+          if:
+            properties:
+              type:
+                const: card
+          then:
+            type: object
+            required:
+              - card_number
+      ```
+    Then it should accept:
+      ```
+      type: card
+      card_number: "4111 1111 1111 1111"
+      ```
+    And it should accept:
+      ```
+      type: bank
+      ```
+    And it should accept:
+      ```
+      card_number: "4111 1111 1111 1111"
+      ```
+    But it should NOT accept:
+      ```
+      type: card
+      ```
+    And the error message should be "[1:1] .: Required property 'card_number' is missing! (from dependentSchemas, as property 'type' is present)"
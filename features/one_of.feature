@@ -111,4 +111,45 @@ Feature: oneOf
       ```
       - gamma
       ```
-    And the error message should be "[1:3] .: None of the schemas in `oneOf` matched!"
+    And the error message should be "[1:3] .0: None of the schemas in `oneOf` matched!"
+
+  Scenario: oneOf discriminated by a const property
+    Given a YAML schema:
+      ```
+      oneOf:
+        - type: object
+          properties:
+            kind:
+              const: circle
+            radius:
+              type: number
+          required: [kind, radius]
+        - type: object
+          properties:
+            kind:
+              const: square
+            side:
+              type: number
+          required: [kind, side]
+      ```
+    Then it should accept:
+      ```
+      kind: circle
+      radius: 1.5
+      ```
+    And it should accept:
+      ```
+      kind: square
+      side: 2
+      ```
+    But it should NOT accept:
+      ```
+      kind: circle
+      side: 2
+      ```
+    And it should NOT accept:
+      ```
+      kind: triangle
+      side: 2
+      ```
+    And the error message should be "[1:1] .: None of the schemas in `oneOf` matched!"
@@ -20,6 +20,55 @@ Feature: References ($ref)
       name: 42
       ```
 
+  Scenario: $ref to a nested property schema
+    Given a YAML schema:
+      ```
+      type: object
+      properties:
+        home:
+          type: object
+          properties:
+            street:
+              type: string
+              maxLength: 10
+        work:
+          type: object
+          properties:
+            street:
+              $ref: "#/properties/home/properties/street"
+      ```
+    Then it should accept:
+      ```
+      work:
+        street: Main St
+      ```
+    But it should NOT accept:
+      ```
+      work:
+        street: A very long street name
+      ```
+
+  Scenario: $ref to an $anchor
+    Given a YAML schema:
+      ```
+      type: object
+      $defs:
+        name:
+          $anchor: person-name
+          type: string
+      properties:
+        name:
+          $ref: "#person-name"
+      ```
+    Then it should accept:
+      ```
+      name: Alice
+      ```
+    But it should NOT accept:
+      ```
+      name: 42
+      ```
+
   Scenario: Direct circular $ref
     Given a YAML schema:
       ```
@@ -0,0 +1,93 @@
+//! Writes the seed corpora in `fuzz/seeds/` from the schemas and documents of the `features/`
+//! scenarios:
+//!
+//! - `load_from_str/<feature>-NNN`: each schema
+//! - `evaluate/<feature>-NNN`: each schema and a document it should or should NOT accept,
+//!   separated by a NUL byte
+//!
+//! Run with `cargo run --manifest-path fuzz/Cargo.toml --example seeds`.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The steps whose code block is a schema
+const SCHEMA_STEP: &str = "a YAML schema:";
+
+/// The steps whose code block is a document validated against the last schema
+const DOCUMENT_STEPS: [&str; 2] = ["it should accept:", "it should NOT accept:"];
+
+/// The steps' Gherkin keywords
+const KEYWORDS: [&str; 5] = ["Given ", "When ", "Then ", "And ", "But "];
+
+fn main() -> io::Result<()> {
+    let fuzz_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut features: Vec<_> = fs::read_dir(fuzz_dir.join("../features"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+    features.retain(|path| path.extension().is_some_and(|ext| ext == "feature"));
+    features.sort();
+
+    let mut schemas = Vec::new();
+    let mut documents = Vec::new();
+    for path in &features {
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        let contents = fs::read_to_string(path)?;
+        let mut step = "";
+        let mut schema: Option<String> = None;
+        let mut lines = contents.lines();
+        while let Some(line) = lines.next() {
+            let trimmed = line.trim_start();
+            if let Some(rest) = KEYWORDS.iter().find_map(|k| trimmed.strip_prefix(k)) {
+                step = rest;
+                continue;
+            }
+            if !trimmed.starts_with("```") {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            let mut block = String::new();
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                block.push_str(line.get(indent..).unwrap_or(line.trim_start()));
+                block.push('\n');
+            }
+            if block.is_empty() {
+                block.push('\n');
+            }
+            if step == SCHEMA_STEP {
+                schemas.push((name, block.clone()));
+                schema = Some(block);
+            } else if DOCUMENT_STEPS.contains(&step)
+                && let Some(schema) = &schema
+            {
+                documents.push((name, format!("{schema}\0{block}")));
+            }
+        }
+    }
+
+    write_corpus(&fuzz_dir.join("seeds/load_from_str"), &schemas)?;
+    write_corpus(&fuzz_dir.join("seeds/evaluate"), &documents)
+}
+
+/// Replaces the files in `dir` with the distinct `inputs`, numbered in order
+fn write_corpus(dir: &Path, inputs: &[(&str, String)]) -> io::Result<()> {
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+    let mut seen = HashSet::new();
+    for (feature, input) in inputs {
+        if seen.insert(input) {
+            fs::write(dir.join(format!("{feature}-{:03}", seen.len())), input)?;
+        }
+    }
+    println!("{}: {} inputs", dir.display(), seen.len());
+    Ok(())
+}
@@ -0,0 +1,20 @@
+//! Validating any document against any schema must return `Ok` or `Err`, never panic. The input
+//! is the schema and the document separated by a NUL byte, see `fuzz/README.md`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use yaml_schema::Engine;
+use yaml_schema::loader;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Some((schema, instance)) = s.split_once('\0') else {
+        return;
+    };
+    if let Ok(root_schema) = loader::load_from_str(schema) {
+        let _ = Engine::evaluate(&root_schema, instance, false);
+    }
+});
@@ -0,0 +1,12 @@
+//! Loading any input as a schema must return `Ok` or `Err`, never panic
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use yaml_schema::loader;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = loader::load_from_str(s);
+    }
+});
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use clap::Parser;
 use clap::Subcommand;
@@ -11,7 +11,12 @@ use url::Url;
 
 use yaml_schema::Engine;
 use yaml_schema::RootSchema;
+use yaml_schema::ValidationOptions;
 use yaml_schema::loader;
+use yaml_schema::loader::LoaderOptions;
+use yaml_schema::loader::UnknownKeys;
+use yaml_schema::loader::UrlLoadError;
+use yaml_schema::validation::Severity;
 use yaml_schema::validation::ValidationError;
 use yaml_schema::version;
 
@@ -30,9 +35,38 @@ pub struct Opts {
     /// Omit when the instance YAML has a top-level string `$schema` (URL or path).
     #[arg(short = 'f', long = "schema")]
     pub schemas: Vec<String>,
+    /// URL of the schema to validate against, downloaded over http(s) instead of read from a
+    /// file. Exits with 3 if it can't be downloaded.
+    #[arg(long = "schema-url", conflicts_with = "schemas")]
+    pub schema_url: Option<String>,
+    /// Timeout in seconds for downloading the --schema-url schema
+    #[arg(long = "timeout", default_value = "30")]
+    pub timeout: u64,
     /// Specify this flag to exit (1) as soon as any error is encountered
     #[arg(long = "fail-fast", default_value = "false")]
     pub fail_fast: bool,
+    /// Allow `$ref`s to http(s) URLs to be fetched over the network
+    #[arg(long = "allow-remote-refs")]
+    pub allow_remote_refs: bool,
+    /// How to treat unknown keywords in the -f/--schema files, such as the typo `minmum`:
+    /// ignore, warn, or error
+    #[arg(long = "unknown-keys", default_value = "ignore")]
+    pub unknown_keys: UnknownKeys,
+    /// Fail on a `format` in the -f/--schema files that isn't a known format, instead of
+    /// ignoring it
+    #[arg(long = "strict-formats")]
+    pub strict_formats: bool,
+    /// Fail on keywords in the -f/--schema files that are at the wrong nesting level, such as
+    /// `minLength` in a `type: object` schema, instead of warning
+    #[arg(long = "strict")]
+    pub strict: bool,
+    /// Validate every document of a multi-document YAML file, not only the first
+    #[arg(long = "all-documents")]
+    pub all_documents: bool,
+    /// Show the location in the schema of the rule behind each error, e.g.
+    /// #/properties/port/maximum, and list suppressed diagnostics
+    #[arg(long = "verbose")]
+    pub verbose: bool,
     /// Emit errors as JSON: validation failures as a JSON array on stdout; other failures as
     /// {"error":"..."} on stderr.
     #[arg(long = "json")]
@@ -47,23 +81,15 @@ pub enum Commands {
     Version,
 }
 
+/// The exit code when the --schema-url schema can't be downloaded
+const EXIT_NETWORK_ERROR: i32 = 3;
+
 fn emit_json_error(message: &str) {
     eprintln!("{}", json!({ "error": message }));
 }
 
 fn emit_validation_errors_json(errors: &[ValidationError]) {
-    let entries: Vec<serde_json::Value> = errors
-        .iter()
-        .map(|e| {
-            json!({
-                "index": e.marker.map(|m| m.index()),
-                "line": e.marker.map(|m| m.line()),
-                "col": e.marker.map(|m| m.col()),
-                "path": e.path,
-                "error": e.error,
-            })
-        })
-        .collect();
+    let entries: Vec<serde_json::Value> = errors.iter().map(ValidationError::to_json).collect();
     println!("{}", serde_json::Value::Array(entries));
 }
 
@@ -105,20 +131,31 @@ fn schema_uri(path: &str) -> Result<String> {
 }
 
 fn insert_preloaded_entry(
-    preloaded: &mut HashMap<String, Rc<RootSchema>>,
+    preloaded: &mut HashMap<String, Arc<RootSchema>>,
     schema: RootSchema,
     uri: String,
-) -> Rc<RootSchema> {
-    let schema_rc = Rc::new(schema);
+) -> Arc<RootSchema> {
+    let schema_rc = Arc::new(schema);
     let key = schema_rc.cache_key(&uri);
     // Insert under both `uri` and `key` when they differ so $ref resolution matches the CLI preload map.
     if key != uri {
-        preloaded.insert(uri, Rc::clone(&schema_rc));
+        preloaded.insert(uri, Arc::clone(&schema_rc));
     }
-    preloaded.insert(key, Rc::clone(&schema_rc));
+    preloaded.insert(key, Arc::clone(&schema_rc));
     schema_rc
 }
 
+/// Print the warnings from loading the schema at `location`, e.g. unknown keywords
+fn emit_schema_warnings(location: &str, schema: &RootSchema, json: bool) {
+    for warning in &schema.warnings {
+        if json {
+            eprintln!("{}", json!({ "warning": format!("{location}: {warning}") }));
+        } else {
+            eprintln!("warning: {location}: {warning}");
+        }
+    }
+}
+
 /// The `ys validate` command
 fn command_validate(opts: Opts) -> Result<i32> {
     let json = opts.json;
@@ -130,9 +167,41 @@ fn command_validate(opts: Opts) -> Result<i32> {
     let yaml_contents = std::fs::read_to_string(yaml_filename)
         .wrap_err_with(|| format!("Failed to read YAML file: {yaml_filename}"))?;
 
-    let (root_for_eval, preloaded) = if !opts.schemas.is_empty() {
+    let loader_options = LoaderOptions::default()
+        .unknown_keys(opts.unknown_keys)
+        .strict_formats(opts.strict_formats)
+        .strict(opts.strict);
+    let (root_for_eval, preloaded) = if let Some(url) = &opts.schema_url {
+        let root = match loader::download_from_url_with_options(
+            url,
+            Some(opts.timeout),
+            &loader_options,
+        ) {
+            Ok(schema) => schema,
+            Err(e) => {
+                if json {
+                    emit_json_error(&format!("Failed to load schema from URL {url}: {e}"));
+                } else {
+                    eprintln!("Failed to load schema from URL: {url}: {e}");
+                }
+                let is_network_error = matches!(
+                    e,
+                    yaml_schema::Error::UrlLoadError(UrlLoadError::DownloadError(_))
+                );
+                return Ok(if is_network_error {
+                    EXIT_NETWORK_ERROR
+                } else {
+                    1
+                });
+            }
+        };
+        emit_schema_warnings(url, &root, json);
+        let mut preloaded = HashMap::new();
+        let root_rc = insert_preloaded_entry(&mut preloaded, root, url.clone());
+        (root_rc, preloaded)
+    } else if !opts.schemas.is_empty() {
         let root_path = opts.schemas.first().expect("non-empty schemas");
-        let root_schema = match loader::load_file(root_path) {
+        let root_schema = match loader::load_file_with_options(root_path, &loader_options) {
             Ok(schema) => schema,
             Err(e) => {
                 if json {
@@ -158,7 +227,7 @@ fn command_validate(opts: Opts) -> Result<i32> {
                     return Ok(1);
                 }
             };
-            let schema = match loader::load_file(path) {
+            let schema = match loader::load_file_with_options(path, &loader_options) {
                 Ok(s) => s,
                 Err(e) => {
                     if json {
@@ -170,10 +239,11 @@ fn command_validate(opts: Opts) -> Result<i32> {
                     return Ok(1);
                 }
             };
+            emit_schema_warnings(path, &schema, json);
             let _ = insert_preloaded_entry(&mut preloaded, schema, uri);
         }
 
-        let root_rc = Rc::new(root_schema);
+        let root_rc = Arc::new(root_schema);
         (root_rc, preloaded)
     } else {
         let instance_parent = Path::new(yaml_filename).parent().unwrap_or(Path::new("."));
@@ -181,7 +251,7 @@ fn command_validate(opts: Opts) -> Result<i32> {
             Ok(Some(s)) => s,
             Ok(None) => {
                 return Err(eyre::eyre!(
-                    "No schema: pass -f/--schema or add a string `$schema` key to the YAML root mapping"
+                    "No schema: pass -f/--schema or --schema-url, or add a string `$schema` key to the YAML root mapping"
                 ));
             }
             Err(e) => {
@@ -212,25 +282,52 @@ fn command_validate(opts: Opts) -> Result<i32> {
         (root_rc, preloaded)
     };
 
-    match Engine::evaluate_with_schemas(
+    if !root_for_eval.looks_like_schema() {
+        let schema_name = opts
+            .schemas
+            .first()
+            .or(opts.schema_url.as_ref())
+            .map_or("The schema", String::as_str);
+        let warning = format!(
+            "{schema_name} doesn't look like a schema (it has no `type`, `properties`, `$schema` or combinators), so it accepts any value. Was a data file passed as the schema?"
+        );
+        if json {
+            eprintln!("{}", json!({ "warning": warning }));
+        } else {
+            eprintln!("warning: {warning}");
+        }
+    }
+
+    let options = ValidationOptions::default()
+        .fail_fast(opts.fail_fast)
+        .allow_remote_refs(opts.allow_remote_refs)
+        .all_documents(opts.all_documents)
+        .verbose(opts.verbose);
+    match Engine::evaluate_with_schemas_and_options(
         root_for_eval.as_ref(),
         &yaml_contents,
-        opts.fail_fast,
+        &options,
         preloaded,
     ) {
         Ok(context) => {
-            if context.has_errors() {
-                let errors = context.errors.borrow();
-                if json {
+            let failed = context.has_errors();
+            let errors = context.errors.borrow();
+            if json {
+                if failed {
                     emit_validation_errors_json(errors.as_slice());
                 } else {
-                    for error in errors.iter() {
-                        eprintln!("{error}");
+                    // Warnings don't fail validation, so they go to stderr like other warnings
+                    for warning in errors.iter().filter(|e| e.severity == Severity::Warning) {
+                        eprintln!("{}", json!({ "warning": warning.to_json() }));
                     }
                 }
-                return Ok(1);
+            } else {
+                // Suppressed diagnostics are only listed with --verbose
+                for error in errors.iter().filter(|e| opts.verbose || !e.suppressed) {
+                    eprintln!("{error}");
+                }
             }
-            Ok(0)
+            Ok(if failed { 1 } else { 0 })
         }
         Err(e) => {
             if json {
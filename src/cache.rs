@@ -0,0 +1,201 @@
+//! A cache of parsed root schemas keyed by the hash of their source text, for applications that
+//! load the same schema text repeatedly.
+
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use hashlink::LruCache;
+
+use crate::Result;
+use crate::RootSchema;
+use crate::loader;
+
+/// Hit and miss counts of a [`SchemaCache`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// A cached schema, with the text it was loaded from to guard against hash collisions
+struct Entry {
+    text: String,
+    schema: Arc<RootSchema>,
+}
+
+struct Inner {
+    entries: LruCache<u64, Entry>,
+    stats: CacheStats,
+}
+
+/// A thread-safe LRU cache of parsed [`RootSchema`]s keyed by a hash of their source text.
+///
+/// When the cache is full, the least recently used schema is evicted. Schemas are loaded while
+/// the cache is locked, so concurrent requests for the same new text parse it only once.
+///
+/// ```
+/// use yaml_schema::Engine;
+/// use yaml_schema::SchemaCache;
+///
+/// let cache = SchemaCache::new(16);
+/// let schema = cache.get_or_load("type: string").unwrap();
+/// let context = Engine::evaluate(&schema, "42", false).unwrap();
+/// assert!(context.has_errors());
+///
+/// cache.get_or_load("type: string").unwrap();
+/// assert_eq!(cache.stats().hits, 1);
+/// assert_eq!(cache.stats().misses, 1);
+/// ```
+pub struct SchemaCache {
+    inner: Mutex<Inner>,
+}
+
+impl SchemaCache {
+    /// Create a cache holding at most `capacity` schemas
+    pub fn new(capacity: usize) -> SchemaCache {
+        SchemaCache {
+            inner: Mutex::new(Inner {
+                entries: LruCache::new(capacity),
+                stats: CacheStats::default(),
+            }),
+        }
+    }
+
+    /// Returns the schema parsed from `text`, loading and caching it on a miss. Schemas that
+    /// fail to load are not cached.
+    pub fn get_or_load(&self, text: &str) -> Result<Arc<RootSchema>> {
+        let key = content_hash(text);
+        let mut inner = self.lock();
+        if let Some(entry) = inner.entries.get(&key)
+            && entry.text == text
+        {
+            let schema = Arc::clone(&entry.schema);
+            inner.stats.hits += 1;
+            return Ok(schema);
+        }
+        inner.stats.misses += 1;
+        let schema = Arc::new(loader::load_from_str(text)?);
+        inner.entries.insert(
+            key,
+            Entry {
+                text: text.to_string(),
+                schema: Arc::clone(&schema),
+            },
+        );
+        Ok(schema)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().entries.capacity()
+    }
+
+    /// Remove all cached schemas and reset the statistics
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.entries.clear();
+        inner.stats = CacheStats::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for SchemaCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.lock();
+        f.debug_struct("SchemaCache")
+            .field("len", &inner.entries.len())
+            .field("capacity", &inner.entries.capacity())
+            .field("stats", &inner.stats)
+            .finish()
+    }
+}
+
+fn content_hash(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use crate::Engine;
+
+    use super::*;
+
+    const PORT: &str = "type: integer\nminimum: 1\nmaximum: 65535";
+    const NAME: &str = "type: string\nminLength: 1";
+
+    #[test]
+    fn test_concurrent_get_or_load() {
+        let cache = SchemaCache::new(4);
+        thread::scope(|scope| {
+            for i in 0..8 {
+                let cache = &cache;
+                scope.spawn(move || {
+                    for _ in 0..25 {
+                        let text = if i % 2 == 0 { PORT } else { NAME };
+                        let schema = cache.get_or_load(text).unwrap();
+                        let (good, bad) = if i % 2 == 0 {
+                            ("8080", "0")
+                        } else {
+                            ("alice", "''")
+                        };
+                        assert!(!Engine::evaluate(&schema, good, false).unwrap().has_errors());
+                        assert!(Engine::evaluate(&schema, bad, false).unwrap().has_errors());
+                    }
+                });
+            }
+        });
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 198,
+                misses: 2
+            }
+        );
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = SchemaCache::new(2);
+        let port = cache.get_or_load(PORT).unwrap();
+        cache.get_or_load(NAME).unwrap();
+        cache.get_or_load(PORT).unwrap();
+        cache.get_or_load("type: boolean").unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // `NAME` was the least recently used, so it was evicted and `PORT` was kept
+        assert!(Arc::ptr_eq(&port, &cache.get_or_load(PORT).unwrap()));
+        cache.get_or_load(NAME).unwrap();
+        assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 4 });
+    }
+
+    #[test]
+    fn test_load_errors_are_not_cached() {
+        let cache = SchemaCache::new(4);
+        assert!(cache.get_or_load("type: widget").is_err());
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().misses, 1);
+    }
+}
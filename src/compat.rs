@@ -0,0 +1,927 @@
+//! Compare two versions of a schema: a structural [`diff`] of the changes that affect documents,
+//! and a human-readable [`migration_report`] built on it.
+//!
+//! Both work on the schema as written: `$ref`s are not followed, and only properties, `items`,
+//! and `$defs` that exist at the same location in both versions are compared.
+
+use std::fmt::Write;
+
+use crate::ConstValue;
+use crate::Number;
+use crate::RootSchema;
+use crate::YamlSchema;
+use crate::fixes::path_to_pointer;
+use crate::schemas::BooleanOrSchema;
+use crate::schemas::NumericBounds;
+use crate::schemas::Subschema;
+
+/// A single change between two versions of a schema. `pointer` is the JSON Pointer to the
+/// changed subschema, e.g. `/properties/server/properties/port`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// A property was added to `properties`
+    PropertyAdded {
+        pointer: String,
+        name: String,
+        required: bool,
+    },
+    /// A property was removed from `properties`
+    PropertyRemoved {
+        pointer: String,
+        name: String,
+        was_required: bool,
+    },
+    /// A property present in both versions became required
+    RequiredAdded { pointer: String, name: String },
+    /// A property present in both versions is no longer required
+    RequiredRemoved { pointer: String, name: String },
+    /// The `type` changed
+    TypeChanged {
+        pointer: String,
+        old: String,
+        new: String,
+    },
+    /// `enum` values were added or removed
+    EnumChanged {
+        pointer: String,
+        added: Vec<ConstValue>,
+        removed: Vec<ConstValue>,
+    },
+    /// A bound such as `minimum` or `maxLength` now accepts fewer values
+    ConstraintTightened {
+        pointer: String,
+        keyword: &'static str,
+        old: Option<String>,
+        new: String,
+    },
+    /// A bound such as `minimum` or `maxLength` now accepts more values
+    ConstraintLoosened {
+        pointer: String,
+        keyword: &'static str,
+        old: String,
+        new: Option<String>,
+    },
+    /// A constraint such as `pattern` changed in a way that may both accept and reject values
+    /// that the old one didn't
+    ConstraintChanged {
+        pointer: String,
+        keyword: &'static str,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+/// Compute the changes between two versions of a schema, in document order
+pub fn diff(old: &RootSchema, new: &RootSchema) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    diff_schemas(&old.schema, &new.schema, &mut Vec::new(), &mut changes);
+    changes
+}
+
+fn diff_schemas(
+    old: &YamlSchema,
+    new: &YamlSchema,
+    path: &mut Vec<String>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let (YamlSchema::Subschema(old), YamlSchema::Subschema(new)) = (old, new) else {
+        return;
+    };
+    let pointer = path_to_pointer(path);
+
+    if old.r#type != new.r#type {
+        changes.push(SchemaChange::TypeChanged {
+            pointer: pointer.clone(),
+            old: old.r#type.to_string(),
+            new: new.r#type.to_string(),
+        });
+    }
+
+    if let (Some(old_enum), Some(new_enum)) = (&old.r#enum, &new.r#enum) {
+        let added: Vec<ConstValue> = new_enum
+            .r#enum
+            .iter()
+            .filter(|v| !old_enum.r#enum.contains(v))
+            .cloned()
+            .collect();
+        let removed: Vec<ConstValue> = old_enum
+            .r#enum
+            .iter()
+            .filter(|v| !new_enum.r#enum.contains(v))
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            changes.push(SchemaChange::EnumChanged {
+                pointer: pointer.clone(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    diff_bounds(old, new, &pointer, changes);
+    diff_properties(old, new, path, changes);
+
+    let old_items = old.array_schema.as_ref().and_then(|a| a.items.as_ref());
+    let new_items = new.array_schema.as_ref().and_then(|a| a.items.as_ref());
+    if let (Some(BooleanOrSchema::Schema(old_items)), Some(BooleanOrSchema::Schema(new_items))) =
+        (old_items, new_items)
+    {
+        path.push("items".to_string());
+        diff_schemas(old_items, new_items, path, changes);
+        path.pop();
+    }
+
+    if let (Some(old_defs), Some(new_defs)) = (&old.defs, &new.defs) {
+        for (name, old_def) in old_defs {
+            if let Some(new_def) = new_defs.get(name) {
+                path.push("$defs".to_string());
+                path.push(name.clone());
+                diff_schemas(old_def, new_def, path, changes);
+                path.truncate(path.len() - 2);
+            }
+        }
+    }
+}
+
+fn diff_properties(
+    old: &Subschema,
+    new: &Subschema,
+    path: &mut Vec<String>,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let (Some(old_object), Some(new_object)) = (&old.object_schema, &new.object_schema) else {
+        return;
+    };
+    let is_required = |required: &Option<Vec<String>>, name: &str| {
+        required
+            .as_ref()
+            .is_some_and(|required| required.iter().any(|r| r == name))
+    };
+    let empty = Default::default();
+    let old_properties = old_object.properties.as_ref().unwrap_or(&empty);
+    let new_properties = new_object.properties.as_ref().unwrap_or(&empty);
+
+    for (name, old_property) in old_properties {
+        path.push("properties".to_string());
+        path.push(name.clone());
+        match new_properties.get(name) {
+            Some(new_property) => {
+                let was_required = is_required(&old_object.required, name);
+                let required = is_required(&new_object.required, name);
+                let pointer = path_to_pointer(path);
+                if required && !was_required {
+                    changes.push(SchemaChange::RequiredAdded {
+                        pointer,
+                        name: name.clone(),
+                    });
+                } else if was_required && !required {
+                    changes.push(SchemaChange::RequiredRemoved {
+                        pointer,
+                        name: name.clone(),
+                    });
+                }
+                diff_schemas(old_property, new_property, path, changes);
+            }
+            None => changes.push(SchemaChange::PropertyRemoved {
+                pointer: path_to_pointer(path),
+                name: name.clone(),
+                was_required: is_required(&old_object.required, name),
+            }),
+        }
+        path.truncate(path.len() - 2);
+    }
+
+    for name in new_properties.keys() {
+        if !old_properties.contains_key(name) {
+            path.push("properties".to_string());
+            path.push(name.clone());
+            changes.push(SchemaChange::PropertyAdded {
+                pointer: path_to_pointer(path),
+                name: name.clone(),
+                required: is_required(&new_object.required, name),
+            });
+            path.truncate(path.len() - 2);
+        }
+    }
+}
+
+fn diff_bounds(old: &Subschema, new: &Subschema, pointer: &str, changes: &mut Vec<SchemaChange>) {
+    let numeric_bounds = |s: &Subschema| -> Option<NumericBounds> {
+        let mut bounds: NumericBounds = s
+            .integer_schema
+            .as_ref()
+            .map(|i| &i.bounds)
+            .or_else(|| s.number_schema.as_ref().map(|n| &n.bounds))?
+            .clone();
+        // A draft-04 boolean `exclusiveMinimum`/`exclusiveMaximum` makes the bound exclusive, so
+        // compare it as the numeric form
+        if bounds.draft04_exclusive_minimum == Some(true) {
+            bounds.exclusive_minimum = bounds.minimum.take();
+        }
+        if bounds.draft04_exclusive_maximum == Some(true) {
+            bounds.exclusive_maximum = bounds.maximum.take();
+        }
+        Some(bounds)
+    };
+    let old_bounds = numeric_bounds(old).unwrap_or_default();
+    let new_bounds = numeric_bounds(new).unwrap_or_default();
+    let f64_of = |n: Option<Number>| n.map(Number::to_f64);
+    compare_bound(
+        "minimum",
+        true,
+        f64_of(old_bounds.minimum),
+        f64_of(new_bounds.minimum),
+        pointer,
+        changes,
+    );
+    compare_bound(
+        "maximum",
+        false,
+        f64_of(old_bounds.maximum),
+        f64_of(new_bounds.maximum),
+        pointer,
+        changes,
+    );
+    compare_bound(
+        "exclusiveMinimum",
+        true,
+        f64_of(old_bounds.exclusive_minimum),
+        f64_of(new_bounds.exclusive_minimum),
+        pointer,
+        changes,
+    );
+    compare_bound(
+        "exclusiveMaximum",
+        false,
+        f64_of(old_bounds.exclusive_maximum),
+        f64_of(new_bounds.exclusive_maximum),
+        pointer,
+        changes,
+    );
+    compare_multiple_of(
+        old_bounds.multiple_of,
+        new_bounds.multiple_of,
+        pointer,
+        changes,
+    );
+
+    let patterns = |s: &Subschema| {
+        s.string_schema
+            .as_ref()
+            .and_then(|s| s.pattern.as_ref())
+            .map(|p| p.as_str().to_string())
+    };
+    compare_pattern(patterns(old), patterns(new), pointer, changes);
+
+    let lengths = |s: &Subschema, min: bool| {
+        s.string_schema
+            .as_ref()
+            .and_then(|s| if min { s.min_length } else { s.max_length })
+            .map(|n| n as f64)
+    };
+    compare_bound(
+        "minLength",
+        true,
+        lengths(old, true),
+        lengths(new, true),
+        pointer,
+        changes,
+    );
+    compare_bound(
+        "maxLength",
+        false,
+        lengths(old, false),
+        lengths(new, false),
+        pointer,
+        changes,
+    );
+
+    let items = |s: &Subschema, min: bool| {
+        s.array_schema
+            .as_ref()
+            .and_then(|a| if min { a.min_items } else { a.max_items })
+            .map(|n| n as f64)
+    };
+    compare_bound(
+        "minItems",
+        true,
+        items(old, true),
+        items(new, true),
+        pointer,
+        changes,
+    );
+    compare_bound(
+        "maxItems",
+        false,
+        items(old, false),
+        items(new, false),
+        pointer,
+        changes,
+    );
+
+    let properties = |s: &Subschema, min: bool| {
+        s.object_schema
+            .as_ref()
+            .and_then(|o| {
+                if min {
+                    o.min_properties
+                } else {
+                    o.max_properties
+                }
+            })
+            .map(|n| n as f64)
+    };
+    compare_bound(
+        "minProperties",
+        true,
+        properties(old, true),
+        properties(new, true),
+        pointer,
+        changes,
+    );
+    compare_bound(
+        "maxProperties",
+        false,
+        properties(old, false),
+        properties(new, false),
+        pointer,
+        changes,
+    );
+}
+
+/// Record whether a lower (`is_min`) or upper bound got tighter or looser
+fn compare_bound(
+    keyword: &'static str,
+    is_min: bool,
+    old: Option<f64>,
+    new: Option<f64>,
+    pointer: &str,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let tightened = match (old, new) {
+        (None, Some(_)) => true,
+        (Some(_), None) => false,
+        (Some(old), Some(new)) if old == new => return,
+        (Some(old), Some(new)) => (new > old) == is_min,
+        (None, None) => return,
+    };
+    if tightened {
+        changes.push(SchemaChange::ConstraintTightened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: old.map(|n| n.to_string()),
+            new: new.map(|n| n.to_string()).unwrap_or_default(),
+        });
+    } else {
+        changes.push(SchemaChange::ConstraintLoosened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: old.map(|n| n.to_string()).unwrap_or_default(),
+            new: new.map(|n| n.to_string()),
+        });
+    }
+}
+
+/// Record a `multipleOf` change: a multiple of the old divisor accepts fewer values, a divisor
+/// of it more, and any other change both
+fn compare_multiple_of(
+    old: Option<Number>,
+    new: Option<Number>,
+    pointer: &str,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let keyword = "multipleOf";
+    let change = match (old, new) {
+        (None, None) => return,
+        (Some(old), Some(new)) if old.to_f64() == new.to_f64() => return,
+        (None, Some(new)) => SchemaChange::ConstraintTightened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: None,
+            new: new.to_string(),
+        },
+        (Some(old), None) => SchemaChange::ConstraintLoosened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: old.to_string(),
+            new: None,
+        },
+        (Some(old), Some(new)) if new.is_multiple_of(old) => SchemaChange::ConstraintTightened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: Some(old.to_string()),
+            new: new.to_string(),
+        },
+        (Some(old), Some(new)) if old.is_multiple_of(new) => SchemaChange::ConstraintLoosened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: old.to_string(),
+            new: Some(new.to_string()),
+        },
+        (old, new) => SchemaChange::ConstraintChanged {
+            pointer: pointer.to_string(),
+            keyword,
+            old: old.map(|n| n.to_string()),
+            new: new.map(|n| n.to_string()),
+        },
+    };
+    changes.push(change);
+}
+
+/// Record a `pattern` change. Whether one regular expression accepts fewer strings than another
+/// isn't worked out, so a replaced pattern is reported as changed.
+fn compare_pattern(
+    old: Option<String>,
+    new: Option<String>,
+    pointer: &str,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let keyword = "pattern";
+    let change = match (old, new) {
+        (old, new) if old == new => return,
+        (None, Some(new)) => SchemaChange::ConstraintTightened {
+            pointer: pointer.to_string(),
+            keyword,
+            old: None,
+            new,
+        },
+        (Some(old), None) => SchemaChange::ConstraintLoosened {
+            pointer: pointer.to_string(),
+            keyword,
+            old,
+            new: None,
+        },
+        (old, new) => SchemaChange::ConstraintChanged {
+            pointer: pointer.to_string(),
+            keyword,
+            old,
+            new,
+        },
+    };
+    changes.push(change);
+}
+
+/// How sure the report is that a removed and an added property are the same property renamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameConfidence {
+    /// The subschemas are identical, including a non-empty `description`
+    Likely,
+    /// The `type` and `description` match, but the subschemas differ or have no description
+    Uncertain,
+}
+
+/// A property that appears to have been renamed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+    pub confidence: RenameConfidence,
+}
+
+/// A checklist of what consumers must change to move from one schema version to the next
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationReport {
+    /// Pointers to properties that must now be present
+    pub new_required: Vec<String>,
+    /// Pointers to properties that no longer exist
+    pub removed: Vec<String>,
+    pub renamed: Vec<Rename>,
+    /// `enum`s that gained or lost values
+    pub enum_changes: Vec<SchemaChange>,
+    /// Bounds that now accept fewer values, and `type` changes
+    pub tightened: Vec<SchemaChange>,
+    /// Constraints such as `pattern` that were replaced, so documents must be re-checked
+    pub changed: Vec<SchemaChange>,
+}
+
+/// Build a [`MigrationReport`] from the [`diff`] of two schema versions.
+///
+/// A removed and an added property under the same object are reported as a rename only if
+/// their `type` and `description` match and no other removed or added property there matches
+/// either of them.
+pub fn migration_report(old: &RootSchema, new: &RootSchema) -> MigrationReport {
+    let changes = diff(old, new);
+    let mut report = MigrationReport::default();
+
+    let mut added: Vec<(&String, bool)> = Vec::new();
+    let mut removed: Vec<&String> = Vec::new();
+    for change in &changes {
+        match change {
+            SchemaChange::PropertyAdded {
+                pointer, required, ..
+            } => added.push((pointer, *required)),
+            SchemaChange::PropertyRemoved { pointer, .. } => removed.push(pointer),
+            SchemaChange::RequiredAdded { pointer, .. } => {
+                report.new_required.push(pointer.clone())
+            }
+            SchemaChange::EnumChanged { .. } => report.enum_changes.push(change.clone()),
+            SchemaChange::TypeChanged { .. } | SchemaChange::ConstraintTightened { .. } => {
+                report.tightened.push(change.clone())
+            }
+            SchemaChange::ConstraintChanged { .. } => report.changed.push(change.clone()),
+            SchemaChange::RequiredRemoved { .. } | SchemaChange::ConstraintLoosened { .. } => {}
+        }
+    }
+
+    let renames: Vec<(&String, &String, RenameConfidence)> = removed
+        .iter()
+        .filter_map(|from| {
+            let candidates: Vec<_> = added
+                .iter()
+                .filter_map(|(to, _)| rename_confidence(old, new, from, to).map(|c| (*to, c)))
+                .collect();
+            let [(to, confidence)] = candidates[..] else {
+                return None;
+            };
+            let rivals = removed
+                .iter()
+                .filter(|other| rename_confidence(old, new, other, to).is_some())
+                .count();
+            (rivals == 1).then_some((*from, to, confidence))
+        })
+        .collect();
+
+    for from in removed {
+        if let Some((_, to, confidence)) = renames.iter().find(|(f, _, _)| *f == from) {
+            report.renamed.push(Rename {
+                from: from.clone(),
+                to: (*to).clone(),
+                confidence: *confidence,
+            });
+        } else {
+            report.removed.push(from.clone());
+        }
+    }
+    for (to, required) in added {
+        let renamed_from = renames.iter().find(|(_, t, _)| *t == to).map(|r| r.0);
+        let was_required = renamed_from.is_some_and(|from| is_required_at(old, from));
+        if required && !was_required {
+            report.new_required.push(to.clone());
+        }
+    }
+    report
+}
+
+/// Whether the property at `from` in `old` looks like the property at `to` in `new`
+fn rename_confidence(
+    old: &RootSchema,
+    new: &RootSchema,
+    from: &str,
+    to: &str,
+) -> Option<RenameConfidence> {
+    if parent_pointer(from) != parent_pointer(to) {
+        return None;
+    }
+    let old_schema = old.resolve_fragment(from)?;
+    let new_schema = new.resolve_fragment(to)?;
+    let (YamlSchema::Subschema(old_sub), YamlSchema::Subschema(new_sub)) = (old_schema, new_schema)
+    else {
+        return None;
+    };
+    let description = &old_sub.metadata_and_annotations.description;
+    if old_sub.r#type != new_sub.r#type
+        || *description != new_sub.metadata_and_annotations.description
+    {
+        return None;
+    }
+    if old_schema == new_schema && description.as_ref().is_some_and(|d| !d.is_empty()) {
+        Some(RenameConfidence::Likely)
+    } else if description.is_some() || old_schema == new_schema {
+        Some(RenameConfidence::Uncertain)
+    } else {
+        None
+    }
+}
+
+fn parent_pointer(pointer: &str) -> &str {
+    pointer.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+/// Whether the property at the given `.../properties/<name>` pointer is required by its parent
+fn is_required_at(root: &RootSchema, pointer: &str) -> bool {
+    let Some((properties, name)) = pointer.rsplit_once('/') else {
+        return false;
+    };
+    let parent = properties.strip_suffix("/properties").unwrap_or(properties);
+    let name = name.replace("~1", "/").replace("~0", "~");
+    match root.resolve_fragment(parent) {
+        Some(YamlSchema::Subschema(parent)) => parent
+            .object_schema
+            .as_ref()
+            .and_then(|o| o.required.as_ref())
+            .is_some_and(|required| required.contains(&name)),
+        _ => false,
+    }
+}
+
+impl MigrationReport {
+    pub fn is_empty(&self) -> bool {
+        self.new_required.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.enum_changes.is_empty()
+            && self.tightened.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// Render the report as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Schema migration\n");
+        if self.is_empty() {
+            out.push_str("\nNo changes require migration.\n");
+            return out;
+        }
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                let _ = write!(out, "\n## {title}\n\n");
+                for line in lines {
+                    let _ = writeln!(out, "- {line}");
+                }
+            }
+        };
+        section(
+            "New required properties",
+            self.new_required.iter().map(|p| format!("`{p}`")).collect(),
+        );
+        section(
+            "Removed properties",
+            self.removed.iter().map(|p| format!("`{p}`")).collect(),
+        );
+        section(
+            "Renamed properties",
+            self.renamed
+                .iter()
+                .map(|r| match r.confidence {
+                    RenameConfidence::Likely => format!("`{}` → `{}`", r.from, r.to),
+                    RenameConfidence::Uncertain => {
+                        format!("`{}` → `{}` (uncertain, please verify)", r.from, r.to)
+                    }
+                })
+                .collect(),
+        );
+        section(
+            "Enum changes",
+            self.enum_changes.iter().map(describe_change).collect(),
+        );
+        section(
+            "Tightened constraints",
+            self.tightened.iter().map(describe_change).collect(),
+        );
+        section(
+            "Changed constraints (re-check documents)",
+            self.changed.iter().map(describe_change).collect(),
+        );
+        out
+    }
+}
+
+fn describe_change(change: &SchemaChange) -> String {
+    match change {
+        SchemaChange::EnumChanged {
+            pointer,
+            added,
+            removed,
+        } => {
+            let mut parts = Vec::new();
+            if !removed.is_empty() {
+                parts.push(format!("removed {}", format_values(removed)));
+            }
+            if !added.is_empty() {
+                parts.push(format!("added {}", format_values(added)));
+            }
+            format!("`{pointer}`: {}", parts.join("; "))
+        }
+        SchemaChange::TypeChanged { pointer, old, new } => {
+            format!("`{pointer}`: `type` {old} → {new}")
+        }
+        SchemaChange::ConstraintTightened {
+            pointer,
+            keyword,
+            old,
+            new,
+        } => match old {
+            Some(old) => format!("`{pointer}`: `{keyword}` {old} → {new}"),
+            None => format!("`{pointer}`: new `{keyword}` {new}"),
+        },
+        SchemaChange::ConstraintChanged {
+            pointer,
+            keyword,
+            old,
+            new,
+        } => format!(
+            "`{pointer}`: `{keyword}` {} → {}",
+            old.as_deref().unwrap_or("none"),
+            new.as_deref().unwrap_or("none")
+        ),
+        other => format!("{other:?}"),
+    }
+}
+
+fn format_values(values: &[ConstValue]) -> String {
+    values
+        .iter()
+        .map(|v| format!("`{v}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loader;
+
+    use super::*;
+
+    fn report(old: &str, new: &str) -> MigrationReport {
+        migration_report(
+            &loader::load_from_str(old).unwrap(),
+            &loader::load_from_str(new).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_added_required_property() {
+        let report = report(
+            "type: object\nproperties:\n  name:\n    type: string",
+            "type: object\nproperties:\n  name:\n    type: string\n  email:\n    type: string\nrequired: [email]",
+        );
+        assert_eq!(report.new_required, vec!["/properties/email"]);
+        assert!(report.removed.is_empty());
+        assert!(report.renamed.is_empty());
+        assert!(
+            report
+                .to_markdown()
+                .contains("## New required properties\n\n- `/properties/email`\n"),
+            "{}",
+            report.to_markdown()
+        );
+    }
+
+    #[test]
+    fn test_removed_property() {
+        let report = report(
+            "type: object\nproperties:\n  name:\n    type: string\n  age:\n    type: integer",
+            "type: object\nproperties:\n  name:\n    type: string",
+        );
+        assert_eq!(report.removed, vec!["/properties/age"]);
+        assert!(report.new_required.is_empty());
+        assert!(report.renamed.is_empty());
+    }
+
+    #[test]
+    fn test_renamed_property_with_identical_subschema() {
+        let old = r#"
+            type: object
+            properties:
+              name:
+                type: string
+                description: The user's display name
+            required: [name]
+            "#;
+        let new = r#"
+            type: object
+            properties:
+              display_name:
+                type: string
+                description: The user's display name
+            required: [display_name]
+            "#;
+        let report = report(old, new);
+        assert_eq!(
+            report.renamed,
+            vec![Rename {
+                from: "/properties/name".to_string(),
+                to: "/properties/display_name".to_string(),
+                confidence: RenameConfidence::Likely,
+            }]
+        );
+        assert!(report.removed.is_empty());
+        assert!(report.new_required.is_empty());
+        assert!(
+            report
+                .to_markdown()
+                .contains("- `/properties/name` → `/properties/display_name`\n")
+        );
+    }
+
+    #[test]
+    fn test_rename_without_description_is_uncertain() {
+        let report = report(
+            "type: object\nproperties:\n  name:\n    type: string",
+            "type: object\nproperties:\n  title:\n    type: string",
+        );
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].confidence, RenameConfidence::Uncertain);
+        assert!(report.to_markdown().contains("(uncertain, please verify)"));
+    }
+
+    #[test]
+    fn test_ambiguous_rename_is_not_guessed() {
+        let report = report(
+            "type: object\nproperties:\n  a:\n    type: string\n  b:\n    type: string",
+            "type: object\nproperties:\n  c:\n    type: string",
+        );
+        assert!(report.renamed.is_empty());
+        assert_eq!(report.removed, vec!["/properties/a", "/properties/b"]);
+    }
+
+    #[test]
+    fn test_enum_narrowing() {
+        let old = "type: object\nproperties:\n  status:\n    enum: [active, inactive, archived]";
+        let new = "type: object\nproperties:\n  status:\n    enum: [active, inactive]";
+        let report = report(old, new);
+        assert_eq!(
+            report.enum_changes,
+            vec![SchemaChange::EnumChanged {
+                pointer: "/properties/status".to_string(),
+                added: vec![],
+                removed: vec![ConstValue::String("archived".to_string())],
+            }]
+        );
+        assert!(
+            report
+                .to_markdown()
+                .contains("## Enum changes\n\n- `/properties/status`: removed `\"archived\"`\n")
+        );
+    }
+
+    #[test]
+    fn test_tightened_constraints() {
+        let old = "type: object\nproperties:\n  age:\n    type: integer\n    minimum: 0";
+        let new = "type: object\nproperties:\n  age:\n    type: integer\n    minimum: 18\n    maximum: 150";
+        let report = report(old, new);
+        assert_eq!(report.tightened.len(), 2);
+        let markdown = report.to_markdown();
+        assert!(
+            markdown.contains("- `/properties/age`: `minimum` 0 → 18\n"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("- `/properties/age`: new `maximum` 150\n"),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn test_exclusive_bounds_and_multiple_of() {
+        let old = "type: number\nexclusiveMinimum: 0\nmultipleOf: 2";
+        let new = "type: number\nexclusiveMinimum: 1\nexclusiveMaximum: 10\nmultipleOf: 4";
+        let report = report(old, new);
+        let markdown = report.to_markdown();
+        assert_eq!(report.tightened.len(), 3, "{markdown}");
+        assert!(
+            markdown.contains("- ``: `exclusiveMinimum` 0 → 1\n"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("- ``: new `exclusiveMaximum` 10\n"),
+            "{markdown}"
+        );
+        assert!(
+            markdown.contains("- ``: `multipleOf` 2 → 4\n"),
+            "{markdown}"
+        );
+
+        let loosened = diff(
+            &loader::load_from_str("type: integer\nmultipleOf: 4").unwrap(),
+            &loader::load_from_str("type: integer\nmultipleOf: 2").unwrap(),
+        );
+        assert!(
+            matches!(
+                loosened[..],
+                [SchemaChange::ConstraintLoosened {
+                    keyword: "multipleOf",
+                    ..
+                }]
+            ),
+            "{loosened:?}"
+        );
+    }
+
+    #[test]
+    fn test_replaced_pattern_is_changed() {
+        let old = "type: object\nproperties:\n  id:\n    type: string\n    pattern: '^[a-z]+$'";
+        let new = "type: object\nproperties:\n  id:\n    type: string\n    pattern: '^[0-9]+$'";
+        let report = report(old, new);
+        assert!(report.tightened.is_empty());
+        assert_eq!(report.changed.len(), 1);
+        assert!(
+            report.to_markdown().contains(
+                "## Changed constraints (re-check documents)\n\n- `/properties/id`: `pattern` ^[a-z]+$ → ^[0-9]+$\n"
+            ),
+            "{}",
+            report.to_markdown()
+        );
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let schema = "type: object\nproperties:\n  name:\n    type: string";
+        let report = report(schema, schema);
+        assert!(report.is_empty());
+        assert_eq!(
+            report.to_markdown(),
+            "# Schema migration\n\nNo changes require migration.\n"
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Validating every YAML file under a directory against one schema, see
+//! [`Engine::evaluate_dir`](crate::Engine::evaluate_dir).
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+use crate::Context;
+use crate::Engine;
+use crate::Error;
+use crate::Result;
+use crate::RootSchema;
+use crate::ValidationOptions;
+use crate::loader::read_to_string;
+use crate::utils::glob_matches;
+
+/// The files validated when no `include` globs are given
+const DEFAULT_INCLUDE: [&str; 2] = ["**/*.yaml", "**/*.yml"];
+
+/// Options for [`Engine::evaluate_dir`](crate::Engine::evaluate_dir).
+///
+/// Globs are matched against file paths relative to the directory, with `/` separators: `*`
+/// and `?` match within one path segment, and `**` matches any number of segments, e.g.
+/// `**/*.yaml` or `**/generated/**`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirOptions {
+    /// Validate files matching any of these globs; all `.yaml` and `.yml` files when empty
+    pub include: Vec<String>,
+    /// Skip files matching any of these globs, even if they are included
+    pub exclude: Vec<String>,
+    /// Follow symbolic links to files and directories. Off by default, so links are skipped.
+    pub follow_symlinks: bool,
+    /// Validate up to this many files at once; `0` uses the available parallelism
+    pub max_threads: usize,
+    /// Record YAML syntax errors and files that can't be read as UTF-8 text as a document error on
+    /// the file's context and carry on, rather than failing the whole run
+    pub continue_on_parse_error: bool,
+    /// How each file is validated
+    pub validation: ValidationOptions,
+}
+
+impl DirOptions {
+    pub fn include<S: Into<String>>(mut self, glob: S) -> Self {
+        self.include.push(glob.into());
+        self
+    }
+
+    pub fn exclude<S: Into<String>>(mut self, glob: S) -> Self {
+        self.exclude.push(glob.into());
+        self
+    }
+
+    pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    pub fn max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = max_threads;
+        self
+    }
+
+    pub fn continue_on_parse_error(mut self, continue_on_parse_error: bool) -> Self {
+        self.continue_on_parse_error = continue_on_parse_error;
+        self
+    }
+
+    pub fn validation(mut self, validation: ValidationOptions) -> Self {
+        self.validation = validation;
+        self
+    }
+}
+
+/// Validate the files under `root` selected by `options`, see
+/// [`Engine::evaluate_dir`](crate::Engine::evaluate_dir)
+pub fn evaluate_dir<'r>(
+    root_schema: &'r RootSchema,
+    root: &Path,
+    options: &DirOptions,
+) -> Result<Vec<(PathBuf, Context<'r>)>> {
+    let include = if options.include.is_empty() {
+        split_globs(DEFAULT_INCLUDE)
+    } else {
+        split_globs(&options.include)
+    };
+    let exclude = split_globs(&options.exclude);
+
+    let mut files = Vec::new();
+    let mut ancestors = HashSet::new();
+    if options.follow_symlinks {
+        ancestors.insert(fs::canonicalize(root)?);
+    }
+    collect_files(
+        root,
+        "",
+        options.follow_symlinks,
+        &mut ancestors,
+        &mut |relative, path| {
+            let segments: Vec<&str> = relative.split('/').collect();
+            if include.iter().any(|glob| glob_matches(glob, &segments))
+                && !exclude.iter().any(|glob| glob_matches(glob, &segments))
+            {
+                files.push(path);
+            }
+        },
+    )?;
+    files.sort();
+
+    let outcomes = validate_files(root_schema, &files, options);
+    let mut results = Vec::with_capacity(files.len());
+    for (path, outcome) in files.into_iter().zip(outcomes) {
+        let message = match outcome {
+            Ok(context) => {
+                results.push((path, context));
+                continue;
+            }
+            Err(Error::YamlParsingError(e)) if options.continue_on_parse_error => {
+                format!("Invalid YAML: {e}")
+            }
+            Err(e @ (Error::InvalidDirective(_) | Error::ReadError { .. }))
+                if options.continue_on_parse_error =>
+            {
+                e.to_string()
+            }
+            Err(Error::YamlParsingError(e)) => {
+                return Err(generic_error!("{}: {}", path.display(), e));
+            }
+            Err(e) => return Err(e),
+        };
+        let context =
+            Engine::context_with_options(root_schema, &options.validation, HashMap::new());
+        context.add_doc_error(message);
+        results.push((path, context));
+    }
+    Ok(results)
+}
+
+/// Validate `files` on up to `options.max_threads` threads, returning the context of each file in
+/// the same order as `files`
+fn validate_files<'r>(
+    root_schema: &'r RootSchema,
+    files: &[PathBuf],
+    options: &DirOptions,
+) -> Vec<Result<Context<'r>>> {
+    let threads = match options.max_threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .clamp(1, files.len().max(1));
+    let next = AtomicUsize::new(0);
+    let mut outcomes: Vec<(usize, Result<Context<'r>>)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outcomes = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = files.get(index) else {
+                            break;
+                        };
+                        outcomes.push((index, validate_file(root_schema, path, options)));
+                    }
+                    outcomes
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+fn validate_file<'r>(
+    root_schema: &'r RootSchema,
+    path: &Path,
+    options: &DirOptions,
+) -> Result<Context<'r>> {
+    // Unlike `fs::read_to_string`, fails with `Error::ReadError` for text that isn't UTF-8
+    let text = read_to_string(fs::File::open(path)?, None)?;
+    Engine::evaluate_with_options(root_schema, &text, &options.validation)
+}
+
+/// Call `found` with the relative path (using `/` separators) and full path of each file under
+/// `dir`. When following symbolic links, `ancestors` holds the canonical paths of the directories
+/// being walked, so links back up the tree aren't walked again.
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    follow_symlinks: bool,
+    ancestors: &mut HashSet<PathBuf>,
+    found: &mut dyn FnMut(&str, PathBuf),
+) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = format!("{prefix}{}", entry.file_name().to_string_lossy());
+        let mut file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            if !follow_symlinks {
+                continue;
+            }
+            // Broken links are skipped
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            file_type = metadata.file_type();
+        }
+        if file_type.is_dir() {
+            let canonical = if follow_symlinks {
+                let canonical = fs::canonicalize(&path)?;
+                if !ancestors.insert(canonical.clone()) {
+                    continue;
+                }
+                Some(canonical)
+            } else {
+                None
+            };
+            collect_files(
+                &path,
+                &format!("{relative}/"),
+                follow_symlinks,
+                ancestors,
+                found,
+            )?;
+            if let Some(canonical) = canonical {
+                ancestors.remove(&canonical);
+            }
+        } else if file_type.is_file() {
+            found(&relative, path);
+        }
+    }
+    Ok(())
+}
+
+/// Split globs into their `/`-separated segments, see [`glob_matches`]
+fn split_globs<I, S>(globs: I) -> Vec<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    globs
+        .into_iter()
+        .map(|glob| glob.as_ref().split('/').map(String::from).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::loader;
+
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        type: object
+        properties:
+          name:
+            type: string
+          port:
+            type: integer
+            minimum: 1
+        required: [name]
+    "#;
+
+    /// A directory tree with valid, invalid, excluded, ignored, and malformed files
+    fn config_tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("a.yaml", "name: a\nport: 80\n"),
+            ("b.yml", "name: b\nport: 0\n"),
+            ("broken.yaml", "name: [unclosed\n"),
+            ("notes.txt", "not: [yaml\n"),
+            ("nested/c.yaml", "port: 8080\n"),
+            ("nested/generated/d.yaml", "port: -1\n"),
+        ];
+        for (name, text) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        dir
+    }
+
+    /// Each file's path relative to `root` with its error messages
+    fn summarize(root: &Path, results: &[(PathBuf, Context)]) -> Vec<(String, Vec<String>)> {
+        results
+            .iter()
+            .map(|(path, context)| {
+                let relative = path.strip_prefix(root).unwrap();
+                let errors = context.errors.borrow();
+                (
+                    relative.to_string_lossy().replace('\\', "/"),
+                    errors.iter().map(|e| e.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_dir() {
+        let root_schema = loader::load_from_str(SCHEMA).unwrap();
+        let dir = config_tree();
+        let options = DirOptions::default()
+            .exclude("**/generated/**")
+            .continue_on_parse_error(true);
+
+        let results =
+            Engine::evaluate_dir(&root_schema, dir.path(), &options.clone().max_threads(4))
+                .unwrap();
+        let summary = summarize(dir.path(), &results);
+        let paths: Vec<&str> = summary.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(paths, ["a.yaml", "b.yml", "broken.yaml", "nested/c.yaml"]);
+        assert!(summary[0].1.is_empty());
+        assert_eq!(summary[1].1.len(), 1);
+        assert!(summary[2].1[0].contains("Invalid YAML"), "{:?}", summary[2]);
+        assert_eq!(
+            summary[3].1,
+            ["[1:1] .: Required property 'name' is missing!"]
+        );
+
+        // The results don't depend on how many files are validated at once
+        for max_threads in [1, 2, 0] {
+            let options = options.clone().max_threads(max_threads);
+            let results = Engine::evaluate_dir(&root_schema, dir.path(), &options).unwrap();
+            assert_eq!(summarize(dir.path(), &results), summary, "{max_threads}");
+        }
+
+        let options = DirOptions::default().include("nested/**").max_threads(2);
+        let results = Engine::evaluate_dir(&root_schema, dir.path(), &options).unwrap();
+        let paths: Vec<String> = summarize(dir.path(), &results)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, ["nested/c.yaml", "nested/generated/d.yaml"]);
+    }
+
+    #[test]
+    fn test_evaluate_dir_parse_errors() {
+        let root_schema = loader::load_from_str(SCHEMA).unwrap();
+        let dir = config_tree();
+        let err = Engine::evaluate_dir(&root_schema, dir.path(), &DirOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("broken.yaml"), "{err}");
+    }
+
+    #[test]
+    fn test_evaluate_dir_keeps_each_files_context() {
+        let root_schema = loader::load_from_str(
+            "title: Config\ntype: object\nproperties:\n  name:\n    type: string\n",
+        )
+        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.yaml"), "name: a\n").unwrap();
+        fs::write(dir.path().join("b.yaml"), b"name: \xff\n").unwrap();
+        let options = DirOptions::default()
+            .continue_on_parse_error(true)
+            .validation(
+                ValidationOptions::default()
+                    .collect_annotations(true)
+                    .count_checks(true),
+            );
+
+        let results = Engine::evaluate_dir(&root_schema, dir.path(), &options).unwrap();
+        let context = &results[0].1;
+        assert!(!context.has_errors());
+        assert_eq!(context.annotations.as_ref().unwrap().borrow().len(), 1);
+        assert_eq!(context.check_counts().unwrap().validators, 2);
+        let errors = results[1].1.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0]
+                .error
+                .starts_with("Error reading YAML after 6 bytes:"),
+            "{}",
+            errors[0]
+        );
+        drop(errors);
+
+        let options = options.continue_on_parse_error(false);
+        assert!(Engine::evaluate_dir(&root_schema, dir.path(), &options).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_evaluate_dir_symlinks() {
+        let root_schema = loader::load_from_str(SCHEMA).unwrap();
+        let dir = config_tree();
+        std::os::unix::fs::symlink(dir.path().join("nested"), dir.path().join("linked")).unwrap();
+        // A link back up the tree is only walked once
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("nested/loop")).unwrap();
+        let options = DirOptions::default()
+            .include("**/c.yaml")
+            .follow_symlinks(true);
+
+        let results = Engine::evaluate_dir(&root_schema, dir.path(), &options).unwrap();
+        let paths: Vec<String> = summarize(dir.path(), &results)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, ["linked/c.yaml", "nested/c.yaml"]);
+
+        let options = options.follow_symlinks(false);
+        let results = Engine::evaluate_dir(&root_schema, dir.path(), &options).unwrap();
+        assert_eq!(results.len(), 1);
+    }
+}
@@ -0,0 +1,248 @@
+//! YAML directives (`%YAML` and `%TAG`) at the start of a YAML stream, and resolving the parsed
+//! document the way they declare
+
+use std::fmt::Display;
+
+use saphyr::MarkedYaml;
+use saphyr::Marker;
+use saphyr::Scalar;
+use saphyr::YamlData;
+
+use crate::Error;
+use crate::Result;
+
+/// A YAML version declared by a `%YAML` directive, e.g. `1.1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YamlVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl YamlVersion {
+    /// Whether documents of this version read plain scalars such as `on` and `no` as booleans,
+    /// like YAML 1.0 and 1.1 did
+    pub fn has_yaml_1_1_booleans(&self) -> bool {
+        self.major == 1 && self.minor < 2
+    }
+}
+
+impl Display for YamlVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// The directives before the first document of a YAML stream
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Directives {
+    /// The version declared by `%YAML`, if any
+    pub yaml_version: Option<YamlVersion>,
+    /// The `(handle, prefix)` of each `%TAG`, e.g. `("!e!", "tag:example.com,2000:")`
+    pub tags: Vec<(String, String)>,
+}
+
+/// Scan the directives before the first document of `s`, failing with
+/// [`Error::InvalidDirective`] on a YAML version other than 1.x, on directives other than
+/// `%YAML` and `%TAG`, and on directives that aren't followed by a `---` document start marker.
+///
+/// Malformed `%YAML` and `%TAG` directives are left for the YAML parser to report.
+pub fn scan_directives(s: &str) -> Result<Directives> {
+    let mut directives = Directives::default();
+    let mut seen_directive = false;
+    for (index, line) in s.lines().enumerate() {
+        let marker = format!("[{}, 0]", index + 1);
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let Some(directive) = line.strip_prefix('%') else {
+            if seen_directive && !line.starts_with("---") {
+                return Err(Error::InvalidDirective(format!(
+                    "{marker} Directives must be followed by a '---' document start marker"
+                )));
+            }
+            break;
+        };
+        seen_directive = true;
+        let mut parts = directive.split_whitespace();
+        match parts.next() {
+            Some("YAML") => {
+                let Some((major, minor)) = parts.next().and_then(|v| v.split_once('.')) else {
+                    continue;
+                };
+                let (Ok(major), Ok(minor)) = (major.parse(), minor.parse()) else {
+                    continue;
+                };
+                let version = YamlVersion { major, minor };
+                if major != 1 {
+                    return Err(Error::InvalidDirective(format!(
+                        "{marker} Unsupported YAML version {version}, expected 1.x"
+                    )));
+                }
+                directives.yaml_version = Some(version);
+            }
+            Some("TAG") => {
+                if let (Some(handle), Some(prefix)) = (parts.next(), parts.next()) {
+                    directives
+                        .tags
+                        .push((handle.to_string(), prefix.to_string()));
+                }
+            }
+            name => {
+                return Err(Error::InvalidDirective(format!(
+                    "{marker} Unknown directive '%{}', expected %YAML or %TAG",
+                    name.unwrap_or_default()
+                )));
+            }
+        }
+    }
+    Ok(directives)
+}
+
+/// Resolve the parsed document `doc` of the YAML text `source` for validation: nodes with a
+/// custom tag (e.g. `!e!point` after `%TAG !e! ...`) are validated by their content, and with
+/// `yaml_1_1_booleans`, plain scalar values such as `on`, `off`, `yes` and `no` are booleans.
+/// Mapping keys are left as they are, so `on:` remains the key `on`.
+pub fn resolve_document<'a>(
+    source: &str,
+    doc: MarkedYaml<'a>,
+    yaml_1_1_booleans: bool,
+) -> MarkedYaml<'a> {
+    let lines: Vec<&str> = if yaml_1_1_booleans {
+        source.lines().collect()
+    } else {
+        Vec::new()
+    };
+    resolve_node(&lines, doc, yaml_1_1_booleans)
+}
+
+fn resolve_node<'a>(
+    lines: &[&str],
+    node: MarkedYaml<'a>,
+    yaml_1_1_booleans: bool,
+) -> MarkedYaml<'a> {
+    let span = node.span;
+    let data = match node.data {
+        // The tagged content has an empty span, so keep the tagged node's
+        YamlData::Tagged(_, content) => {
+            let content = resolve_node(lines, *content, yaml_1_1_booleans);
+            return MarkedYaml {
+                span,
+                data: content.data,
+            };
+        }
+        YamlData::Sequence(items) => YamlData::Sequence(
+            items
+                .into_iter()
+                .map(|item| resolve_node(lines, item, yaml_1_1_booleans))
+                .collect(),
+        ),
+        YamlData::Mapping(mapping) => YamlData::Mapping(
+            mapping
+                .into_iter()
+                .map(|(key, value)| {
+                    (
+                        resolve_node(lines, key, false),
+                        resolve_node(lines, value, yaml_1_1_booleans),
+                    )
+                })
+                .collect(),
+        ),
+        YamlData::Value(Scalar::String(s)) if yaml_1_1_booleans => match yaml_1_1_boolean(&s) {
+            Some(b) if is_plain_untagged(lines, span.start, span.end, &s) => {
+                YamlData::Value(Scalar::Boolean(b))
+            }
+            _ => YamlData::Value(Scalar::String(s)),
+        },
+        data => data,
+    };
+    MarkedYaml { span, data }
+}
+
+/// The value of a YAML 1.1 boolean such as `yes` or `OFF`
+fn yaml_1_1_boolean(s: &str) -> Option<bool> {
+    match s {
+        "y" | "Y" | "yes" | "Yes" | "YES" | "on" | "On" | "ON" => Some(true),
+        "n" | "N" | "no" | "No" | "NO" | "off" | "Off" | "OFF" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether the string scalar `s` from `start` to `end` was written plain, i.e. not quoted (`"on"`) and without
+/// an explicit tag (`!!str on`)
+fn is_plain_untagged(lines: &[&str], start: Marker, end: Marker, s: &str) -> bool {
+    if end.index() - start.index() != s.chars().count() {
+        return false;
+    }
+    let Some(line) = start
+        .line()
+        .checked_sub(1)
+        .and_then(|index| lines.get(index))
+    else {
+        return false;
+    };
+    let before: String = line.chars().take(start.col()).collect();
+    !before
+        .split_whitespace()
+        .next_back()
+        .is_some_and(|token| token.starts_with('!'))
+}
+
+#[cfg(test)]
+mod tests {
+    use saphyr::LoadableYamlNode;
+
+    use super::*;
+
+    #[test]
+    fn test_scan_directives() {
+        let directives =
+            scan_directives("# comment\n%YAML 1.1\n%TAG !e! tag:example.com,2000:\n---\na: 1\n")
+                .unwrap();
+        assert_eq!(
+            directives.yaml_version,
+            Some(YamlVersion { major: 1, minor: 1 })
+        );
+        assert_eq!(
+            directives.tags,
+            vec![("!e!".to_string(), "tag:example.com,2000:".to_string())]
+        );
+        assert_eq!(scan_directives("a: 1\n").unwrap(), Directives::default());
+        assert_eq!(scan_directives("--- a\n").unwrap(), Directives::default());
+
+        let error = |s: &str| scan_directives(s).unwrap_err().to_string();
+        assert_eq!(
+            error("%YAML 2.0\n---\na: 1\n"),
+            "Invalid YAML directive: [1, 0] Unsupported YAML version 2.0, expected 1.x"
+        );
+        assert_eq!(
+            error("%FOO bar\n---\na: 1\n"),
+            "Invalid YAML directive: [1, 0] Unknown directive '%FOO', expected %YAML or %TAG"
+        );
+        assert_eq!(
+            error("%YAML 1.1\n\na: 1\n"),
+            "Invalid YAML directive: [3, 0] Directives must be followed by a '---' document start marker"
+        );
+    }
+
+    #[test]
+    fn test_resolve_document() {
+        let source = "%TAG !e! tag:example.com,2000:\n---\non: on\nb: [off, \"off\", !!str off, Yes]\nc: !e!point {x: no}\nd: true\n";
+        let doc = MarkedYaml::load_from_str(source).unwrap().remove(0);
+        let resolved = resolve_document(source, doc.clone(), true);
+        // The tagged mapping keeps its position, after the tag
+        assert_eq!(
+            crate::utils::format_yaml_data(&resolved.data),
+            "{ \"on\": [3, 4] true, \"b\": [4, 3] [[4, 4] false, [4, 9] \"off\", [4, 22] \"off\", \
+             [4, 27] true], \"c\": [5, 12] { \"x\": [5, 16] false }, \"d\": [6, 3] true }"
+        );
+
+        let resolved = resolve_document(source, doc, false);
+        assert_eq!(
+            crate::utils::format_yaml_data(&resolved.data),
+            "{ \"on\": [3, 4] \"on\", \"b\": [4, 3] [[4, 4] \"off\", [4, 9] \"off\", \
+             [4, 22] \"off\", [4, 27] \"Yes\"], \"c\": [5, 12] { \"x\": [5, 16] \"no\" }, \
+             \"d\": [6, 3] true }"
+        );
+    }
+}
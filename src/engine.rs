@@ -1,14 +1,29 @@
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use saphyr::LoadableYamlNode;
 
+use crate::DirOptions;
 use crate::Error;
+use crate::Report;
 use crate::Result;
 use crate::RootSchema;
+use crate::SchemaRegistry;
 use crate::Validator as _;
 use crate::YamlSchema;
+use crate::directives::resolve_document;
+use crate::directives::scan_directives;
+use crate::loader::read_to_string;
+use crate::serialize::to_marked_yaml;
 use crate::validation::Context;
+use crate::validation::ObserverRef;
+use crate::validation::Shared;
+use crate::validation::ValidationObserver;
+use crate::validation::ValidationOptions;
+use crate::validation::ValidationSummary;
 
 #[derive(Debug)]
 pub struct Engine<'a> {
@@ -32,33 +47,238 @@ impl<'a> Engine<'a> {
         Self::evaluate_with_schemas(root_schema, value, fail_fast, HashMap::new())
     }
 
+    /// Evaluate `value` with the given [`ValidationOptions`] and collect the outcome in a
+    /// [`Report`], which serializes to JSON
+    pub fn evaluate_to_report<'b: 'a>(
+        root_schema: &'b RootSchema,
+        value: &str,
+        options: &ValidationOptions,
+    ) -> Result<Report> {
+        Ok(Report::from_context(Self::evaluate_with_options(
+            root_schema,
+            value,
+            options,
+        )?))
+    }
+
+    /// Evaluate the YAML read from `reader`, e.g. standard input
+    pub fn evaluate_reader<'b: 'a, R: Read>(
+        root_schema: &'b RootSchema,
+        reader: R,
+        fail_fast: bool,
+    ) -> Result<Context<'b>> {
+        let options = ValidationOptions::default().fail_fast(fail_fast);
+        Self::evaluate_reader_with_options(root_schema, reader, &options)
+    }
+
+    /// Evaluate the YAML read from `reader` with the given [`ValidationOptions`]. The input is
+    /// read in full before it is parsed; set [`ValidationOptions::max_input_bytes`] to reject
+    /// large inputs without reading all of them.
+    pub fn evaluate_reader_with_options<'b: 'a, R: Read>(
+        root_schema: &'b RootSchema,
+        reader: R,
+        options: &ValidationOptions,
+    ) -> Result<Context<'b>> {
+        let value = read_to_string(reader, options.max_input_bytes)?;
+        Self::evaluate_with_options(root_schema, &value, options)
+    }
+
+    /// Evaluate a Rust value, such as a config struct, as serialized by `serde`, without writing
+    /// it out as YAML first, see [`serialize::to_marked_yaml`](crate::serialize::to_marked_yaml).
+    /// There is no source text, so errors have no marker; their paths locate them.
+    pub fn evaluate_value<'b: 'a, T: serde::Serialize + ?Sized>(
+        root_schema: &'b RootSchema,
+        value: &T,
+        options: &ValidationOptions,
+    ) -> Result<Context<'b>> {
+        let yaml = to_marked_yaml(value)?;
+        let engine = Engine::new(
+            root_schema,
+            Self::context_with_options(root_schema, options, HashMap::new()),
+        );
+        let result = engine.root_schema.validate(&engine.context, &yaml);
+        for error in engine.context.errors.borrow_mut().iter_mut() {
+            error.marker = None;
+        }
+        match result {
+            Ok(()) | Err(Error::FailFast) => Ok(engine.context),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Evaluate with pre-loaded schemas (e.g. from multiple -f flags).
     /// Schemas are keyed by document URI (file:// or https://).
     pub fn evaluate_with_schemas<'b: 'a>(
         root_schema: &'b RootSchema,
         value: &str,
         fail_fast: bool,
-        preloaded_schemas: HashMap<String, Rc<RootSchema>>,
+        preloaded_schemas: HashMap<String, Arc<RootSchema>>,
     ) -> Result<Context<'b>> {
-        let context =
-            Context::with_root_schema_and_schemas(root_schema, fail_fast, preloaded_schemas);
+        let options = ValidationOptions::default().fail_fast(fail_fast);
+        Self::evaluate_with_schemas_and_options(root_schema, value, &options, preloaded_schemas)
+    }
+
+    /// Evaluate with the given [`ValidationOptions`], e.g. to allow `$ref`s to http(s) URLs.
+    pub fn evaluate_with_options<'b: 'a>(
+        root_schema: &'b RootSchema,
+        value: &str,
+        options: &ValidationOptions,
+    ) -> Result<Context<'b>> {
+        Self::evaluate_with_schemas_and_options(root_schema, value, options, HashMap::new())
+    }
+
+    /// Evaluate with pre-loaded schemas and the given [`ValidationOptions`].
+    pub fn evaluate_with_schemas_and_options<'b: 'a>(
+        root_schema: &'b RootSchema,
+        value: &str,
+        options: &ValidationOptions,
+        preloaded_schemas: HashMap<String, Arc<RootSchema>>,
+    ) -> Result<Context<'b>> {
+        Self::evaluate_observed(root_schema, value, options, preloaded_schemas, None)
+    }
+
+    /// Evaluate with the given [`ValidationOptions`], reporting progress to a
+    /// [`ValidationObserver`].
+    pub fn evaluate_with_observer<'b: 'a>(
+        root_schema: &'b RootSchema,
+        value: &str,
+        options: &ValidationOptions,
+        observer: &'b (dyn ValidationObserver + Sync),
+    ) -> Result<Context<'b>> {
+        Self::evaluate_observed(root_schema, value, options, HashMap::new(), Some(observer))
+    }
+
+    /// A fresh context configured by `options`
+    pub(crate) fn context_with_options<'b>(
+        root_schema: &'b RootSchema,
+        options: &ValidationOptions,
+        preloaded_schemas: HashMap<String, Arc<RootSchema>>,
+    ) -> Context<'b> {
+        let mut context = Context::with_root_schema_and_schemas(
+            root_schema,
+            options.fail_fast,
+            preloaded_schemas,
+        );
+        context.options = Arc::new(options.clone());
+        if options.collect_annotations {
+            context.annotations = Some(Shared::default());
+        }
+        if options.count_checks {
+            context.check_counts = Some(Shared::default());
+        }
+        context
+    }
+
+    fn evaluate_observed<'b: 'a>(
+        root_schema: &'b RootSchema,
+        value: &str,
+        options: &ValidationOptions,
+        preloaded_schemas: HashMap<String, Arc<RootSchema>>,
+        observer: Option<&'b (dyn ValidationObserver + Sync)>,
+    ) -> Result<Context<'b>> {
+        let mut context = Self::context_with_options(root_schema, options, preloaded_schemas);
+        context.observer = observer.map(ObserverRef);
+        let directives = scan_directives(value)?;
+        context.yaml_version = directives.yaml_version;
+        let yaml_1_1_booleans = directives
+            .yaml_version
+            .map_or(options.yaml_1_1, |version| version.has_yaml_1_1_booleans());
         let engine = Engine::new(root_schema, context);
         let docs = saphyr::MarkedYaml::load_from_str(value).map_err(Error::YamlParsingError)?;
-        match docs.first() {
-            Some(yaml) => {
-                engine.root_schema.validate(&engine.context, yaml)?;
+        let mut docs: Vec<_> = docs
+            .into_iter()
+            .map(|doc| resolve_document(value, doc, yaml_1_1_booleans))
+            .enumerate()
+            .collect();
+        if options.all_documents {
+            // Empty documents, such as after a trailing `---`, have nothing to validate
+            docs.retain(|(_, doc)| !is_empty_document(value, doc));
+        }
+        let result = match docs.first() {
+            Some(_) if options.all_documents => docs.iter().try_for_each(|(index, yaml)| {
+                let context = engine.context.child_for_document(*index);
+                context.observe_node(None, yaml);
+                engine.root_schema.validate(&context, yaml)?;
+                if context.has_errors() {
+                    fail_fast!(context);
+                }
+                Ok(())
+            }),
+            Some((_, yaml)) => {
+                engine.context.observe_node(None, yaml);
+                engine.root_schema.validate(&engine.context, yaml)
             }
             None => match &engine.root_schema.schema {
-                YamlSchema::Empty | YamlSchema::BooleanLiteral(true) => (),
-                _ => engine
-                    .context
-                    .add_doc_error("Empty YAML document is not allowed"),
+                YamlSchema::Empty | YamlSchema::BooleanLiteral(true) => Ok(()),
+                _ => {
+                    engine
+                        .context
+                        .add_doc_error("Empty YAML document is not allowed");
+                    Ok(())
+                }
             },
+        };
+        if let Some(observer) = observer {
+            observer.on_document_done(ValidationSummary::of(&engine.context.errors.borrow()));
+        }
+        match result {
+            Ok(()) | Err(Error::FailFast) => Ok(engine.context),
+            Err(e) => Err(e),
         }
-        Ok(engine.context)
+    }
+
+    /// Evaluate with the schemas in a [`SchemaRegistry`] available to `$ref` by `$id`.
+    pub fn evaluate_with_registry<'b: 'a>(
+        root_schema: &'b RootSchema,
+        value: &str,
+        options: &ValidationOptions,
+        registry: &SchemaRegistry,
+    ) -> Result<Context<'b>> {
+        Self::evaluate_with_schemas_and_options(root_schema, value, options, registry.to_schemas())
+    }
+
+    /// Validate each YAML file under the `root` directory selected by `options`, by default all
+    /// `.yaml` and `.yml` files. Files are validated in parallel on up to
+    /// [`DirOptions::max_threads`](crate::DirOptions::max_threads) threads, and the results are
+    /// sorted by path.
+    ///
+    /// Fails on the first (in path order) file that can't be read, or that isn't valid YAML
+    /// unless [`DirOptions::continue_on_parse_error`](crate::DirOptions::continue_on_parse_error)
+    /// is set.
+    pub fn evaluate_dir<'b>(
+        root_schema: &'b RootSchema,
+        root: &Path,
+        options: &DirOptions,
+    ) -> Result<Vec<(PathBuf, Context<'b>)>> {
+        crate::dir::evaluate_dir(root_schema, root, options)
+    }
+
+    /// Describe the schemas that apply to the node at `instance_pointer` (a JSON Pointer such as
+    /// `/servers/0/port`, or `""` for the root) in `value`, to help answer "why did this fail?".
+    ///
+    /// Local `$ref`s are followed and each `allOf`/`anyOf`/`oneOf` branch and `if`/`then`/`else`
+    /// schema is listed separately with a label, along with the keywords it checks. Branches are
+    /// listed whether or not the node matches them.
+    pub fn explain(
+        root_schema: &RootSchema,
+        value: &saphyr::MarkedYaml,
+        instance_pointer: &str,
+    ) -> Result<String> {
+        crate::explain::explain(root_schema, value, instance_pointer)
     }
 }
 
+/// Whether `doc` is an empty document, with nothing but comments, as opposed to an explicit `null`.
+/// The span of an empty document is either empty or covers the `---` or `...` that ends it.
+fn is_empty_document(value: &str, doc: &saphyr::MarkedYaml) -> bool {
+    if !matches!(doc.data, saphyr::YamlData::Value(saphyr::Scalar::Null)) {
+        return false;
+    }
+    let (start, end) = (doc.span.start.index(), doc.span.end.index());
+    let text: String = value.chars().skip(start).take(end - start).collect();
+    matches!(text.as_str(), "" | "---" | "...")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,4 +304,519 @@ mod tests {
         let context = Engine::evaluate(&root_schema, "", false).unwrap();
         assert!(context.has_errors());
     }
+
+    const REMOTE_REF_SCHEMA: &str = r#"
+        type: object
+        properties:
+          a:
+            $ref: "https://example.com/defs.yaml#/$defs/name"
+          b:
+            $ref: "https://example.com/defs.yaml#/$defs/name"
+        "#;
+
+    #[test]
+    fn test_remote_refs_disabled_by_default() {
+        let root_schema = crate::loader::load_from_str(REMOTE_REF_SCHEMA).unwrap();
+        let err = Engine::evaluate(&root_schema, "a: x", false).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("fetching https://example.com/defs.yaml over the network is disabled"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_remote_refs_use_cached_schema() {
+        let root_schema = crate::loader::load_from_str(REMOTE_REF_SCHEMA).unwrap();
+        let defs = crate::loader::load_from_str(
+            r#"
+            $defs:
+              name:
+                type: string
+            "#,
+        )
+        .unwrap();
+        let mut schemas = HashMap::new();
+        schemas.insert("https://example.com/defs.yaml".to_string(), Arc::new(defs));
+        let options = ValidationOptions::default().allow_remote_refs(true);
+        let context = Engine::evaluate_with_schemas_and_options(
+            &root_schema,
+            "a: x\nb: 1",
+            &options,
+            schemas,
+        )
+        .unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "b");
+        assert_eq!(context.schemas.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_remote_ref_unresolvable_fragment() {
+        let root_schema = crate::loader::load_from_str(REMOTE_REF_SCHEMA).unwrap();
+        let mut schemas = HashMap::new();
+        schemas.insert(
+            "https://example.com/defs.yaml".to_string(),
+            Arc::new(RootSchema::new(YamlSchema::Empty)),
+        );
+        let context = Engine::evaluate_with_schemas(&root_schema, "a: x", false, schemas).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(
+            errors[0].error,
+            "Cannot resolve $ref https://example.com/defs.yaml#/$defs/name: fragment #/$defs/name not found in https://example.com/defs.yaml"
+        );
+    }
+
+    #[derive(Default)]
+    struct CountingObserver {
+        nodes: std::sync::Mutex<Vec<(String, crate::validation::NodeKind)>>,
+        errors: std::sync::Mutex<Vec<String>>,
+        summary: std::sync::Mutex<Option<ValidationSummary>>,
+    }
+
+    impl ValidationObserver for CountingObserver {
+        fn on_node(&self, path: &str, kind: crate::validation::NodeKind) {
+            self.nodes.lock().unwrap().push((path.to_string(), kind));
+        }
+
+        fn on_error(&self, error: &crate::validation::ValidationError) {
+            self.errors.lock().unwrap().push(error.path.clone());
+        }
+
+        fn on_document_done(&self, summary: ValidationSummary) {
+            *self.summary.lock().unwrap() = Some(summary);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_with_observer() {
+        use crate::validation::NodeKind;
+
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+              servers:
+                type: array
+                items:
+                  type: object
+                  properties:
+                    port:
+                      type: integer
+            "#,
+        )
+        .unwrap();
+        let observer = CountingObserver::default();
+        let context = Engine::evaluate_with_observer(
+            &root_schema,
+            "name: x\nservers:\n  - port: 1\n  - port: abc",
+            &ValidationOptions::default(),
+            &observer,
+        )
+        .unwrap();
+
+        let nodes = observer.nodes.lock().unwrap();
+        assert_eq!(
+            *nodes,
+            vec![
+                ("".to_string(), NodeKind::Mapping),
+                ("name".to_string(), NodeKind::Scalar),
+                ("servers".to_string(), NodeKind::Sequence),
+                ("servers.0".to_string(), NodeKind::Mapping),
+                ("servers.1".to_string(), NodeKind::Mapping),
+                ("servers.0.port".to_string(), NodeKind::Scalar),
+                ("servers.1.port".to_string(), NodeKind::Scalar),
+            ]
+        );
+        assert_eq!(*observer.errors.lock().unwrap(), vec!["servers.1.port"]);
+        assert_eq!(
+            *observer.summary.lock().unwrap(),
+            Some(ValidationSummary {
+                errors: 1,
+                warnings: 0,
+                suppressed: 0,
+            })
+        );
+        assert_eq!(context.errors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_fail_fast_never_leaks() {
+        use saphyr::MarkedYaml;
+
+        use crate::Validator as _;
+
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+              servers:
+                type: array
+                items:
+                  type: object
+                  properties:
+                    port:
+                      type: integer
+                      minimum: 1
+                    tags:
+                      type: array
+                      items:
+                        oneOf:
+                          - type: string
+                          - type: integer
+            required: [name]
+            "#,
+        )
+        .unwrap();
+        let cases = [
+            ("top level", "[]", ""),
+            ("required", "servers: []", ""),
+            ("property", "name: 42", "name"),
+            (
+                "array item",
+                "name: a\nservers: [{port: 0}, {port: -1}]",
+                "servers.0.port",
+            ),
+            (
+                "combinator",
+                "name: a\nservers: [{port: 1, tags: [a, true, null]}]",
+                "servers.0.tags.1",
+            ),
+        ];
+        let options = ValidationOptions::default().fail_fast(true);
+        for (case, value, path) in cases {
+            let context = Engine::evaluate_with_options(&root_schema, value, &options)
+                .unwrap_or_else(|e| panic!("{case}: {e:?}"));
+            let errors = context.errors.borrow();
+            assert_eq!(errors.len(), 1, "{case}: {errors:?}");
+            assert_eq!(errors[0].path, path, "{case}");
+
+            let docs = MarkedYaml::load_from_str(value).unwrap();
+            let context = Context::with_root_schema(&root_schema, true);
+            let result = root_schema.validate(&context, docs.first().unwrap());
+            assert!(result.is_ok(), "{case}: {result:?}");
+            assert!(context.has_errors(), "{case}");
+        }
+    }
+
+    #[test]
+    fn test_yaml_1_1_directive() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              enabled:
+                type: boolean
+              mode:
+                type: string
+            "#,
+        )
+        .unwrap();
+        let value = "%YAML 1.1\n---\nenabled: on\nmode: \"off\"\n";
+        let context = Engine::evaluate(&root_schema, value, false).unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+        assert_eq!(
+            context.yaml_version,
+            Some(crate::directives::YamlVersion { major: 1, minor: 1 })
+        );
+
+        let context = Engine::evaluate(&root_schema, "enabled: on", false).unwrap();
+        assert!(context.has_errors());
+        assert_eq!(context.yaml_version, None);
+        let options = ValidationOptions::default().yaml_1_1(true);
+        let context =
+            Engine::evaluate_with_options(&root_schema, "enabled: off", &options).unwrap();
+        assert!(!context.has_errors());
+        // The declared version wins over the option
+        let context =
+            Engine::evaluate_with_options(&root_schema, "%YAML 1.2\n---\nenabled: off", &options)
+                .unwrap();
+        assert!(context.has_errors());
+
+        let error =
+            Engine::evaluate(&root_schema, "%YAML 2.0\n---\nenabled: true", false).unwrap_err();
+        assert!(matches!(error, Error::InvalidDirective(_)), "{error:?}");
+    }
+
+    #[test]
+    fn test_tag_directive() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            properties:
+              point:
+                type: object
+                properties:
+                  x:
+                    type: integer
+                required: [x]
+            "#,
+        )
+        .unwrap();
+        let value = "%TAG !e! tag:example.com,2000:\n---\npoint: !e!point {x: 1}\n";
+        let context = Engine::evaluate(&root_schema, value, false).unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+
+        let value = "%TAG !e! tag:example.com,2000:\n---\npoint: !e!point {y: 1}\n";
+        let context = Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["[3:17] .point: Required property 'x' is missing!"]
+        );
+    }
+
+    #[test]
+    fn test_all_documents() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              port:
+                type: integer
+            "#,
+        )
+        .unwrap();
+        let value = "port: a\n---\nport: 1\nname: x\n---\n# third\n\n  port: c\n";
+        let context = Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].document_index, None);
+        drop(errors);
+
+        let options = ValidationOptions::default().all_documents(true);
+        let context = Engine::evaluate_with_options(&root_schema, value, &options).unwrap();
+        let errors = context.errors.borrow();
+        let attributed: Vec<(Option<usize>, String)> = errors
+            .iter()
+            .map(|e| (e.document_index, e.to_string()))
+            .collect();
+        assert_eq!(
+            attributed,
+            vec![
+                (
+                    Some(0),
+                    "[1:7] .port: Expected a number, but got: \"a\" (string)".to_string()
+                ),
+                (
+                    Some(2),
+                    "[8:9] .port: Expected a number, but got: \"c\" (string)".to_string()
+                ),
+            ]
+        );
+        assert_eq!(errors[1].to_json()["document"], 2);
+        drop(errors);
+
+        let options = options.fail_fast(true);
+        let context = Engine::evaluate_with_options(&root_schema, value, &options).unwrap();
+        assert_eq!(context.errors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_all_skips_empty_documents() {
+        let root_schema = crate::loader::load_from_str("type: object").unwrap();
+        let value = "a: 1\n---\n# nothing here\n---\n42\n---\n~\n---\n";
+        let options = ValidationOptions::default().all_documents(true);
+        let context = Engine::evaluate_with_options(&root_schema, value, &options).unwrap();
+        let errors = context.errors.borrow();
+        let attributed: Vec<(Option<usize>, String)> = errors
+            .iter()
+            .map(|e| (e.document_index, e.to_string()))
+            .collect();
+        assert_eq!(
+            attributed,
+            vec![
+                (
+                    Some(2),
+                    "[5:1] .: Expected an object, but got: 42".to_string()
+                ),
+                (
+                    Some(3),
+                    "[7:1] .: Expected an object, but got: null".to_string()
+                ),
+            ]
+        );
+        drop(errors);
+
+        let context = Engine::evaluate_with_options(&root_schema, "a: 1\n---\n", &options).unwrap();
+        assert!(!context.has_errors());
+        let fail_fast = options.clone().fail_fast(true);
+        let context = Engine::evaluate_with_options(&root_schema, value, &fail_fast).unwrap();
+        assert_eq!(context.errors.borrow().len(), 1);
+        let context = Engine::evaluate_with_options(&root_schema, "---\n", &options).unwrap();
+        assert_eq!(
+            context.errors.borrow()[0].error,
+            "Empty YAML document is not allowed"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_reader() {
+        let root_schema = crate::loader::load_from_str("type: object").unwrap();
+        let context = Engine::evaluate_reader(&root_schema, "a: 1\n".as_bytes(), false).unwrap();
+        assert!(!context.has_errors());
+        let context = Engine::evaluate_reader(&root_schema, "42".as_bytes(), false).unwrap();
+        assert!(context.has_errors());
+
+        // A large input is rejected without reading all of it
+        let options = ValidationOptions::default().max_input_bytes(1024);
+        let mut input = std::io::repeat(b'a').take(1 << 30);
+        let err =
+            Engine::evaluate_reader_with_options(&root_schema, &mut input, &options).unwrap_err();
+        assert!(
+            matches!(err, Error::InputTooLarge { limit: 1024 }),
+            "{err:?}"
+        );
+        assert_eq!(input.limit(), (1 << 30) - 1025);
+    }
+
+    #[test]
+    fn test_evaluate_value() {
+        #[derive(serde::Serialize)]
+        struct Server {
+            port: u32,
+            host: String,
+            ratio: f64,
+            tags: Vec<String>,
+            backup: Option<String>,
+        }
+
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              host:
+                type: string
+                minLength: 1
+              port:
+                type: integer
+                maximum: 65535
+              ratio:
+                type: number
+              tags:
+                type: array
+                items:
+                  type: string
+                uniqueItems: true
+              backup:
+                type: [string, "null"]
+            required: [host, port]
+            additionalProperties: false
+            "#,
+        )
+        .unwrap();
+        let mut server = Server {
+            port: 8080,
+            host: "localhost".to_string(),
+            ratio: 0.5,
+            tags: vec!["a".to_string(), "b".to_string()],
+            backup: None,
+        };
+        let options = ValidationOptions::default();
+        let context = Engine::evaluate_value(&root_schema, &server, &options).unwrap();
+        assert!(!context.has_errors());
+
+        server.host.clear();
+        server.port = 70000;
+        let context = Engine::evaluate_value(&root_schema, &server, &options).unwrap();
+        let errors: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        // Without source text there are no markers, and fields keep their declaration order
+        assert_eq!(
+            errors,
+            vec![
+                ".port: Number must be less than or equal to 65535",
+                ".host: String is too short! (min length: 1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_to_report() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              port:
+                type: integer
+                maximum: 65535
+            "#,
+        )
+        .unwrap();
+        let options = ValidationOptions::default();
+        let report = Engine::evaluate_to_report(&root_schema, "port: 70000", &options).unwrap();
+        assert!(!report.valid);
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            concat!(
+                r##"{"valid":false,"errors":1,"warnings":0,"suppressed":0,"diagnostics":[{"##,
+                r##""path":"port","instance_path":"/port","##,
+                r##""schema_path":"#/properties/port/maximum","##,
+                r##""message":"Number must be less than or equal to 65535","##,
+                r##""line":1,"column":7,"severity":"error","suppressed":false}]}"##
+            )
+        );
+
+        let report = Engine::evaluate_to_report(&root_schema, "port: 80", &options).unwrap();
+        assert_eq!(
+            report.to_json(),
+            serde_json::json!({
+                "valid": true,
+                "errors": 0,
+                "warnings": 0,
+                "suppressed": 0,
+                "diagnostics": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_count_checks() {
+        let root_schema = crate::loader::load_from_str(
+            r##"
+            type: object
+            properties:
+              name:
+                type: string
+                pattern: "^[a-z]+$"
+              tags:
+                $ref: "#/$defs/tags"
+            $defs:
+              tags:
+                type: array
+                items:
+                  type: string
+            "##,
+        )
+        .unwrap();
+        let value = "name: abc\ntags: [a, b]";
+        let context = Engine::evaluate(&root_schema, value, false).unwrap();
+        assert_eq!(context.check_counts(), None);
+
+        let options = ValidationOptions::default().count_checks(true);
+        let context = Engine::evaluate_with_options(&root_schema, value, &options).unwrap();
+        assert!(!context.has_errors());
+        assert_eq!(
+            context.check_counts(),
+            // The root, `name`, `tags`, `#/$defs/tags` and its two items
+            Some(crate::validation::CheckCounts {
+                validators: 6,
+                regex_matches: 1,
+                refs_resolved: 1,
+            })
+        );
+    }
 }
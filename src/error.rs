@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 use crate::loader::UrlLoadError;
+use crate::validation::ValidationError;
 
 /// Unexpected errors that can occur during the validation of a YAML schema
 #[derive(Debug, Error)]
@@ -11,14 +12,43 @@ pub enum Error {
     IOError(#[from] std::io::Error),
     #[error("File not found: {0}")]
     FileNotFound(String),
+    /// An error reading YAML from a reader, after `bytes_read` bytes were read successfully
+    #[error("Error reading YAML after {bytes_read} bytes: {source}")]
+    ReadError {
+        bytes_read: usize,
+        source: std::io::Error,
+    },
+    /// The input is larger than the `max_input_bytes` option allows
+    #[error("Input is larger than the limit of {limit} bytes")]
+    InputTooLarge { limit: u64 },
     #[error(transparent)]
     YamlParsingError(#[from] saphyr::ScanError),
+    /// A YAML parse error in a (possibly multi-document) schema file, with the 1-based index of
+    /// the document it occurred in and the offending line
+    #[error("Error parsing YAML document {document}: {source}\n    {snippet}")]
+    YamlDocumentParsingError {
+        document: usize,
+        snippet: String,
+        source: saphyr::ScanError,
+    },
     #[error(transparent)]
     FloatParsingError(#[from] std::num::ParseFloatError),
     #[error(transparent)]
     RegexParsingError(#[from] regex::Error),
+    /// A `%YAML` or other directive that isn't supported, see
+    /// [`scan_directives`](crate::directives::scan_directives)
+    #[error("Invalid YAML directive: {0}")]
+    InvalidDirective(String),
     #[error("Error loading schema: {0}")]
     SchemaLoadingError(String),
+    /// An error loading a schema nested in another, at `location` (e.g. `anyOf[2]`, `$defs.foo`
+    /// or `properties.bar`), with `marker` the line and column of the nested schema
+    #[error("{marker} {location}: {source}")]
+    NestedSchemaError {
+        marker: String,
+        location: String,
+        source: Box<Error>,
+    },
     #[error("Unsupported type: {0}")]
     UnsupportedType(String),
     #[error("{0} Expected mapping, but got: {1}")]
@@ -27,6 +57,11 @@ pub enum Error {
     ExpectedScalar(String),
     #[error("{0} Expected a string value for `type:`, but got: {1}")]
     ExpectedTypeIsString(String, String),
+    /// Internal signal that unwinds validation after the first error in fail-fast mode. The
+    /// public entry points ([`Engine`](crate::Engine)'s `evaluate*` methods and
+    /// [`RootSchema`](crate::RootSchema)'s `validate`) turn it into an `Ok` context holding the
+    /// errors, so callers never see it.
+    #[doc(hidden)]
     #[error("Fail fast signal")]
     FailFast,
     #[error("Invalid regular expression: {0}")]
@@ -39,6 +74,51 @@ pub enum Error {
     JsonPtrError(#[from] jsonptr::ParseError),
     #[error("Not yet implemented!")]
     NotYetImplemented,
+    #[error("Subschema has a reference, but no root schema was provided!")]
+    MissingRootSchema,
+    #[error("{}", format_validation_failed(.0))]
+    ValidationFailed(Vec<ValidationError>),
+}
+
+impl Error {
+    /// Attributes an error loading the nested schema `value` to its `location`
+    pub(crate) fn in_nested_schema(self, location: String, value: &saphyr::MarkedYaml) -> Error {
+        Error::NestedSchemaError {
+            marker: crate::utils::format_marker(&value.span.start),
+            location,
+            source: Box::new(self),
+        }
+    }
+
+    /// Internal failures abort validation outright. Any other error raised while trying a branch
+    /// of a combinator (e.g. an unresolvable `$ref`) only means that the branch did not match.
+    pub fn is_internal(&self) -> bool {
+        matches!(
+            self,
+            Error::IOError(_) | Error::MissingRootSchema | Error::NotYetImplemented
+        )
+    }
+}
+
+/// How many error messages the `ValidationFailed` display lists before summarizing the rest
+const MAX_DISPLAYED_ERRORS: usize = 3;
+
+fn format_validation_failed(errors: &[ValidationError]) -> String {
+    let mut message = format!(
+        "Validation failed with {} error{}",
+        errors.len(),
+        if errors.len() == 1 { "" } else { "s" }
+    );
+    for error in errors.iter().take(MAX_DISPLAYED_ERRORS) {
+        message.push_str(&format!("\n  {error}"));
+    }
+    if errors.len() > MAX_DISPLAYED_ERRORS {
+        message.push_str(&format!(
+            "\n  ... and {} more",
+            errors.len() - MAX_DISPLAYED_ERRORS
+        ));
+    }
+    message
 }
 
 #[macro_export]
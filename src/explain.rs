@@ -0,0 +1,708 @@
+//! Explains which schemas apply to a node of a YAML document, to help answer "why did this
+//! fail?". See [`Engine::explain`](crate::Engine::explain).
+
+use jsonptr::Pointer;
+use saphyr::MarkedYaml;
+use saphyr::YamlData;
+
+use crate::Result;
+use crate::RootSchema;
+use crate::YamlSchema;
+use crate::fixes::path_to_pointer;
+use crate::schemas::BooleanOrSchema;
+use crate::schemas::NumericBounds;
+use crate::schemas::SchemaType;
+use crate::schemas::Subschema;
+use crate::utils::scalar_to_string;
+
+/// How many `$ref`s are followed in a row, so reference cycles terminate
+const MAX_REF_DEPTH: usize = 32;
+
+/// A schema that applies to the node being explained
+struct Applicable<'s> {
+    /// How the schema was reached from its parent, e.g. `oneOf branch 1` or `$ref #/$defs/port`
+    label: Option<String>,
+    /// JSON Pointer tokens locating the schema in the root schema
+    location: Vec<String>,
+    schema: &'s YamlSchema,
+}
+
+impl<'s> Applicable<'s> {
+    fn child(&self, tokens: &[&str], schema: &'s YamlSchema) -> Applicable<'s> {
+        let mut location = self.location.clone();
+        location.extend(tokens.iter().map(|token| token.to_string()));
+        Applicable {
+            label: None,
+            location,
+            schema,
+        }
+    }
+}
+
+/// Describe the schemas that apply to the node at `instance_pointer` in `value`, see
+/// [`Engine::explain`](crate::Engine::explain)
+pub fn explain(
+    root_schema: &RootSchema,
+    value: &MarkedYaml,
+    instance_pointer: &str,
+) -> Result<String> {
+    let pointer = Pointer::parse(instance_pointer)
+        .map_err(|e| generic_error!("Invalid instance pointer {:?}: {}", instance_pointer, e))?;
+    let root = Applicable {
+        label: None,
+        location: Vec::new(),
+        schema: &root_schema.schema,
+    };
+    let mut applicable = Vec::new();
+    expand(root_schema, root, &mut applicable, 0);
+
+    let mut node = value;
+    for token in pointer.tokens() {
+        let key = token.decoded();
+        let mut next = Vec::new();
+        for child in child_schemas(&applicable, node, &key) {
+            expand(root_schema, child, &mut next, 0);
+        }
+        applicable = next;
+        node = child_node(node, &key)
+            .ok_or_else(|| generic_error!("No value at {:?} in the document", instance_pointer))?;
+    }
+
+    let mut out = format!(
+        "Schemas applying to {}:\n",
+        if instance_pointer.is_empty() {
+            "the document root"
+        } else {
+            instance_pointer
+        }
+    );
+    if applicable.is_empty() {
+        out.push_str("\nNo schema applies; any value is accepted.\n");
+    }
+    for section in &applicable {
+        out.push('\n');
+        let location = format!("#{}", path_to_pointer(&section.location));
+        match &section.label {
+            Some(label) => out.push_str(&format!("# {label} at {location}\n")),
+            None => out.push_str(&format!("# {location}\n")),
+        }
+        let mut keywords = Vec::new();
+        write_top_level(&mut out, section.schema, &mut keywords);
+        if !keywords.is_empty() {
+            out.push_str(&format!("# checks: {}\n", keywords.join(", ")));
+        }
+    }
+    Ok(out)
+}
+
+/// Add `applicable`, followed by the schemas it pulls in through a local `$ref` or a combinator
+fn expand<'s>(
+    root_schema: &'s RootSchema,
+    applicable: Applicable<'s>,
+    out: &mut Vec<Applicable<'s>>,
+    ref_depth: usize,
+) {
+    let YamlSchema::Subschema(subschema) = applicable.schema else {
+        out.push(applicable);
+        return;
+    };
+    let parent = Applicable {
+        label: None,
+        location: applicable.location.clone(),
+        schema: applicable.schema,
+    };
+    out.push(applicable);
+
+    if let Some(reference) = &subschema.r#ref
+        && ref_depth < MAX_REF_DEPTH
+        && let Some(fragment) = reference.ref_name.strip_prefix('#')
+        && let Some(target) = root_schema.resolve_fragment(fragment)
+    {
+        let location = if fragment.starts_with('/') {
+            Pointer::parse(fragment)
+                .map(|p| p.tokens().map(|t| t.decoded().to_string()).collect())
+                .unwrap_or_default()
+        } else {
+            root_schema
+                .anchors
+                .get(fragment)
+                .and_then(|p| Pointer::parse(p).ok())
+                .map(|p| p.tokens().map(|t| t.decoded().to_string()).collect())
+                .unwrap_or_default()
+        };
+        let target = Applicable {
+            label: Some(format!(
+                "$ref {} from #{}",
+                reference.ref_name,
+                path_to_pointer(&parent.location)
+            )),
+            location,
+            schema: target,
+        };
+        expand(root_schema, target, out, ref_depth + 1);
+    }
+
+    let branches = [
+        ("allOf", subschema.all_of.as_ref().map(|s| &s.all_of)),
+        ("anyOf", subschema.any_of.as_ref().map(|s| &s.any_of)),
+        ("oneOf", subschema.one_of.as_ref().map(|s| &s.one_of)),
+    ];
+    for (keyword, schemas) in branches {
+        for (index, schema) in schemas.into_iter().flatten().enumerate() {
+            let mut branch = parent.child(&[keyword, &index.to_string()], schema);
+            branch.label = Some(format!("{keyword} branch {index}"));
+            expand(root_schema, branch, out, ref_depth);
+        }
+    }
+    let conditionals = [
+        (
+            "not",
+            "not (must not match)",
+            subschema.not.as_ref().map(|n| n.not.as_ref()),
+        ),
+        (
+            "if",
+            "if (condition)",
+            subschema
+                .if_then_else
+                .as_ref()
+                .map(|i| i.if_schema.as_ref()),
+        ),
+        (
+            "then",
+            "then (applies when `if` matches)",
+            subschema
+                .if_then_else
+                .as_ref()
+                .and_then(|i| i.then_schema.as_deref()),
+        ),
+        (
+            "else",
+            "else (applies when `if` doesn't match)",
+            subschema
+                .if_then_else
+                .as_ref()
+                .and_then(|i| i.else_schema.as_deref()),
+        ),
+    ];
+    for (keyword, label, schema) in conditionals {
+        if let Some(schema) = schema {
+            let mut branch = parent.child(&[keyword], schema);
+            branch.label = Some(label.to_string());
+            expand(root_schema, branch, out, ref_depth);
+        }
+    }
+}
+
+/// The child of a mapping (by key) or sequence (by index)
+fn child_node<'a, 'r>(node: &'a MarkedYaml<'r>, key: &str) -> Option<&'a MarkedYaml<'r>> {
+    match &node.data {
+        YamlData::Mapping(mapping) => mapping.iter().find_map(|(k, v)| match &k.data {
+            YamlData::Value(scalar) if scalar_to_string(scalar) == key => Some(v),
+            _ => None,
+        }),
+        YamlData::Sequence(sequence) => sequence.get(key.parse::<usize>().ok()?),
+        _ => None,
+    }
+}
+
+/// The schemas that apply to the child `key` of `node`, given the schemas applying to `node`
+fn child_schemas<'s>(
+    applicable: &[Applicable<'s>],
+    node: &MarkedYaml,
+    key: &str,
+) -> Vec<Applicable<'s>> {
+    let mut children = Vec::new();
+    for parent in applicable {
+        let YamlSchema::Subschema(subschema) = parent.schema else {
+            continue;
+        };
+        match &node.data {
+            YamlData::Mapping(_) => {
+                let Some(object_schema) = &subschema.object_schema else {
+                    continue;
+                };
+                let mut matched = false;
+                if let Some(schema) = object_schema
+                    .properties
+                    .as_ref()
+                    .and_then(|properties| properties.get(key))
+                {
+                    matched = true;
+                    children.push(parent.child(&["properties", key], schema));
+                }
+                for pp in object_schema.pattern_properties.iter().flatten() {
+                    if pp.regex.is_match(key) {
+                        matched = true;
+                        children.push(
+                            parent.child(&["patternProperties", pp.regex.as_str()], &pp.schema),
+                        );
+                    }
+                }
+                if !matched
+                    && let Some(BooleanOrSchema::Schema(schema)) =
+                        &object_schema.additional_properties
+                {
+                    children.push(parent.child(&["additionalProperties"], schema));
+                }
+            }
+            YamlData::Sequence(_) => {
+                let Some(array_schema) = &subschema.array_schema else {
+                    continue;
+                };
+                let Ok(index) = key.parse::<usize>() else {
+                    continue;
+                };
+                match array_schema
+                    .prefix_items
+                    .as_ref()
+                    .and_then(|prefix_items| prefix_items.get(index))
+                {
+                    Some(schema) => {
+                        children.push(parent.child(&["prefixItems", &index.to_string()], schema))
+                    }
+                    None => {
+                        if let Some(BooleanOrSchema::Schema(schema)) = &array_schema.items {
+                            children.push(parent.child(&["items"], schema));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    children
+}
+
+/// Write the keywords of a schema that applies to the node. Combinators are listed in `keywords`
+/// but not written, as their branches are shown as separate schemas.
+fn write_top_level(out: &mut String, schema: &YamlSchema, keywords: &mut Vec<&'static str>) {
+    match schema {
+        YamlSchema::Subschema(subschema) => {
+            write_subschema(out, subschema, 0, keywords);
+            let combinators = [
+                ("allOf", subschema.all_of.is_some()),
+                ("anyOf", subschema.any_of.is_some()),
+                ("oneOf", subschema.one_of.is_some()),
+                ("not", subschema.not.is_some()),
+                ("if", subschema.if_then_else.is_some()),
+            ];
+            keywords.extend(
+                combinators
+                    .into_iter()
+                    .filter_map(|(keyword, present)| present.then_some(keyword)),
+            );
+        }
+        other => out.push_str(&format!("{}\n", inline_schema(other))),
+    }
+}
+
+/// Schemas that aren't mappings, written inline
+fn inline_schema(schema: &YamlSchema) -> String {
+    match schema {
+        YamlSchema::Empty => "{}".to_string(),
+        YamlSchema::Null => "null".to_string(),
+        YamlSchema::BooleanLiteral(value) => value.to_string(),
+        YamlSchema::Subschema(_) => String::new(),
+    }
+}
+
+fn line(out: &mut String, indent: usize, text: impl std::fmt::Display) {
+    out.push_str(&format!("{:indent$}{text}\n", ""));
+}
+
+/// Quote `s` as a YAML (JSON-compatible) double-quoted string unless it is a plain word
+fn yaml_string(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && !matches!(s, "true" | "false" | "null" | "~")
+        && s.parse::<f64>().is_err()
+    {
+        s.to_string()
+    } else {
+        serde_json::to_string(s).unwrap_or_else(|_| format!("{s:?}"))
+    }
+}
+
+fn write_keyed_schema(out: &mut String, indent: usize, key: &str, schema: &YamlSchema) {
+    match schema {
+        YamlSchema::Subschema(subschema) => {
+            let mut nested = String::new();
+            write_subschema(&mut nested, subschema, indent + 2, &mut Vec::new());
+            if nested.is_empty() {
+                line(out, indent, format!("{key}: {{}}"));
+            } else {
+                line(out, indent, format!("{key}:"));
+                out.push_str(&nested);
+            }
+        }
+        other => line(out, indent, format!("{key}: {}", inline_schema(other))),
+    }
+}
+
+fn write_schema_list(out: &mut String, indent: usize, key: &str, schemas: &[YamlSchema]) {
+    line(out, indent, format!("{key}:"));
+    for schema in schemas {
+        match schema {
+            YamlSchema::Subschema(subschema) => {
+                let mut nested = String::new();
+                write_subschema(&mut nested, subschema, indent + 4, &mut Vec::new());
+                match nested.get(indent + 4..) {
+                    Some(rest) if !rest.is_empty() => {
+                        out.push_str(&format!("{:indent$}  - {rest}", ""));
+                    }
+                    _ => line(out, indent + 2, "- {}"),
+                }
+            }
+            other => line(out, indent + 2, format!("- {}", inline_schema(other))),
+        }
+    }
+}
+
+fn write_boolean_or_schema(out: &mut String, indent: usize, key: &str, value: &BooleanOrSchema) {
+    match value {
+        BooleanOrSchema::Boolean(b) => line(out, indent, format!("{key}: {b}")),
+        BooleanOrSchema::Schema(schema) => write_keyed_schema(out, indent, key, schema),
+    }
+}
+
+fn write_bounds(
+    out: &mut String,
+    indent: usize,
+    bounds: &NumericBounds,
+    keywords: &mut Vec<&'static str>,
+) {
+    let values = [
+        ("minimum", bounds.minimum.map(|n| n.to_string())),
+        ("maximum", bounds.maximum.map(|n| n.to_string())),
+        (
+            "exclusiveMinimum",
+            bounds
+                .exclusive_minimum
+                .map(|n| n.to_string())
+                .or(bounds.draft04_exclusive_minimum.map(|b| b.to_string())),
+        ),
+        (
+            "exclusiveMaximum",
+            bounds
+                .exclusive_maximum
+                .map(|n| n.to_string())
+                .or(bounds.draft04_exclusive_maximum.map(|b| b.to_string())),
+        ),
+        ("multipleOf", bounds.multiple_of.map(|n| n.to_string())),
+    ];
+    for (keyword, value) in values {
+        if let Some(value) = value {
+            line(out, indent, format!("{keyword}: {value}"));
+            keywords.push(keyword);
+        }
+    }
+}
+
+/// Write the keywords of `subschema` as YAML. Nested `$ref`s are written as-is rather than
+/// followed. Assertion keywords written at this level are added to `keywords`.
+fn write_subschema(
+    out: &mut String,
+    subschema: &Subschema,
+    indent: usize,
+    keywords: &mut Vec<&'static str>,
+) {
+    let meta = &subschema.metadata_and_annotations;
+    if let Some(title) = &meta.title {
+        line(out, indent, format!("title: {}", yaml_string(title)));
+    }
+    if let Some(description) = &meta.description {
+        line(
+            out,
+            indent,
+            format!("description: {}", yaml_string(description)),
+        );
+    }
+    if let Some(default) = &meta.default {
+        line(out, indent, format!("default: {default}"));
+    }
+    if let Some(reference) = &subschema.r#ref {
+        line(
+            out,
+            indent,
+            format!("$ref: {}", yaml_string(&reference.ref_name)),
+        );
+        keywords.push("$ref");
+    }
+    match &subschema.r#type {
+        SchemaType::None => {}
+        SchemaType::Single(t) => {
+            line(out, indent, format!("type: {t}"));
+            keywords.push("type");
+        }
+        SchemaType::Multiple(types) => {
+            line(out, indent, format!("type: [{}]", types.join(", ")));
+            keywords.push("type");
+        }
+    }
+    if let Some(r#const) = &subschema.r#const {
+        line(out, indent, format!("const: {const}"));
+        keywords.push("const");
+    }
+    if let Some(r#enum) = &subschema.r#enum {
+        let members: Vec<String> = r#enum.r#enum.iter().map(|m| m.to_string()).collect();
+        line(out, indent, format!("enum: [{}]", members.join(", ")));
+        keywords.push("enum");
+    }
+
+    if let Some(string_schema) = &subschema.string_schema {
+        if let Some(min_length) = string_schema.min_length {
+            line(out, indent, format!("minLength: {min_length}"));
+            keywords.push("minLength");
+        }
+        if let Some(max_length) = string_schema.max_length {
+            line(out, indent, format!("maxLength: {max_length}"));
+            keywords.push("maxLength");
+        }
+        if let Some(pattern) = &string_schema.pattern {
+            let pattern = serde_json::to_string(pattern.as_str())
+                .unwrap_or_else(|_| format!("{:?}", pattern.as_str()));
+            line(out, indent, format!("pattern: {pattern}"));
+            keywords.push("pattern");
+        }
+        if let Some(format) = &string_schema.format {
+            line(out, indent, format!("format: {format}"));
+            keywords.push("format");
+        }
+        if let Some(content_encoding) = &string_schema.content_encoding {
+            line(out, indent, format!("contentEncoding: {content_encoding}"));
+            keywords.push("contentEncoding");
+        }
+        if let Some(content_media_type) = &string_schema.content_media_type {
+            line(
+                out,
+                indent,
+                format!("contentMediaType: {content_media_type}"),
+            );
+            keywords.push("contentMediaType");
+        }
+    }
+    if let Some(bounds) = subschema
+        .integer_schema
+        .as_ref()
+        .map(|s| &s.bounds)
+        .or(subschema.number_schema.as_ref().map(|s| &s.bounds))
+    {
+        write_bounds(out, indent, bounds, keywords);
+    }
+
+    if let Some(object_schema) = &subschema.object_schema {
+        if let Some(properties) = &object_schema.properties {
+            line(out, indent, "properties:");
+            for (name, schema) in properties {
+                write_keyed_schema(out, indent + 2, &yaml_string(name), schema);
+            }
+            keywords.push("properties");
+        }
+        if let Some(pattern_properties) = &object_schema.pattern_properties {
+            line(out, indent, "patternProperties:");
+            for pp in pattern_properties {
+                let pattern = serde_json::to_string(pp.regex.as_str())
+                    .unwrap_or_else(|_| format!("{:?}", pp.regex.as_str()));
+                write_keyed_schema(out, indent + 2, &pattern, &pp.schema);
+            }
+            keywords.push("patternProperties");
+        }
+        if let Some(additional_properties) = &object_schema.additional_properties {
+            write_boolean_or_schema(out, indent, "additionalProperties", additional_properties);
+            keywords.push("additionalProperties");
+        }
+        if let Some(property_names) = &object_schema.property_names {
+            write_keyed_schema(out, indent, "propertyNames", property_names);
+            keywords.push("propertyNames");
+        }
+        if let Some(required) = &object_schema.required {
+            let names: Vec<String> = required.iter().map(|name| yaml_string(name)).collect();
+            line(out, indent, format!("required: [{}]", names.join(", ")));
+            keywords.push("required");
+        }
+        if let Some(min_properties) = object_schema.min_properties {
+            line(out, indent, format!("minProperties: {min_properties}"));
+            keywords.push("minProperties");
+        }
+        if let Some(max_properties) = object_schema.max_properties {
+            line(out, indent, format!("maxProperties: {max_properties}"));
+            keywords.push("maxProperties");
+        }
+        if let Some(dependent_required) = &object_schema.dependent_required {
+            line(out, indent, "dependentRequired:");
+            for (trigger, dependencies) in dependent_required {
+                let names: Vec<String> =
+                    dependencies.iter().map(|name| yaml_string(name)).collect();
+                line(
+                    out,
+                    indent + 2,
+                    format!("{}: [{}]", yaml_string(trigger), names.join(", ")),
+                );
+            }
+            keywords.push("dependentRequired");
+        }
+        if let Some(dependent_schemas) = &object_schema.dependent_schemas {
+            line(out, indent, "dependentSchemas:");
+            for (trigger, schema) in dependent_schemas {
+                write_keyed_schema(out, indent + 2, &yaml_string(trigger), schema);
+            }
+            keywords.push("dependentSchemas");
+        }
+    }
+
+    if let Some(array_schema) = &subschema.array_schema {
+        if let Some(prefix_items) = &array_schema.prefix_items {
+            write_schema_list(out, indent, "prefixItems", prefix_items);
+            keywords.push("prefixItems");
+        }
+        if let Some(items) = &array_schema.items {
+            write_boolean_or_schema(out, indent, "items", items);
+            keywords.push("items");
+        }
+        if let Some(contains) = &array_schema.contains {
+            write_keyed_schema(out, indent, "contains", contains);
+            keywords.push("contains");
+        }
+        let counts = [
+            ("minItems", array_schema.min_items.map(|n| n as u64)),
+            ("maxItems", array_schema.max_items.map(|n| n as u64)),
+            ("minContains", array_schema.min_contains),
+            ("maxContains", array_schema.max_contains),
+        ];
+        for (keyword, count) in counts {
+            if let Some(count) = count {
+                line(out, indent, format!("{keyword}: {count}"));
+                keywords.push(keyword);
+            }
+        }
+        if let Some(unique_items) = array_schema.unique_items {
+            line(out, indent, format!("uniqueItems: {unique_items}"));
+            keywords.push("uniqueItems");
+        }
+    }
+
+    if let Some(unevaluated_properties) = &subschema.unevaluated_properties {
+        write_boolean_or_schema(out, indent, "unevaluatedProperties", unevaluated_properties);
+        keywords.push("unevaluatedProperties");
+    }
+    if let Some(unevaluated_items) = &subschema.unevaluated_items {
+        write_boolean_or_schema(out, indent, "unevaluatedItems", unevaluated_items);
+        keywords.push("unevaluatedItems");
+    }
+
+    // Nested combinators are written in full; at the top level their branches are explained
+    // separately, see `write_top_level`
+    if indent > 0 {
+        let lists = [
+            ("allOf", subschema.all_of.as_ref().map(|s| &s.all_of)),
+            ("anyOf", subschema.any_of.as_ref().map(|s| &s.any_of)),
+            ("oneOf", subschema.one_of.as_ref().map(|s| &s.one_of)),
+        ];
+        for (keyword, schemas) in lists {
+            if let Some(schemas) = schemas {
+                write_schema_list(out, indent, keyword, schemas);
+            }
+        }
+        if let Some(not) = &subschema.not {
+            write_keyed_schema(out, indent, "not", &not.not);
+        }
+        if let Some(if_then_else) = &subschema.if_then_else {
+            write_keyed_schema(out, indent, "if", &if_then_else.if_schema);
+            if let Some(then_schema) = &if_then_else.then_schema {
+                write_keyed_schema(out, indent, "then", then_schema);
+            }
+            if let Some(else_schema) = &if_then_else.else_schema {
+                write_keyed_schema(out, indent, "else", else_schema);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use saphyr::LoadableYamlNode;
+
+    use crate::Engine;
+    use crate::loader;
+
+    use super::*;
+
+    fn explain_str(schema: &str, value: &str, pointer: &str) -> String {
+        let root_schema = loader::load_from_str(schema).unwrap();
+        let docs = MarkedYaml::load_from_str(value).unwrap();
+        Engine::explain(&root_schema, docs.first().unwrap(), pointer).unwrap()
+    }
+
+    #[test]
+    fn test_explain_dereferences_refs() {
+        let schema = r##"
+            type: object
+            properties:
+              port:
+                $ref: "#/$defs/port"
+            $defs:
+              port:
+                type: integer
+                minimum: 1
+                maximum: 65535
+        "##;
+        let explanation = explain_str(schema, "port: 0", "/port");
+        assert!(explanation.starts_with("Schemas applying to /port:\n"));
+        assert!(explanation.contains("# #/properties/port\n$ref: \"#/$defs/port\"\n"));
+        assert!(
+            explanation.contains("# $ref #/$defs/port from #/properties/port at #/$defs/port\n")
+        );
+        assert!(explanation.contains("type: integer\nminimum: 1\nmaximum: 65535\n"));
+        assert!(explanation.contains("# checks: type, minimum, maximum\n"));
+    }
+
+    #[test]
+    fn test_explain_labels_one_of_branches() {
+        let schema = r##"
+            type: object
+            properties:
+              size:
+                oneOf:
+                  - type: integer
+                    minimum: 0
+                  - type: string
+                    enum: [small, large]
+        "##;
+        let explanation = explain_str(schema, "size: medium", "/size");
+        assert!(explanation.contains("# #/properties/size\n# checks: oneOf\n"));
+        assert!(explanation.contains("# oneOf branch 0 at #/properties/size/oneOf/0\n"));
+        assert!(explanation.contains("# oneOf branch 1 at #/properties/size/oneOf/1\n"));
+        assert!(explanation.contains("type: string\nenum: [\"small\", \"large\"]\n"));
+    }
+
+    #[test]
+    fn test_explain_array_items_and_root() {
+        let schema = r##"
+            type: array
+            prefixItems:
+              - type: string
+            items:
+              type: object
+              properties:
+                name:
+                  type: string
+                  minLength: 1
+              required: [name]
+        "##;
+        let explanation = explain_str(schema, "[a, {name: b}]", "/1");
+        assert!(explanation.contains("# #/items\ntype: object\nproperties:\n  name:\n    type: string\n    minLength: 1\nrequired: [name]\n"));
+
+        let explanation = explain_str(schema, "[a]", "");
+        assert!(explanation.starts_with("Schemas applying to the document root:\n"));
+        assert!(explanation.contains("prefixItems:\n  - type: string\n"));
+
+        let explanation = explain_str("type: object", "a: 1", "/a");
+        assert!(explanation.contains("No schema applies"));
+
+        let root_schema = loader::load_from_str(schema).unwrap();
+        let docs = MarkedYaml::load_from_str("[a]").unwrap();
+        assert!(Engine::explain(&root_schema, docs.first().unwrap(), "/3").is_err());
+    }
+}
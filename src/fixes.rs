@@ -0,0 +1,275 @@
+//! Mechanical fix suggestions for validation errors, and a way to apply them to YAML text
+
+use std::borrow::Cow;
+
+use ordered_float::OrderedFloat;
+use saphyr::LoadableYamlNode;
+use saphyr::Scalar;
+use saphyr::Yaml;
+use saphyr::YamlEmitter;
+
+use crate::ConstValue;
+use crate::Number;
+use crate::Result;
+
+/// The kind of patch operation a [`FixSuggestion`] performs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixOperation {
+    /// Add a new mapping entry (the last pointer segment is the key)
+    Add,
+    /// Replace the value found at the pointer
+    Replace,
+}
+
+impl std::fmt::Display for FixOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FixOperation::Add => write!(f, "add"),
+            FixOperation::Replace => write!(f, "replace"),
+        }
+    }
+}
+
+/// A JSON Pointer addressed patch that would fix a validation error, e.g. `add /server/port = 8080`
+///
+/// Suggestions are only produced where the fix can be derived with confidence: a missing required
+/// property whose schema declares a `default`, or a string that matches exactly one `enum` member
+/// when compared case-insensitively. Values outside numeric bounds are never clamped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixSuggestion {
+    pub op: FixOperation,
+    /// RFC 6901 JSON Pointer to the value to add or replace
+    pub pointer: String,
+    pub value: ConstValue,
+}
+
+impl FixSuggestion {
+    pub fn add<V: Into<String>>(pointer: V, value: ConstValue) -> FixSuggestion {
+        FixSuggestion {
+            op: FixOperation::Add,
+            pointer: pointer.into(),
+            value,
+        }
+    }
+
+    pub fn replace<V: Into<String>>(pointer: V, value: ConstValue) -> FixSuggestion {
+        FixSuggestion {
+            op: FixOperation::Replace,
+            pointer: pointer.into(),
+            value,
+        }
+    }
+}
+
+impl std::fmt::Display for FixSuggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match &self.value {
+            ConstValue::Number(n) => n.to_string(),
+            ConstValue::Boolean(b) => b.to_string(),
+            other => other.to_string(),
+        };
+        write!(f, "{} {} = {}", self.op, self.pointer, value)
+    }
+}
+
+/// Render a list of path segments as an RFC 6901 JSON Pointer
+pub(crate) fn path_to_pointer<S: AsRef<str>>(segments: &[S]) -> String {
+    segments
+        .iter()
+        .map(|s| format!("/{}", s.as_ref().replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Apply the suggestions to the (first document of the) YAML text and return the re-emitted YAML.
+///
+/// The document is re-emitted as a whole, so comments and original formatting are not preserved.
+/// Returns an error if a pointer cannot be resolved against the document.
+pub fn apply(yaml: &str, suggestions: &[FixSuggestion]) -> Result<String> {
+    let mut docs = Yaml::load_from_str(yaml)?;
+    let mut doc = if docs.is_empty() {
+        Yaml::Value(Scalar::Null)
+    } else {
+        docs.swap_remove(0)
+    };
+    for suggestion in suggestions {
+        apply_one(&mut doc, suggestion)?;
+    }
+    let mut out = String::new();
+    let mut emitter = YamlEmitter::new(&mut out);
+    emitter
+        .dump(&doc)
+        .map_err(|e| generic_error!("Failed to emit YAML: {}", e))?;
+    let out = out.strip_prefix("---\n").unwrap_or(&out).to_string();
+    Ok(format!("{out}\n"))
+}
+
+fn apply_one(doc: &mut Yaml<'static>, suggestion: &FixSuggestion) -> Result<()> {
+    let pointer = jsonptr::Pointer::parse(&suggestion.pointer)?;
+    let tokens: Vec<String> = pointer.tokens().map(|t| t.decoded().into_owned()).collect();
+    let value = const_value_to_yaml(&suggestion.value);
+    match suggestion.op {
+        FixOperation::Add => {
+            let (last, parent_tokens) = tokens.split_last().ok_or_else(|| {
+                generic_error!("Cannot add a value at the document root: {}", suggestion)
+            })?;
+            let parent = navigate(doc, parent_tokens, suggestion)?;
+            match parent {
+                Yaml::Mapping(mapping) => {
+                    mapping.insert(string_yaml(last), value);
+                }
+                Yaml::Value(Scalar::Null) => {
+                    let mut mapping = saphyr::Mapping::new();
+                    mapping.insert(string_yaml(last), value);
+                    *parent = Yaml::Mapping(mapping);
+                }
+                _ => {
+                    return Err(generic_error!(
+                        "Cannot add a property to a non-mapping value: {}",
+                        suggestion
+                    ));
+                }
+            }
+        }
+        FixOperation::Replace => {
+            let target = navigate(doc, &tokens, suggestion)?;
+            *target = value;
+        }
+    }
+    Ok(())
+}
+
+fn navigate<'a>(
+    doc: &'a mut Yaml<'static>,
+    tokens: &[String],
+    suggestion: &FixSuggestion,
+) -> Result<&'a mut Yaml<'static>> {
+    let mut current = doc;
+    for token in tokens {
+        current = match current {
+            Yaml::Mapping(mapping) => mapping.get_mut(&string_yaml(token)),
+            Yaml::Sequence(sequence) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| sequence.get_mut(index)),
+            _ => None,
+        }
+        .ok_or_else(|| generic_error!("Cannot resolve pointer for suggestion: {}", suggestion))?;
+    }
+    Ok(current)
+}
+
+fn string_yaml(s: &str) -> Yaml<'static> {
+    Yaml::Value(Scalar::String(Cow::Owned(s.to_string())))
+}
+
+fn const_value_to_yaml(value: &ConstValue) -> Yaml<'static> {
+    match value {
+        ConstValue::Null => Yaml::Value(Scalar::Null),
+        ConstValue::Boolean(b) => Yaml::Value(Scalar::Boolean(*b)),
+        ConstValue::Number(Number::Integer(i)) => Yaml::Value(Scalar::Integer(*i)),
+        ConstValue::Number(Number::Float(f)) => {
+            Yaml::Value(Scalar::FloatingPoint(OrderedFloat::from(*f)))
+        }
+        ConstValue::String(s) => string_yaml(s),
+        ConstValue::Array(values) => {
+            Yaml::Sequence(values.iter().map(const_value_to_yaml).collect())
+        }
+        ConstValue::Object(values) => Yaml::Mapping(
+            values
+                .iter()
+                .map(|(k, v)| (string_yaml(k), const_value_to_yaml(v)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::Engine;
+    use crate::loader;
+
+    use super::*;
+
+    #[test]
+    fn test_path_to_pointer() {
+        assert_eq!(path_to_pointer::<String>(&[]), "");
+        assert_eq!(path_to_pointer(&["server", "port"]), "/server/port");
+        assert_eq!(path_to_pointer(&["a/b", "c~d"]), "/a~1b/c~0d");
+    }
+
+    #[test]
+    fn test_suggestions_round_trip() {
+        let schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              server:
+                type: object
+                properties:
+                  host:
+                    type: string
+                  port:
+                    type: integer
+                    default: 8080
+                required:
+                  - host
+                  - port
+              env:
+                type: string
+                enum:
+                  - prod
+                  - dev
+            "#,
+        )
+        .unwrap();
+        let yaml = "server:\n  host: localhost\nenv: PROD\n";
+        let context = Engine::evaluate(&schema, yaml, false).unwrap();
+        let suggestions: Vec<FixSuggestion> = context
+            .errors
+            .borrow()
+            .iter()
+            .filter_map(|e| e.suggestion.clone())
+            .collect();
+        assert_eq!(
+            suggestions,
+            vec![
+                FixSuggestion::add("/server/port", ConstValue::integer(8080)),
+                FixSuggestion::replace("/env", ConstValue::string("prod")),
+            ]
+        );
+        assert_eq!(suggestions[0].to_string(), "add /server/port = 8080");
+        assert_eq!(suggestions[1].to_string(), "replace /env = \"prod\"");
+
+        let fixed = apply(yaml, &suggestions).unwrap();
+        let context = Engine::evaluate(&schema, &fixed, false).unwrap();
+        assert!(!context.has_errors(), "{fixed}");
+    }
+
+    #[test]
+    fn test_no_suggestion_without_default_or_for_bounds() {
+        let schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              port:
+                type: integer
+                minimum: 1024
+              name:
+                type: string
+            required:
+              - name
+            "#,
+        )
+        .unwrap();
+        let context = Engine::evaluate(&schema, "port: 80", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.suggestion.is_none()));
+    }
+
+    #[test]
+    fn test_apply_unresolvable_pointer() {
+        let suggestion = FixSuggestion::add("/missing/port", ConstValue::integer(1));
+        assert!(apply("server: {}", &[suggestion]).is_err());
+    }
+}
@@ -7,20 +7,36 @@ use saphyr::YamlData;
 
 #[macro_use]
 pub mod error;
+pub mod cache;
+pub mod compat;
+pub mod dir;
+pub mod directives;
 pub mod engine;
+pub mod explain;
+pub mod fixes;
 pub mod loader;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 pub mod reference;
+pub mod registry;
 pub mod schemas;
+pub mod serialize;
 pub mod utils;
 pub mod validation;
 
+pub use cache::SchemaCache;
+pub use dir::DirOptions;
 pub use engine::Engine;
 pub use error::Error;
 pub use reference::RefUri;
 pub use reference::Reference;
+pub use registry::SchemaRegistry;
 pub use schemas::RootSchema;
 pub use schemas::YamlSchema;
 pub use validation::Context;
+pub use validation::Report;
+pub use validation::ValidationObserver;
+pub use validation::ValidationOptions;
 pub use validation::Validator;
 
 use utils::format_marker;
@@ -60,12 +76,20 @@ impl Number {
         }
     }
 
+    /// Whether `self` is an integral multiple of `divisor`. Floats are compared with a small
+    /// relative tolerance, so `0.3` is a multiple of `0.1` despite rounding errors.
     pub fn is_multiple_of(self, divisor: Number) -> bool {
         match (self, divisor) {
-            (Number::Integer(a), Number::Integer(b)) => b != 0 && a % b == 0,
+            // `wrapping_rem` as `i64::MIN % -1` overflows, though it's a multiple
+            (Number::Integer(a), Number::Integer(b)) => b != 0 && a.wrapping_rem(b) == 0,
             _ => {
                 let d = divisor.to_f64();
-                d != 0.0 && self.to_f64() % d == 0.0
+                if d == 0.0 {
+                    return false;
+                }
+                let quotient = self.to_f64() / d;
+                quotient.is_finite()
+                    && (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
             }
         }
     }
@@ -115,7 +139,7 @@ impl std::fmt::Display for Number {
 /// A ConstValue represents a constant value for the `const` keyword.
 /// Per JSON Schema, `const` can be any JSON value: null, boolean, number,
 /// string, array, or object.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConstValue {
     Null,
     Boolean(bool),
@@ -148,15 +172,10 @@ impl ConstValue {
             ConstValue::Boolean(expected) => {
                 matches!(&value.data, YamlData::Value(Scalar::Boolean(actual)) if *expected == *actual)
             }
-            ConstValue::Number(number) => match (number, &value.data) {
-                (Number::Integer(expected), YamlData::Value(Scalar::Integer(actual))) => {
-                    *actual == *expected
-                }
-                (Number::Float(expected), YamlData::Value(Scalar::FloatingPoint(of))) => {
-                    of.into_inner() == *expected
-                }
-                _ => false,
-            },
+            // Numbers are equal by value, so `1` and `1.0` match (as in JSON Schema)
+            ConstValue::Number(expected) => Number::try_from(value).is_ok_and(|actual| {
+                actual.partial_cmp(expected) == Some(std::cmp::Ordering::Equal)
+            }),
             ConstValue::String(expected) => {
                 matches!(&value.data, YamlData::Value(Scalar::String(actual)) if expected == actual.as_ref())
             }
@@ -171,14 +190,16 @@ impl ConstValue {
                     false
                 }
             }
+            // Keys are compared in the string form they were loaded with, so key order and
+            // quoting (`"true":` vs `true:`) don't matter
             ConstValue::Object(expected) => {
                 if let YamlData::Mapping(actual) = &value.data {
                     expected.len() == actual.len()
-                        && expected.iter().all(|(key, exp_val)| {
-                            let key_yaml = MarkedYaml::value_from_str(key);
-                            actual
-                                .get(&key_yaml)
-                                .is_some_and(|act_yaml| exp_val.accepts(act_yaml))
+                        && actual.iter().all(|(key, act_val)| {
+                            marked_yaml_mapping_key_to_string(key)
+                                .ok()
+                                .and_then(|key| expected.get(&key))
+                                .is_some_and(|exp_val| exp_val.accepts(act_val))
                         })
                 } else {
                     false
@@ -242,9 +263,9 @@ impl<'a> TryFrom<&MarkedYaml<'a>> for ConstValue {
 impl std::fmt::Display for ConstValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ConstValue::Boolean(b) => write!(f, "{b} (bool)"),
+            ConstValue::Boolean(b) => write!(f, "{b}"),
             ConstValue::Null => write!(f, "null"),
-            ConstValue::Number(n) => write!(f, "{n} (number)"),
+            ConstValue::Number(n) => write!(f, "{n}"),
             ConstValue::String(s) => write!(f, "\"{s}\""),
             ConstValue::Array(arr) => {
                 write!(f, "[")?;
@@ -289,6 +310,21 @@ mod tests {
     use super::*;
     use ordered_float::OrderedFloat;
 
+    #[test]
+    fn test_is_multiple_of() {
+        let multiple = |a: Number, b: Number| a.is_multiple_of(b);
+        // Exact float modulo gets these wrong: 0.3 % 0.1 is 0.09999999999999998
+        assert!(multiple(Number::float(0.3), Number::float(0.1)));
+        assert!(multiple(Number::float(4.35), Number::float(0.01)));
+        assert!(multiple(Number::integer(7), Number::float(0.7)));
+        assert!(!multiple(Number::float(0.35), Number::float(0.1)));
+        assert!(!multiple(Number::integer(3), Number::float(0.7)));
+        // The quotient overflows to infinity
+        assert!(!multiple(Number::float(1e308), Number::float(0.123456789)));
+        assert!(multiple(Number::integer(i64::MIN), Number::integer(-1)));
+        assert!(!multiple(Number::integer(1), Number::integer(0)));
+    }
+
     #[test]
     fn test_const_equality() {
         let i1 = ConstValue::integer(42);
@@ -379,4 +415,48 @@ mod tests {
         assert!(!cv.accepts(not_matching.first().unwrap()));
         Ok(())
     }
+
+    #[test]
+    fn test_const_nested_object() -> Result<()> {
+        let root = loader::load_from_str("const:\n  x: 1\n  y: [2, 3]")?;
+        for accepted in ["x: 1\ny: [2, 3]", "y: [2, 3]\nx: 1", "x: 1.0\ny: [2, 3.0]"] {
+            let context = Engine::evaluate(&root, accepted, false)?;
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for rejected in [
+            "x: 1\ny: [3, 2]",
+            "x: 1\ny: [2, 3, 4]",
+            "x: 1\ny: [2, 3]\nz: 4",
+        ] {
+            let context = Engine::evaluate(&root, rejected, false)?;
+            assert!(context.has_errors(), "{rejected}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_of_mappings() -> Result<()> {
+        let root = loader::load_from_str(
+            "enum:\n  - {mode: fast, \"true\": 1}\n  - {mode: safe, \"true\": [1, 2]}",
+        )?;
+        for accepted in [
+            "mode: fast\n\"true\": 1",
+            "\"true\": 1.0\nmode: fast",
+            "true: 1\nmode: fast",
+            "mode: safe\n\"true\": [1.0, 2]",
+        ] {
+            let context = Engine::evaluate(&root, accepted, false)?;
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for rejected in [
+            "mode: fast",
+            "mode: fast\n\"true\": [1, 2]",
+            "mode: safe\n\"true\": [2, 1]",
+            "[mode, fast]",
+        ] {
+            let context = Engine::evaluate(&root, rejected, false)?;
+            assert!(context.has_errors(), "{rejected}");
+        }
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 //! The loader module loads the YAML schema from a file into the in-memory model
 
+use std::io::Read;
 use std::path::Path;
 use std::time::Duration;
 
@@ -15,22 +16,112 @@ use crate::Error;
 use crate::Number;
 use crate::Result;
 use crate::RootSchema;
+use crate::directives::resolve_document;
+use crate::directives::scan_directives;
 use crate::schemas::BooleanOrSchema;
 use crate::schemas::YamlSchema;
+use crate::schemas::keywords::misplaced_keywords;
+use crate::schemas::keywords::unknown_formats;
+use crate::schemas::keywords::unknown_keywords;
+use crate::utils::COMPLEX_KEY_MESSAGE;
+use crate::utils::format_const_like;
 use crate::utils::format_marker;
+use crate::utils::humanize_yaml_data;
+use crate::utils::mistyped_keyword_error;
 use crate::utils::scalar_to_string;
 use crate::utils::try_unwrap_saphyr_scalar;
 
+/// How [`LoaderOptions`] treat keys of a schema that are neither JSON Schema keywords nor `x-`
+/// extensions, which are otherwise silently ignored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownKeys {
+    /// Ignore unknown keys
+    #[default]
+    Ignore,
+    /// Record each unknown key on [`RootSchema::warnings`]
+    Warn,
+    /// Fail to load the schema
+    Error,
+}
+
+impl std::str::FromStr for UnknownKeys {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(UnknownKeys::Ignore),
+            "warn" => Ok(UnknownKeys::Warn),
+            "error" => Ok(UnknownKeys::Error),
+            _ => Err(format!(
+                "Expected one of: ignore, warn, error, but got: {s}"
+            )),
+        }
+    }
+}
+
+/// Options for [`load_file_with_options`], [`load_from_str_with_options`] and
+/// [`load_from_reader_with_options`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoaderOptions {
+    /// How to treat unknown keys, such as the typo `minmum: 3`
+    pub unknown_keys: UnknownKeys,
+    /// Fail to load schemas with a `format` that isn't one of the
+    /// [`StringFormat`](crate::schemas::StringFormat)s. Unknown formats are otherwise
+    /// annotations that always pass.
+    pub strict_formats: bool,
+    /// Fail to load schemas with a keyword that is certainly at the wrong nesting level, such as
+    /// `minLength` in a `type: object` schema. Misplaced keywords are otherwise recorded on
+    /// [`RootSchema::warnings`], as are possibly misplaced ones either way.
+    pub strict: bool,
+    /// Fail to load schema files and readers larger than this many bytes, before parsing them
+    pub max_input_bytes: Option<u64>,
+}
+
+impl LoaderOptions {
+    pub fn unknown_keys(mut self, unknown_keys: UnknownKeys) -> Self {
+        self.unknown_keys = unknown_keys;
+        self
+    }
+
+    pub fn strict_formats(mut self, strict_formats: bool) -> Self {
+        self.strict_formats = strict_formats;
+        self
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn max_input_bytes(mut self, max_input_bytes: u64) -> Self {
+        self.max_input_bytes = Some(max_input_bytes);
+        self
+    }
+}
+
 /// Load a YAML schema from a file.
 /// Delegates to the `load_from_doc` function to load the schema from the first document.
 /// Sets `base_uri` to the canonical file URL for resolving relative `$ref` values.
 pub fn load_file<S: AsRef<str>>(path: S) -> Result<RootSchema> {
+    load_file_with_options(path, &LoaderOptions::default())
+}
+
+/// Like [`load_file`], with the given [`LoaderOptions`]
+pub fn load_file_with_options<S: AsRef<str>>(
+    path: S,
+    options: &LoaderOptions,
+) -> Result<RootSchema> {
     let fs_metadata = std::fs::metadata(path.as_ref())?;
     if !fs_metadata.is_file() {
         return Err(Error::FileNotFound(path.as_ref().to_string()));
     }
+    if let Some(limit) = options.max_input_bytes
+        && fs_metadata.len() > limit
+    {
+        return Err(Error::InputTooLarge { limit });
+    }
     let s = std::fs::read_to_string(path.as_ref())?;
-    let mut root = load_from_str(&s)?;
+    let mut root = load_from_str_with_options(&s, options)?;
     let canonical = Path::new(path.as_ref()).canonicalize()?;
     root.base_uri = Some(
         ParseUrl::from_file_path(canonical)
@@ -41,10 +132,142 @@ pub fn load_file<S: AsRef<str>>(path: S) -> Result<RootSchema> {
 
 /// Load a YAML schema from a &str.
 pub fn load_from_str(s: &str) -> Result<RootSchema> {
-    let docs = MarkedYaml::load_from_str(s).map_err(Error::YamlParsingError)?;
+    let docs = parse_documents(s)?;
     load_from_docs(docs)
 }
 
+/// Load a YAML schema read from `reader`, e.g. standard input
+pub fn load_from_reader<R: Read>(reader: R) -> Result<RootSchema> {
+    load_from_reader_with_options(reader, &LoaderOptions::default())
+}
+
+/// Like [`load_from_reader`], with the given [`LoaderOptions`]
+pub fn load_from_reader_with_options<R: Read>(
+    reader: R,
+    options: &LoaderOptions,
+) -> Result<RootSchema> {
+    let s = read_to_string(reader, options.max_input_bytes)?;
+    load_from_str_with_options(&s, options)
+}
+
+/// Reads all of `reader` as UTF-8 text. The YAML parser needs the whole text, so fails with
+/// [`Error::InputTooLarge`] as soon as more than `max_bytes` bytes are read, rather than buffering
+/// an arbitrarily large input.
+pub(crate) fn read_to_string<R: Read>(mut reader: R, max_bytes: Option<u64>) -> Result<String> {
+    let mut bytes = Vec::new();
+    let read = match max_bytes {
+        // Read one byte past the limit, to tell an input of exactly `limit` bytes from a larger one
+        Some(limit) => reader.take(limit.saturating_add(1)).read_to_end(&mut bytes),
+        None => reader.read_to_end(&mut bytes),
+    };
+    if let Err(source) = read {
+        return Err(Error::ReadError {
+            bytes_read: bytes.len(),
+            source,
+        });
+    }
+    if let Some(limit) = max_bytes
+        && bytes.len() as u64 > limit
+    {
+        return Err(Error::InputTooLarge { limit });
+    }
+    String::from_utf8(bytes).map_err(|e| Error::ReadError {
+        bytes_read: e.utf8_error().valid_up_to(),
+        source: std::io::Error::new(std::io::ErrorKind::InvalidData, e.utf8_error()),
+    })
+}
+
+/// Like [`load_from_str`], with the given [`LoaderOptions`]
+pub fn load_from_str_with_options(s: &str, options: &LoaderOptions) -> Result<RootSchema> {
+    let docs = parse_documents(s)?;
+    match docs.first() {
+        Some(doc) => load_from_doc_with_options(doc, options),
+        None => Ok(RootSchema::empty()),
+    }
+}
+
+/// Like [`load_from_doc`], applying the checks enabled in `options`
+fn load_from_doc_with_options(doc: &MarkedYaml, options: &LoaderOptions) -> Result<RootSchema> {
+    let mut root = load_from_doc(doc)?;
+    let unknown = unknown_keywords(doc);
+    match options.unknown_keys {
+        UnknownKeys::Ignore => {}
+        UnknownKeys::Warn => root.warnings.extend(unknown),
+        UnknownKeys::Error if !unknown.is_empty() => {
+            return Err(Error::SchemaLoadingError(unknown.join("; ")));
+        }
+        UnknownKeys::Error => {}
+    }
+    let misplaced = misplaced_keywords(doc);
+    if options.strict {
+        let certain: Vec<&str> = misplaced
+            .iter()
+            .filter(|m| m.certain)
+            .map(|m| m.message.as_str())
+            .collect();
+        if !certain.is_empty() {
+            return Err(Error::SchemaLoadingError(certain.join("; ")));
+        }
+    }
+    root.warnings
+        .extend(misplaced.into_iter().map(|m| m.message));
+    if options.strict_formats {
+        let unknown = unknown_formats(doc);
+        if !unknown.is_empty() {
+            return Err(Error::SchemaLoadingError(unknown.join("; ")));
+        }
+    }
+    Ok(root)
+}
+
+/// Parse a YAML stream, reporting which document a parse error occurred in. The documents are
+/// resolved as declared by the stream's directives, see [`resolve_document`].
+pub fn parse_documents(s: &str) -> Result<Vec<MarkedYaml<'_>>> {
+    let directives = scan_directives(s)?;
+    let yaml_1_1_booleans = directives
+        .yaml_version
+        .is_some_and(|version| version.has_yaml_1_1_booleans());
+    let docs = MarkedYaml::load_from_str(s).map_err(|e| {
+        let line = e.marker().line();
+        let lines: Vec<&str> = s.lines().take(line).collect();
+        Error::YamlDocumentParsingError {
+            document: document_index(&lines),
+            snippet: lines.last().map_or("", |l| l.trim_end()).to_string(),
+            source: e,
+        }
+    })?;
+    Ok(docs
+        .into_iter()
+        .map(|doc| resolve_document(s, doc, yaml_1_1_booleans))
+        .collect())
+}
+
+/// The 1-based index of the document the last of the given lines belongs to, counting `---`
+/// document start markers and `...` document end markers
+fn document_index(lines: &[&str]) -> usize {
+    let is_marker = |line: &str, marker: &str| {
+        line.strip_prefix(marker)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+    };
+    let mut documents = 0;
+    let mut in_document = false;
+    for line in lines {
+        if is_marker(line, "---") {
+            documents += 1;
+            in_document = true;
+        } else if is_marker(line, "...") {
+            in_document = false;
+        } else if !in_document {
+            let content = line.trim_start();
+            if !content.is_empty() && !content.starts_with('#') && !line.starts_with('%') {
+                documents += 1;
+                in_document = true;
+            }
+        }
+    }
+    documents.max(1)
+}
+
 /// Load a RootSchema from Vec of docs.
 pub fn load_from_docs<'f>(docs: Vec<MarkedYaml<'f>>) -> Result<RootSchema> {
     let Some(first_doc) = docs.first() else {
@@ -82,7 +305,7 @@ impl From<reqwest::Error> for crate::Error {
 
 /// Load a schema from string content with an optional base URI for resolving relative $ref values.
 pub fn load_from_content(content: &str, base_uri: Option<ParseUrl>) -> Result<RootSchema> {
-    let docs = MarkedYaml::load_from_str(content).map_err(Error::YamlParsingError)?;
+    let docs = parse_documents(content)?;
     let doc = docs
         .first()
         .ok_or_else(|| crate::generic_error!("No YAML documents in content"))?;
@@ -236,14 +459,28 @@ pub fn fetch_url(url_string: &str, timeout_seconds: Option<u64>) -> Result<(Stri
 /// let schema = download_from_url("https://example.com/schema.yaml", None).unwrap();
 /// ```
 pub fn download_from_url(url_string: &str, timeout_seconds: Option<u64>) -> Result<RootSchema> {
+    download_from_url_with_options(url_string, timeout_seconds, &LoaderOptions::default())
+}
+
+/// Like [`download_from_url`], with the given [`LoaderOptions`]
+pub fn download_from_url_with_options(
+    url_string: &str,
+    timeout_seconds: Option<u64>,
+    options: &LoaderOptions,
+) -> Result<RootSchema> {
     let (yaml_content, url) = fetch_url(url_string, timeout_seconds)?;
+    if let Some(limit) = options.max_input_bytes
+        && yaml_content.len() as u64 > limit
+    {
+        return Err(Error::InputTooLarge { limit });
+    }
 
     // Parse the YAML content
     let docs = MarkedYaml::load_from_str(&yaml_content).map_err(UrlLoadError::ParseError)?;
 
     match docs.first() {
         Some(doc) => {
-            let mut root = load_from_doc(doc)?;
+            let mut root = load_from_doc_with_options(doc, options)?;
             root.base_uri = Some(url);
             Ok(root)
         }
@@ -267,24 +504,37 @@ pub fn marked_yaml_mapping_key_to_string(yaml: &MarkedYaml) -> Result<String> {
     if let YamlData::Value(scalar) = &yaml.data {
         Ok(scalar_to_string(scalar))
     } else {
-        Err(expected_scalar!(
-            "[{}] Expected a scalar mapping key, got: {:?}",
-            format_marker(&yaml.span.start),
-            yaml
-        ))
+        Err(complex_key_error(yaml))
     }
 }
 
-pub fn load_array_of_schemas_marked<'f>(value: &MarkedYaml<'f>) -> Result<Vec<YamlSchema>> {
+/// The loading error for a schema mapping key that is itself a mapping or sequence
+pub(crate) fn complex_key_error(key: &MarkedYaml) -> Error {
+    expected_scalar!(
+        "{} {COMPLEX_KEY_MESSAGE}: {}",
+        format_marker(&key.span.start),
+        format_const_like(&key.data)
+    )
+}
+
+pub fn load_array_of_schemas_marked<'f>(
+    keyword: &str,
+    value: &MarkedYaml<'f>,
+) -> Result<Vec<YamlSchema>> {
     if let YamlData::Sequence(values) = &value.data {
         values
             .iter()
-            .map(|v| {
-                if v.is_mapping() {
+            .enumerate()
+            .map(|(i, v)| {
+                if v.is_mapping() || matches!(v.data, YamlData::Value(Scalar::Boolean(_))) {
                     v.try_into()
                 } else {
-                    Err(generic_error!("Expected a mapping, but got: {:?}", v))
+                    Err(generic_error!(
+                        "Expected a mapping or boolean, but got: {}",
+                        humanize_yaml_data(&v.data)
+                    ))
                 }
+                .map_err(|e: Error| e.in_nested_schema(format!("{keyword}[{i}]"), v))
             })
             .collect::<Result<Vec<YamlSchema>>>()
     } else {
@@ -333,23 +583,15 @@ pub fn load_number(value: &saphyr::Yaml) -> Result<Number> {
 
 pub fn load_array_items_marked<'input>(value: &MarkedYaml<'input>) -> Result<BooleanOrSchema> {
     match &value.data {
-        YamlData::Value(scalar) => {
-            if let Scalar::Boolean(b) = scalar {
-                Ok(BooleanOrSchema::Boolean(*b))
-            } else {
-                Err(generic_error!(
-                    "array: boolean or mapping with type or $ref, but got: {:?}",
-                    value
-                ))
-            }
-        }
+        YamlData::Value(Scalar::Boolean(b)) => Ok(BooleanOrSchema::Boolean(*b)),
         YamlData::Mapping(_mapping) => {
             let schema: YamlSchema = value.try_into()?;
             Ok(BooleanOrSchema::schema(schema))
         }
-        _ => Err(generic_error!(
-            "array: boolean or mapping with type or $ref, but got: {:?}",
-            value
+        _ => Err(mistyped_keyword_error(
+            "items",
+            "a boolean or a mapping",
+            value,
         )),
     }
 }
@@ -711,6 +953,101 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_schema_directives() {
+        let root_schema =
+            loader::load_from_str("%YAML 1.1\n---\ntype: object\nadditionalProperties: no")
+                .unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "a: 1", false).unwrap();
+        assert!(context.has_errors());
+
+        let err = loader::load_from_str("%YAML 1.1\ntype: object").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid YAML directive: [2, 0] Directives must be followed by a '---' document start marker"
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_options() {
+        let schema = "type: integer\nminmum: 3";
+        let root_schema = loader::load_from_str(schema).unwrap();
+        assert!(root_schema.warnings.is_empty());
+
+        let options = LoaderOptions::default().unknown_keys(UnknownKeys::Warn);
+        let root_schema = load_from_str_with_options(schema, &options).unwrap();
+        assert_eq!(
+            root_schema.warnings,
+            vec!["[2, 0] Unknown keyword 'minmum' (did you mean 'minimum'?)"]
+        );
+
+        let options = LoaderOptions::default().unknown_keys(UnknownKeys::Error);
+        let err = load_from_str_with_options(schema, &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [2, 0] Unknown keyword 'minmum' (did you mean 'minimum'?)"
+        );
+        assert!(load_from_str_with_options("type: integer\nminimum: 3", &options).is_ok());
+        assert_eq!("warn".parse(), Ok(UnknownKeys::Warn));
+        assert!("loud".parse::<UnknownKeys>().is_err());
+    }
+
+    #[test]
+    fn test_strict_formats_option() {
+        let schema = "type: string\nformat: data-time";
+        let root_schema = loader::load_from_str(schema).unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "anything", false).unwrap();
+        assert!(!context.has_errors());
+
+        let options = LoaderOptions::default().strict_formats(true);
+        let err = load_from_str_with_options(schema, &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [2, 8] Unknown format 'data-time'"
+        );
+        assert!(load_from_str_with_options("type: string\nformat: date-time", &options).is_ok());
+    }
+
+    #[test]
+    fn test_strict_option_rejects_misplaced_keywords() {
+        let schema = "type: object\nminLength: 3\nproperties:\n  name:\n    type: string\n";
+        let root_schema = load_from_str_with_options(schema, &LoaderOptions::default()).unwrap();
+        assert_eq!(
+            root_schema.warnings,
+            vec![
+                "[2, 0] 'minLength' applies to strings, but is in a schema of type 'object'; \
+                 did you mean to place it in the schema of a property?"
+            ]
+        );
+        let options = LoaderOptions::default().strict(true);
+        let err = load_from_str_with_options(schema, &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [2, 0] 'minLength' applies to strings, but is in a schema of \
+             type 'object'; did you mean to place it in the schema of a property?"
+        );
+
+        // A property genuinely named `required` only warns, even in strict mode
+        let schema = "type: object\nproperties:\n  required:\n    type: boolean\n";
+        let root_schema = load_from_str_with_options(schema, &options).unwrap();
+        assert_eq!(
+            root_schema.warnings,
+            vec![
+                "[3, 2] 'required' under 'properties' defines a property named 'required'; \
+                 did you mean to place it at the object level?"
+            ]
+        );
+
+        // `required` misplaced under `properties` isn't a schema, so it fails to load either way
+        let schema = "type: object\nproperties:\n  name:\n    type: string\n  required: [name]\n";
+        let err = load_from_str(schema).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [5, 2] 'required' under 'properties' defines a property named \
+             'required'; did you mean to place it at the object level?"
+        );
+    }
+
     #[test]
     fn test_self_validate() -> Result<()> {
         let schema_filename = "yaml-schema.yaml";
@@ -736,6 +1073,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_self_validate_checks_array_of_schemas_items() {
+        let root_schema = loader::load_file("yaml-schema.yaml").unwrap();
+        // `allOf` and `oneOf` are `$ref: "#/$defs/array_of_schemas"`, whose `items` are in turn
+        // `$ref: "#/$defs/schema"`
+        for (value, expected) in [
+            (
+                "type: object\nallOf:\n  - type: string\n  - type: bogus",
+                "[4:11] .allOf.1.type: None of the schemas in `oneOf` matched!",
+            ),
+            (
+                "oneOf: [{type: 5}]",
+                "[1:16] .oneOf.0.type: None of the schemas in `oneOf` matched!",
+            ),
+        ] {
+            let context = Engine::evaluate(&root_schema, value, false).unwrap();
+            let errors: Vec<String> = context
+                .errors
+                .borrow()
+                .iter()
+                .map(|e| e.to_string())
+                .collect();
+            assert_eq!(errors, vec![expected.to_string()], "{value}");
+        }
+    }
+
     #[test]
     fn test_download_from_url() {
         // This is an integration test that requires internet access
@@ -788,4 +1151,131 @@ mod tests {
             std::panic::resume_unwind(e);
         }
     }
+
+    #[test]
+    fn test_parse_error_names_document() {
+        let err =
+            loader::load_from_str("type: string\n---\ntype: object\nproperties: [a\n").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            matches!(err, Error::YamlDocumentParsingError { document: 2, .. }),
+            "{message}"
+        );
+        assert!(
+            message.starts_with("Error parsing YAML document 2: "),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_document_index() {
+        assert_eq!(document_index(&["a: 1"]), 1);
+        assert_eq!(document_index(&["# comment", "---", "a: 1"]), 1);
+        assert_eq!(document_index(&["a: 1", "---", "b: 2"]), 2);
+        assert_eq!(document_index(&["---", "a: 1", "...", "b: 2"]), 2);
+        assert_eq!(
+            document_index(&["%YAML 1.2", "---", "a: 1", "--- b", "c"]),
+            2
+        );
+    }
+
+    #[test]
+    fn test_nested_schema_errors_name_the_element() {
+        let message = |schema: &str| loader::load_from_str(schema).unwrap_err().to_string();
+        assert_eq!(
+            message("anyOf:\n  - type: string\n  - type: integer\n  - 42\n"),
+            "[4, 4] anyOf[2]: Generic YAML schema error: Expected a mapping or boolean, but got: 42 (int)"
+        );
+        assert_eq!(
+            message("anyOf:\n  - type: string\n  - type: integer\n  - type: strin\n"),
+            "[4, 4] anyOf[2]: Unsupported type: Expected type: string, number, integer, object, array, boolean, or null, but got: strin"
+        );
+        assert_eq!(
+            message(
+                "$defs:\n  ok:\n    type: string\n  foo:\n    type: object\n    required: name\n"
+            ),
+            "[5, 4] $defs.foo: Error loading schema: [6, 14] 'required' expects a sequence of property names, but got a string: \"name\" (hint: 'required' takes a list of property names, e.g. `required: [name]`)"
+        );
+        assert_eq!(
+            message(
+                "type: object\nproperties:\n  name:\n    type: string\n  bar:\n    type: array\n    items: \"string\"\n"
+            ),
+            "[6, 4] properties.bar: Error loading schema: [7, 11] 'items' expects a boolean or a mapping, but got a string: \"string\" (hint: to constrain the type, write `items: { type: string }`)"
+        );
+        // Errors nested deeper name every enclosing element
+        let err = loader::load_from_str(
+            "properties:\n  a:\n    anyOf:\n      - type: string\n      - pattern: \"[\"\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("[3, 4] properties.a: [5, 8] anyOf[1]: Invalid regular expression:"),
+            "{err}"
+        );
+        let Error::NestedSchemaError { source, .. } = err else {
+            panic!("{err:?}");
+        };
+        assert!(
+            matches!(*source, Error::NestedSchemaError { .. }),
+            "{source:?}"
+        );
+    }
+
+    /// Reads nothing and fails, to stand in for a broken pipe or disk
+    struct FailingReader;
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk on fire"))
+        }
+    }
+
+    #[test]
+    fn test_load_from_reader() {
+        let root_schema =
+            loader::load_from_reader("type: string\nminLength: 2\n".as_bytes()).unwrap();
+        assert!(matches!(root_schema.schema, YamlSchema::Subschema(_)));
+
+        let options = LoaderOptions::default().max_input_bytes(12);
+        assert!(loader::load_from_reader_with_options("type: string".as_bytes(), &options).is_ok());
+        let err = loader::load_from_reader_with_options("type: string\n".as_bytes(), &options)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Input is larger than the limit of 12 bytes"
+        );
+
+        let err = loader::load_from_reader("type: ".as_bytes().chain(FailingReader)).unwrap_err();
+        assert!(
+            matches!(err, Error::ReadError { bytes_read: 6, .. }),
+            "{err:?}"
+        );
+        assert_eq!(
+            err.to_string(),
+            "Error reading YAML after 6 bytes: disk on fire"
+        );
+
+        let err = loader::load_from_reader(&b"type: \xff"[..]).unwrap_err();
+        assert!(
+            matches!(err, Error::ReadError { bytes_read: 6, .. }),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_complex_schema_keys_fail_to_load() {
+        let message = |schema: &str| loader::load_from_str(schema).unwrap_err().to_string();
+        assert_eq!(
+            message("type: object\n? [a, b]\n: 1\n"),
+            "Expected YAML scalar: [2, 2] Complex mapping keys are not supported by this schema validator: [\"a\", \"b\"]"
+        );
+        assert_eq!(
+            message("type: object\nproperties:\n  ? {x: 1}\n  : {type: string}\n"),
+            "Expected YAML scalar: [3, 4] Complex mapping keys are not supported by this schema validator: {\"x\": 1}"
+        );
+        assert_eq!(
+            message("type: integer\n? [a]\n: 1\n"),
+            "Expected YAML scalar: [2, 2] Complex mapping keys are not supported by this schema validator: [\"a\"]"
+        );
+    }
 }
@@ -0,0 +1,137 @@
+//! Conversion of validation errors to Language Server Protocol diagnostics, for building a YAML
+//! language server. Enabled by the `lsp` feature.
+
+use lsp_types::Diagnostic;
+use lsp_types::DiagnosticSeverity;
+use lsp_types::DiagnosticTag;
+use lsp_types::NumberOrString;
+use lsp_types::Position;
+use lsp_types::Range;
+
+use crate::validation::Severity;
+use crate::validation::ValidationError;
+
+/// The `source` of the diagnostics, shown by editors next to the message
+pub const DIAGNOSTIC_SOURCE: &str = "yaml-schema";
+
+/// Convert `errors` to LSP diagnostics, see [`to_diagnostic`]
+pub fn to_diagnostics(errors: &[ValidationError]) -> Vec<Diagnostic> {
+    errors.iter().map(to_diagnostic).collect()
+}
+
+/// Convert a validation error to an LSP diagnostic.
+///
+/// The range is the empty range at the error's marker (LSP positions are 0-based), or at the
+/// start of the document for errors without one. Columns count characters, which matches LSP's
+/// UTF-16 offsets outside of the astral planes. Warnings become `WARNING` diagnostics, and
+/// suppressed errors `HINT`s tagged `UNNECESSARY`. The schema keyword, when known, is the code.
+pub fn to_diagnostic(error: &ValidationError) -> Diagnostic {
+    let position = error
+        .marker
+        .map(|marker| Position {
+            line: marker.line().saturating_sub(1) as u32,
+            character: marker.col() as u32,
+        })
+        .unwrap_or_default();
+    let (severity, tags) = if error.suppressed {
+        (
+            DiagnosticSeverity::HINT,
+            Some(vec![DiagnosticTag::UNNECESSARY]),
+        )
+    } else {
+        match error.severity {
+            Severity::Error => (DiagnosticSeverity::ERROR, None),
+            Severity::Warning => (DiagnosticSeverity::WARNING, None),
+        }
+    };
+    let message = if error.path.is_empty() {
+        error.error.clone()
+    } else {
+        format!("{}: {}", error.path, error.error)
+    };
+    Diagnostic::new(
+        Range::new(position, position),
+        Some(severity),
+        error.keyword.clone().map(NumberOrString::String),
+        Some(DIAGNOSTIC_SOURCE.to_string()),
+        message,
+        None,
+        tags,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Engine;
+    use crate::ValidationOptions;
+    use crate::loader;
+
+    #[test]
+    fn test_diagnostic_range_matches_marker() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+              port:
+                type: integer
+                maximum: 65535
+            "#,
+        )
+        .unwrap();
+        let options = ValidationOptions::default().suppress("type", "/name");
+        let context = Engine::evaluate_with_options(
+            &root_schema,
+            "name: 1\nservice:\n  port: 1\nport: 70000",
+            &options,
+        )
+        .unwrap();
+        let errors = context.errors.borrow();
+        let diagnostics = to_diagnostics(&errors);
+        assert_eq!(diagnostics.len(), errors.len());
+        for (error, diagnostic) in errors.iter().zip(&diagnostics) {
+            let marker = error.marker.unwrap();
+            assert_eq!(diagnostic.range.start.line as usize, marker.line() - 1);
+            assert_eq!(diagnostic.range.start.character as usize, marker.col());
+            assert_eq!(diagnostic.range.end, diagnostic.range.start);
+        }
+
+        let port = diagnostics
+            .iter()
+            .find(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+            .unwrap();
+        assert_eq!(port.range.start, Position::new(3, 6));
+        assert_eq!(
+            port.message,
+            "port: Number must be less than or equal to 65535"
+        );
+        assert_eq!(port.code, Some(NumberOrString::String("maximum".into())));
+        assert_eq!(port.source.as_deref(), Some(DIAGNOSTIC_SOURCE));
+    }
+
+    #[test]
+    fn test_suppressed_and_unlocated_errors() {
+        let error = ValidationError {
+            path: String::new(),
+            marker: None,
+            error: "Something went wrong".to_string(),
+            suggestion: None,
+            severity: Severity::Error,
+            schema_fragment: None,
+            keyword: None,
+            suppressed: true,
+            document_index: None,
+            path_segments: Vec::new(),
+            schema_path_segments: Vec::new(),
+            show_schema_path: false,
+        };
+        let diagnostic = to_diagnostic(&error);
+        assert_eq!(diagnostic.range, Range::default());
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::HINT));
+        assert_eq!(diagnostic.tags, Some(vec![DiagnosticTag::UNNECESSARY]));
+        assert_eq!(diagnostic.message, "Something went wrong");
+        assert_eq!(diagnostic.code, None);
+    }
+}
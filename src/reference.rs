@@ -285,6 +285,114 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_circular_reference_to_root() {
+        let root_schema = loader::load_from_str(r##"$ref: "#""##).expect("Failed to load schema");
+        let context = crate::Context::with_root_schema(&root_schema, false);
+        let docs = saphyr::MarkedYaml::load_from_str("test").unwrap();
+        let result = root_schema.validate(&context, docs.first().unwrap());
+        assert!(result.is_ok());
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Circular $ref detected: #");
+    }
+
+    #[test]
+    fn test_recursive_tree_schema() {
+        let schema = r##"
+            $defs:
+                node:
+                    type: object
+                    properties:
+                        name:
+                            type: string
+                        children:
+                            type: array
+                            items:
+                                $ref: "#/$defs/node"
+                    required:
+                        - name
+            $ref: "#/$defs/node"
+        "##;
+        let root_schema = loader::load_from_str(schema).expect("Failed to load schema");
+        let tree = r#"
+            name: root
+            children:
+                - name: a
+                  children:
+                      - name: a1
+                      - name: a2
+                        children:
+                            - name: a2x
+                - name: b
+        "#;
+        let context = crate::Engine::evaluate(&root_schema, tree, false).unwrap();
+        assert!(
+            !context.has_errors(),
+            "Expected no errors, got: {:?}",
+            context.errors.borrow()
+        );
+
+        let tree = r#"
+            name: root
+            children:
+                - name: a
+                  children:
+                      - children: []
+        "#;
+        let context = crate::Engine::evaluate(&root_schema, tree, false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].error.contains("name"), "{}", errors[0].error);
+    }
+
+    #[test]
+    fn test_ref_into_properties_items_and_one_of() {
+        let schema = r##"
+            type: object
+            properties:
+                address:
+                    type: object
+                    properties:
+                        street:
+                            type: string
+                            minLength: 3
+                tags:
+                    type: array
+                    items:
+                        type: string
+                        maxLength: 5
+                mailing_street:
+                    $ref: "#/properties/address/properties/street"
+                label:
+                    $ref: "#/properties/tags/items"
+                choice:
+                    oneOf:
+                        - type: string
+                        - type: integer
+                code:
+                    $ref: "#/properties/choice/oneOf/1"
+        "##;
+        let root_schema = loader::load_from_str(schema).expect("Failed to load schema");
+        let context = crate::Engine::evaluate(
+            &root_schema,
+            "mailing_street: Main St\nlabel: short\ncode: 1",
+            false,
+        )
+        .unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+
+        let context = crate::Engine::evaluate(
+            &root_schema,
+            "mailing_street: M\nlabel: too long\ncode: x",
+            false,
+        )
+        .unwrap();
+        let errors = context.errors.borrow();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["mailing_street", "label", "code"]);
+    }
+
     #[test]
     fn test_non_circular_ref_still_works() {
         let schema = r##"
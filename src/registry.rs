@@ -0,0 +1,219 @@
+//! A registry of root schemas keyed by their `$id`, so schemas can cross-reference each other
+//! by `$id` URI instead of by file path.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use url::Url;
+
+use crate::Result;
+use crate::RootSchema;
+
+/// Holds several [`RootSchema`]s keyed by their `$id`.
+///
+/// A `$ref` such as `https://example.com/address.yaml#/$defs/street` is resolved against the
+/// registered schema whose `$id` is `https://example.com/address.yaml`, without any network or
+/// file access.
+///
+/// ```
+/// use yaml_schema::Engine;
+/// use yaml_schema::SchemaRegistry;
+/// use yaml_schema::ValidationOptions;
+/// use yaml_schema::loader;
+///
+/// let address = loader::load_from_str(r#"
+/// $id: https://example.com/address.yaml
+/// $defs:
+///   street:
+///     type: string
+/// "#).unwrap();
+/// let person = loader::load_from_str(r#"
+/// type: object
+/// properties:
+///   street:
+///     $ref: https://example.com/address.yaml#/$defs/street
+/// "#).unwrap();
+///
+/// let mut registry = SchemaRegistry::new();
+/// registry.register(address).unwrap();
+/// let options = ValidationOptions::default();
+/// let context = Engine::evaluate_with_registry(&person, "street: 42", &options, &registry).unwrap();
+/// assert!(context.has_errors());
+/// ```
+#[derive(Debug, Default)]
+pub struct SchemaRegistry {
+    schemas: HashMap<String, Arc<RootSchema>>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> SchemaRegistry {
+        SchemaRegistry::default()
+    }
+
+    /// Register a schema under its `$id`. The `$id` must be an absolute URI; any fragment is
+    /// ignored. If the schema has no base URI yet, its `$id` becomes the base for resolving
+    /// relative `$ref`s. Registering a second schema with the same `$id` replaces the first.
+    pub fn register(&mut self, mut root_schema: RootSchema) -> Result<()> {
+        let id = root_schema.id().ok_or_else(|| {
+            schema_loading_error!("Cannot register a schema without an `$id` in the registry")
+        })?;
+        let mut url = Url::parse(&id).map_err(|e| {
+            schema_loading_error!("Schema `$id` must be an absolute URI: {}: {}", id, e)
+        })?;
+        url.set_fragment(None);
+        if root_schema.base_uri.is_none() {
+            root_schema.base_uri = Some(url.clone());
+        }
+        self.schemas.insert(url.to_string(), Arc::new(root_schema));
+        Ok(())
+    }
+
+    /// Returns the schema registered under the given `$id`, if any
+    pub fn get(&self, id: &str) -> Option<&RootSchema> {
+        let key = Url::parse(id)
+            .map(|mut url| {
+                url.set_fragment(None);
+                url.to_string()
+            })
+            .unwrap_or_else(|_| id.to_string());
+        self.schemas.get(&key).map(Arc::as_ref)
+    }
+
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    /// The registered schemas keyed by normalized `$id`, in the form used by the validation
+    /// [`Context`](crate::Context) schema cache.
+    pub fn to_schemas(&self) -> HashMap<String, Arc<RootSchema>> {
+        self.schemas.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Engine;
+    use crate::ValidationOptions;
+    use crate::loader;
+
+    use super::*;
+
+    const ADDRESS: &str = r##"
+        $id: https://example.com/schemas/address.yaml
+        $defs:
+          street:
+            type: string
+            minLength: 3
+          address:
+            type: object
+            properties:
+              street:
+                $ref: "#/$defs/street"
+            required:
+              - street
+        "##;
+
+    const PERSON: &str = r##"
+        $id: https://example.com/schemas/person.yaml
+        type: object
+        properties:
+          name:
+            type: string
+          home:
+            $ref: "https://example.com/schemas/address.yaml#/$defs/address"
+        "##;
+
+    #[test]
+    fn test_register_and_get() {
+        let mut registry = SchemaRegistry::new();
+        assert!(registry.is_empty());
+        registry
+            .register(loader::load_from_str(ADDRESS).unwrap())
+            .unwrap();
+        assert_eq!(registry.len(), 1);
+        let address = registry
+            .get("https://example.com/schemas/address.yaml#/$defs/street")
+            .unwrap();
+        assert_eq!(address.defs().count(), 2);
+        assert!(registry.get("https://example.com/other.yaml").is_none());
+    }
+
+    #[test]
+    fn test_register_requires_id() {
+        let mut registry = SchemaRegistry::new();
+        let err = registry
+            .register(loader::load_from_str("type: string").unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("without an `$id`"), "{err}");
+    }
+
+    #[test]
+    fn test_cross_reference_by_id() {
+        let mut registry = SchemaRegistry::new();
+        registry
+            .register(loader::load_from_str(ADDRESS).unwrap())
+            .unwrap();
+        let person = loader::load_from_str(PERSON).unwrap();
+        let options = ValidationOptions::default();
+
+        let context = Engine::evaluate_with_registry(
+            &person,
+            "name: Alice\nhome:\n  street: Main St",
+            &options,
+            &registry,
+        )
+        .unwrap();
+        assert!(!context.has_errors());
+
+        let context = Engine::evaluate_with_registry(
+            &person,
+            "name: Alice\nhome:\n  street: M",
+            &options,
+            &registry,
+        )
+        .unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "home.street");
+
+        drop(errors);
+        let context =
+            Engine::evaluate_with_registry(&person, "name: Alice\nhome: {}", &options, &registry)
+                .unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn test_reference_by_id_under_contains() {
+        let mut registry = SchemaRegistry::new();
+        registry
+            .register(loader::load_from_str(ADDRESS).unwrap())
+            .unwrap();
+        let addresses = loader::load_from_str(
+            r#"
+            type: array
+            contains:
+              $ref: "https://example.com/schemas/address.yaml#/$defs/address"
+            "#,
+        )
+        .unwrap();
+        let options = ValidationOptions::default();
+
+        let context = Engine::evaluate_with_registry(
+            &addresses,
+            "- street: M\n- street: Main St",
+            &options,
+            &registry,
+        )
+        .unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+
+        let context =
+            Engine::evaluate_with_registry(&addresses, "- street: M", &options, &registry).unwrap();
+        assert!(context.has_errors());
+    }
+}
@@ -7,6 +7,7 @@ mod r#enum;
 mod format;
 mod if_then_else;
 mod integer;
+pub(crate) mod keywords;
 mod not;
 mod number;
 mod numeric;
@@ -33,4 +34,6 @@ pub use root_schema::RootSchema;
 pub use string::StringSchema;
 pub use yaml_schema::BooleanOrSchema;
 pub use yaml_schema::SchemaType;
+pub use yaml_schema::Subschema;
+pub use yaml_schema::SubschemaBuilder;
 pub use yaml_schema::YamlSchema;
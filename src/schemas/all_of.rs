@@ -5,12 +5,12 @@ use saphyr::MarkedYaml;
 use saphyr::YamlData;
 
 use crate::Context;
-use crate::Error;
 use crate::Result;
 use crate::Validator;
 use crate::YamlSchema;
 use crate::loader;
 use crate::utils::format_vec;
+use crate::validation::ValidationError;
 
 /// The `allOf` schema is a schema that matches if all of the schemas in the `allOf` array match.
 /// The schemas are tried in order, and the first match is used. If no match is found, an error is added
@@ -43,7 +43,7 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for AllOfSchema {
 
     fn try_from(mapping: &AnnotatedMapping<'r, MarkedYaml<'r>>) -> crate::Result<Self> {
         let all_of = match mapping.get(&MarkedYaml::value_from_str("allOf")) {
-            Some(value) => loader::load_array_of_schemas_marked(value)?,
+            Some(value) => loader::load_array_of_schemas_marked("allOf", value)?,
             None => {
                 debug!("[allOf] No `allOf` key found!");
                 Vec::new()
@@ -55,44 +55,45 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for AllOfSchema {
 
 impl Validator for AllOfSchema {
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
-        let all_of_is_valid = validate_all_of(&self.all_of, context, value)?;
-        debug!("[AllOf#validate] all_of_is_valid: {all_of_is_valid}");
-        if !all_of_is_valid {
-            debug!("[AllOf#validate] Not all of the schemas in `allOf` matched!");
-            context.add_error(value, "Not all of the schemas in `allOf` matched!");
-            fail_fast!(context);
-        }
-        Ok(())
+        context.with_schema_segment(&["allOf"], || {
+            let all_of_is_valid = validate_all_of(&self.all_of, context, value)?;
+            debug!("[AllOf#validate] all_of_is_valid: {all_of_is_valid}");
+            Ok(())
+        })
     }
 }
 
+/// Validates `value` against every schema, recording the errors of each in `context`, and
+/// returns whether all of them matched. Only stops at the first failing schema with `fail_fast`.
 pub fn validate_all_of(
     schemas: &[YamlSchema],
     context: &Context,
     value: &saphyr::MarkedYaml,
 ) -> Result<bool> {
-    for schema in schemas {
+    let mut all_matched = true;
+    for (i, schema) in schemas.iter().enumerate() {
         debug!("[AllOf#validate_all_of] Validating value: {value:?} against schema: {schema:?}");
-        // We can short circuit as soon as any sub schema fails to validate
         let sub_context = context.get_sub_context();
-        let sub_result = schema.validate(&sub_context, value);
-        match sub_result {
-            Ok(()) => {
-                debug!("[AllOf#validate_all_of] schema {schema:?} validated");
-                debug!(
-                    "[AllOf#validate_all_of] sub_context.has_errors(): {}",
-                    sub_context.has_errors()
-                );
-                if sub_context.has_errors() {
-                    return Ok(false);
-                }
+        let sub_result =
+            context.with_schema_segment(&[i.to_string()], || schema.validate(&sub_context, value));
+        debug!(
+            "[AllOf#validate_all_of] sub_context.has_errors(): {}",
+            sub_context.has_errors()
+        );
+        let matched = context.branch_matched("allOf", value, &sub_context, sub_result)?;
+        let errors = sub_context.errors.take();
+        // A member that couldn't be evaluated (e.g. an unresolvable `$ref`) leaves only a warning
+        let member_reported_error = errors.iter().any(ValidationError::is_error);
+        context.extend_errors(errors);
+        if !matched {
+            all_matched = false;
+            if !member_reported_error {
+                context.add_error(value, "Not all of the schemas in `allOf` matched!");
             }
-            Err(Error::FailFast) => return Ok(false),
-            Err(e) => return Err(e),
+            fail_fast!(context);
         }
     }
-    // If we get here, then all of the schemas matched
-    Ok(true)
+    Ok(all_matched)
 }
 
 #[cfg(test)]
@@ -136,6 +137,63 @@ mod tests {
         assert!(context.has_errors());
         let errors = context.errors.borrow();
         let error = errors.first().unwrap();
-        assert_eq!("Not all of the schemas in `allOf` matched!", error.error);
+        assert_eq!("String is too long! (max length: 5)", error.error);
+    }
+
+    fn required_errors(value: &str, fail_fast: bool) -> Vec<String> {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            allOf:
+              - required: [a]
+              - required: [b]
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(&root_schema, value, fail_fast).unwrap();
+        let errors = context.errors.borrow();
+        errors.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_all_of_combines_required() {
+        assert!(required_errors("a: 1\nb: 2", false).is_empty());
+        assert_eq!(
+            required_errors("a: 1", false),
+            vec!["[1:1] .: Required property 'b' is missing!"]
+        );
+        // Every member is validated, not only the first one that fails
+        assert_eq!(
+            required_errors("c: 3", false),
+            vec![
+                "[1:1] .: Required property 'a' is missing!",
+                "[1:1] .: Required property 'b' is missing!",
+            ]
+        );
+        assert_eq!(
+            required_errors("c: 3", true),
+            vec!["[1:1] .: Required property 'a' is missing!"]
+        );
+    }
+
+    #[test]
+    fn test_all_of_unresolvable_ref_is_an_error() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            allOf:
+              - $ref: "https://example.invalid/x.yaml"
+              - type: object
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "a: 1", false).unwrap();
+        assert!(context.has_errors());
+        let errors = context.errors.borrow();
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter(|e| e.is_error())
+            .map(|e| e.error.as_str())
+            .collect();
+        assert_eq!(messages, vec!["Not all of the schemas in `allOf` matched!"]);
+        assert_eq!(errors.last().unwrap().schema_path(), "#/allOf");
     }
 }
@@ -4,7 +4,6 @@ use saphyr::MarkedYaml;
 use saphyr::YamlData;
 
 use crate::Context;
-use crate::Error;
 use crate::Result;
 use crate::Validator;
 use crate::YamlSchema;
@@ -12,8 +11,8 @@ use crate::loader;
 use crate::utils::format_vec;
 
 /// The `anyOf` schema is a schema that matches if any of the schemas in the `anyOf` array match.
-/// The schemas are tried in order, and the first match is used. If no match is found, an error is added
-/// to the context.
+/// Every schema is tried, so the annotations of all matching ones are collected. If no match is
+/// found, an error is added to the context.
 #[derive(Debug, Default, PartialEq)]
 pub struct AnyOfSchema {
     pub any_of: Vec<YamlSchema>,
@@ -43,7 +42,7 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for AnyOfSchema {
     fn try_from(mapping: &AnnotatedMapping<'r, MarkedYaml<'r>>) -> crate::Result<Self> {
         let mut any_of_schema = AnyOfSchema::default();
         if let Some(value) = mapping.get(&MarkedYaml::value_from_str("anyOf")) {
-            any_of_schema.any_of = loader::load_array_of_schemas_marked(value)?;
+            any_of_schema.any_of = loader::load_array_of_schemas_marked("anyOf", value)?;
         } else {
             debug!("[anyOf] No `anyOf` key found!");
         }
@@ -53,14 +52,16 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for AnyOfSchema {
 
 impl Validator for crate::schemas::AnyOfSchema {
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
-        let any_of_is_valid = validate_any_of(&self.any_of, context, value)?;
-        debug!("any_of_is_valid: {any_of_is_valid}");
-        if !any_of_is_valid {
-            debug!("AnyOf: None of the schemas in `anyOf` matched!");
-            context.add_error(value, "None of the schemas in `anyOf` matched!");
-            fail_fast!(context);
-        }
-        Ok(())
+        context.with_schema_segment(&["anyOf"], || {
+            let any_of_is_valid = validate_any_of(&self.any_of, context, value)?;
+            debug!("any_of_is_valid: {any_of_is_valid}");
+            if !any_of_is_valid {
+                debug!("AnyOf: None of the schemas in `anyOf` matched!");
+                context.add_error(value, "None of the schemas in `anyOf` matched!");
+                fail_fast!(context);
+            }
+            Ok(())
+        })
     }
 }
 
@@ -71,31 +72,31 @@ pub fn validate_any_of(
 ) -> Result<bool> {
     debug!("[AnyOf] &context: {context:p}");
     let mut any_ok = false;
-    for schema in schemas {
+    for (i, schema) in schemas.iter().enumerate() {
+        if schema.type_excludes(context, marked_yaml) {
+            debug!("[AnyOf] Skipping schema {schema}: its type can't match");
+            continue;
+        }
         debug!("[AnyOf] Validating value: {marked_yaml:?} against schema: {schema}");
         let sub_context = context.get_sub_context_fresh_eval();
         debug!("[AnyOf]     context: {context:?}");
         debug!("[AnyOf] sub_context: {sub_context:?}");
-        match schema.validate(&sub_context, marked_yaml) {
-            Ok(()) | Err(Error::FailFast) => {
-                if sub_context.has_errors() {
-                    continue;
-                }
-                debug!("[AnyOf] Schema {schema:?} matched");
-                any_ok = true;
-                if let (Some(p), Some(b)) =
-                    (&context.object_evaluated, &sub_context.object_evaluated)
-                {
-                    p.extend(&b.snapshot());
-                }
-                if let (Some(pcell), Some(bcell)) =
-                    (&context.array_unevaluated, &sub_context.array_unevaluated)
-                {
-                    let snap = bcell.borrow().clone();
-                    pcell.borrow_mut().merge_from(&snap);
-                }
-            }
-            Err(e) => return Err(e),
+        let result = context.with_schema_segment(&[i.to_string()], || {
+            schema.validate(&sub_context, marked_yaml)
+        });
+        if !context.branch_matched("anyOf", marked_yaml, &sub_context, result)? {
+            continue;
+        }
+        debug!("[AnyOf] Schema {schema:?} matched");
+        any_ok = true;
+        if let (Some(p), Some(b)) = (&context.object_evaluated, &sub_context.object_evaluated) {
+            p.extend(&b.snapshot());
+        }
+        if let (Some(pcell), Some(bcell)) =
+            (&context.array_unevaluated, &sub_context.array_unevaluated)
+        {
+            let snap = bcell.borrow().clone();
+            pcell.borrow_mut().merge_from(&snap);
         }
     }
     debug!("[AnyOf] any_ok: {any_ok}");
@@ -150,4 +151,79 @@ mod tests {
             .expect("Validation failed");
         assert!(context.has_errors(), "Should NOT accept boolean");
     }
+
+    #[test]
+    fn test_any_of_broken_ref_branch_is_a_warning() {
+        let schema_str = r#"
+        anyOf:
+          - $ref: "./missing.yaml"
+          - type: string
+        "#;
+        let root_schema = loader::load_from_str(schema_str).expect("Failed to load schema");
+        let context = crate::Engine::evaluate(&root_schema, "hello", false)
+            .expect("Broken branch should not abort validation");
+        assert!(!context.has_errors());
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert!(!errors[0].is_error());
+        assert!(
+            errors[0]
+                .error
+                .starts_with("A branch of `anyOf` could not be evaluated: "),
+            "{}",
+            errors[0].error
+        );
+        drop(errors);
+
+        let context = crate::Engine::evaluate(&root_schema, "42", false).unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn test_any_of_collects_annotations_of_every_matching_branch() {
+        let schema_str = r#"
+        type: object
+        properties:
+          port:
+            anyOf:
+              - type: integer
+                description: A port number
+              - type: number
+                minimum: 1
+                description: A positive number
+              - type: string
+                description: A named port
+        "#;
+        let root_schema = loader::load_from_str(schema_str).expect("Failed to load schema");
+        let options = crate::ValidationOptions::default().collect_annotations(true);
+        let context =
+            crate::Engine::evaluate_with_options(&root_schema, "port: 8080", &options).unwrap();
+        assert!(!context.has_errors());
+        let annotations = context.annotations.as_ref().unwrap().borrow();
+        let collected: Vec<(&str, &str, &str)> = annotations
+            .iter()
+            .map(|a| (a.path.as_str(), a.keyword.as_str(), a.value.as_str()))
+            .collect();
+        assert_eq!(
+            collected,
+            vec![
+                ("port", "description", "A port number"),
+                ("port", "description", "A positive number"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_any_of_internal_error_aborts() {
+        let schema_str = r##"
+        anyOf:
+          - $ref: "#/$defs/missing"
+          - type: string
+        "##;
+        let root_schema = loader::load_from_str(schema_str).expect("Failed to load schema");
+        let value = MarkedYaml::value_from_str("hello");
+        let context = Context::default();
+        let result = root_schema.validate(&context, &value);
+        assert!(matches!(result, Err(crate::Error::MissingRootSchema)));
+    }
 }
@@ -61,12 +61,23 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for ArraySchema {
                                     s
                                 ));
                             }
+                        } else if let YamlData::Sequence(values) = &value.data {
+                            if !values
+                                .iter()
+                                .any(|v| v.data == MarkedYaml::value_from_str("array").data)
+                            {
+                                return Err(unsupported_type!(
+                                    "Expected type: array, but got: {:?}",
+                                    value
+                                ));
+                            }
                         } else {
                             return Err(expected_type_is_string!(value));
                         }
                     }
                     "prefixItems" => {
-                        let prefix_items = loader::load_array_of_schemas_marked(value)?;
+                        let prefix_items =
+                            loader::load_array_of_schemas_marked("prefixItems", value)?;
                         array_schema.prefix_items = Some(prefix_items);
                     }
                     "minContains" => {
@@ -145,30 +156,39 @@ impl Validator for ArraySchema {
         debug!("[ArraySchema] Validating value: {}", format_yaml_data(data));
 
         if let saphyr::YamlData::Sequence(array) = data {
-            let err_after_meta = context.errors.borrow().len();
+            let err_after_meta = context.error_count();
+            if context.observer.is_some() {
+                for (i, item) in array.iter().enumerate() {
+                    context.observe_node(Some(&i.to_string()), item);
+                }
+            }
 
             // validate contains with minContains / maxContains
             if let Some(min_items) = self.min_items
                 && array.len() < min_items
             {
-                context.add_error(
+                context.add_keyword_error(
                     value,
                     format!(
                         "Array has too few items (minimum {min_items}, found {})",
                         array.len()
                     ),
+                    "minItems",
+                    min_items,
                 );
                 fail_fast!(context);
             }
             if let Some(max_items) = self.max_items
                 && array.len() > max_items
             {
-                context.add_error(
+                context.add_keyword_error(
                     value,
                     format!(
                         "Array has too many items (maximum {max_items}, found {})",
                         array.len()
                     ),
+                    "maxItems",
+                    max_items,
                 );
                 fail_fast!(context);
             }
@@ -177,9 +197,11 @@ impl Validator for ArraySchema {
                 let mut seen = HashSet::with_capacity(array.len());
                 for item in array {
                     if !seen.insert(item) {
-                        context.add_error(
+                        context.add_keyword_error(
                             item,
                             format!("Duplicate array element: {}", format_yaml_data(&item.data)),
+                            "uniqueItems",
+                            true,
                         );
                         fail_fast!(context);
                     }
@@ -191,33 +213,38 @@ impl Validator for ArraySchema {
                 let match_count = array
                     .iter()
                     .filter(|item| {
-                        let sub_context = crate::Context {
-                            root_schema: context.root_schema,
-                            fail_fast: true,
-                            ..Default::default()
-                        };
+                        let sub_context = contains_item_context(context);
                         sub_schema.validate(&sub_context, item).is_ok() && !sub_context.has_errors()
                     })
                     .count() as u64;
 
                 let min = self.min_contains.unwrap_or(1);
                 if match_count < min {
-                    context.add_error(
-                        value,
-                        format!(
-                            "Array must contain at least {min} item(s) matching the contains schema, but only {match_count} matched"
-                        ),
-                    );
+                    let keyword = if self.min_contains.is_some() {
+                        "minContains"
+                    } else {
+                        "contains"
+                    };
+                    context.with_schema_segment(&[keyword], || {
+                        context.add_error(
+                            value,
+                            format!(
+                                "Array must contain at least {min} item(s) matching the contains schema, but only {match_count} matched"
+                            ),
+                        )
+                    });
                 }
                 if let Some(max) = self.max_contains
                     && match_count > max
                 {
-                    context.add_error(
-                        value,
-                        format!(
-                            "Array must contain at most {max} item(s) matching the contains schema, but {match_count} matched"
-                        ),
-                    );
+                    context.with_schema_segment(&["maxContains"], || {
+                        context.add_error(
+                            value,
+                            format!(
+                                "Array must contain at most {max} item(s) matching the contains schema, but {match_count} matched"
+                            ),
+                        )
+                    });
                 }
             }
 
@@ -234,7 +261,9 @@ impl Validator for ArraySchema {
                             "[ArraySchema] Validating prefix item {} with schema: {}",
                             i, prefix_items[i]
                         );
-                        prefix_items[i].validate(context, item)?;
+                        context.with_schema_segment(&["prefixItems", &i.to_string()], || {
+                            prefix_items[i].validate(&context.append_index(i), item)
+                        })?;
                     } else if let Some(items) = &self.items {
                         // if the index is not within the prefix items, validate against the array items schema
                         debug!("[ArraySchema] Validating array item {i} with schema: {items}");
@@ -244,13 +273,17 @@ impl Validator for ArraySchema {
                                 break;
                             }
                             BooleanOrSchema::Boolean(false) => {
-                                context.add_error(
-                                    item,
-                                    "Additional array items are not allowed!".to_string(),
-                                );
+                                context.with_schema_segment(&["items"], || {
+                                    context.append_index(i).add_error(
+                                        item,
+                                        "Additional array items are not allowed!".to_string(),
+                                    )
+                                });
                             }
                             BooleanOrSchema::Schema(yaml_schema) => {
-                                yaml_schema.validate(context, item)?;
+                                context.with_schema_segment(&["items"], || {
+                                    yaml_schema.validate(&context.append_index(i), item)
+                                })?;
                             }
                         }
                     } else {
@@ -264,27 +297,33 @@ impl Validator for ArraySchema {
                         BooleanOrSchema::Boolean(true) => { /* no-op */ }
                         BooleanOrSchema::Boolean(false) => {
                             if self.prefix_items.is_none() && !array.is_empty() {
-                                context
-                                    .add_error(value, "Array items are not allowed!".to_string());
+                                context.with_schema_segment(&["items"], || {
+                                    context.add_error(
+                                        value,
+                                        "Array items are not allowed!".to_string(),
+                                    )
+                                });
                             }
                         }
                         BooleanOrSchema::Schema(yaml_schema) => {
-                            for item in array {
-                                yaml_schema.validate(context, item)?;
+                            for (i, item) in array.iter().enumerate() {
+                                context.with_schema_segment(&["items"], || {
+                                    yaml_schema.validate(&context.append_index(i), item)
+                                })?;
                             }
                         }
                     }
                 }
             }
 
-            if context.errors.borrow().len() == err_after_meta {
+            if context.error_count() == err_after_meta {
                 Self::record_unevaluated_array_annotations(self, context, array);
             }
 
             Ok(())
         } else {
             debug!("[ArraySchema] context.fail_fast: {}", context.fail_fast);
-            context.add_error(
+            context.add_type_error(
                 value,
                 format!(
                     "Expected an array, but got: {}",
@@ -297,6 +336,17 @@ impl Validator for ArraySchema {
     }
 }
 
+/// A context for checking whether an item matches `contains`. It stops at the first error, and
+/// keeps the options and loaded schemas, but not the evaluated annotations, of `context`.
+fn contains_item_context<'r>(context: &Context<'r>) -> Context<'r> {
+    Context {
+        fail_fast: true,
+        object_evaluated: None,
+        array_unevaluated: None,
+        ..context.get_sub_context()
+    }
+}
+
 impl ArraySchema {
     /// Update [`Context::array_unevaluated`] from this schema's `prefixItems` / `items` / `contains` (2020-12).
     fn record_unevaluated_array_annotations(
@@ -316,11 +366,7 @@ impl ArraySchema {
             } else {
                 let mut matching = HashSet::new();
                 for (i, item) in array.iter().enumerate() {
-                    let sub_context = Context {
-                        root_schema: context.root_schema,
-                        fail_fast: true,
-                        ..Default::default()
-                    };
+                    let sub_context = contains_item_context(context);
                     if sub_schema.validate(&sub_context, item).is_ok() && !sub_context.has_errors()
                     {
                         matching.insert(i);
@@ -397,6 +443,21 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_items_type_name_hint() {
+        let err = crate::loader::load_from_str("type: array\nitems: \"string\"").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message
+                .contains("'items' expects a boolean or a mapping, but got a string: \"string\""),
+            "{message}"
+        );
+        assert!(
+            message.contains("(hint: to constrain the type, write `items: { type: string }`)"),
+            "{message}"
+        );
+    }
+
     #[test]
     fn test_array_schema_prefix_items() {
         let schema = ArraySchema {
@@ -593,6 +654,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_prefix_items_with_min_items() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: array
+            prefixItems:
+              - type: string
+              - type: integer
+              - type: boolean
+            minItems: 3
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "[\"a\", 1, true]", false).unwrap();
+        assert!(!context.has_errors());
+
+        // Missing positions are fine for `prefixItems`; only `minItems` fails
+        let context = crate::Engine::evaluate(&root_schema, "[\"a\", 1]", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(
+            errors[0].error,
+            "Array has too few items (minimum 3, found 2)"
+        );
+    }
+
+    #[test]
+    fn test_items_enum_without_type() {
+        let root_schema =
+            crate::loader::load_from_str("type: array\nitems:\n  enum: [a, b]").unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "[a, b, a]", false).unwrap();
+        assert!(!context.has_errors());
+
+        let context = crate::Engine::evaluate(&root_schema, "- a\n- b\n- c", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].path, "2");
+        assert_eq!(
+            errors[0].to_string(),
+            "[3:3] .2: Value \"c\" is not in the enum: [\"a\", \"b\"]"
+        );
+    }
+
     #[test]
     fn test_max_items_from_yaml() {
         let schema_string = "type: array\nmaxItems: 5";
@@ -760,4 +864,50 @@ mod tests {
         schema.validate(&context, docs.first().unwrap()).unwrap();
         assert!(context.errors.take().is_empty());
     }
+
+    #[test]
+    fn test_contains_uses_validation_options() {
+        let root_schema =
+            crate::loader::load_from_str("contains:\n  enum: [Red]\nunevaluatedItems: false")
+                .unwrap();
+        let options = crate::ValidationOptions::default().case_insensitive_enums(true);
+        let context =
+            crate::Engine::evaluate_with_options(&root_schema, "- red", &options).unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+
+        let context = crate::Engine::evaluate(&root_schema, "- red", false).unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn test_item_errors_report_index() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              ports:
+                type: array
+                items:
+                  type: integer
+              pair:
+                type: array
+                prefixItems:
+                  - type: string
+                  - type: integer
+                items: false
+            "#,
+        )
+        .unwrap();
+        let value = "ports: [80, 443, http]\npair: [a, b, c]";
+        let context = crate::Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors = context.errors.borrow();
+        let paths: Vec<(&str, usize)> = errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.marker.unwrap().col() + 1))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![("ports.2", 18), ("pair.1", 11), ("pair.2", 14),]
+        );
+    }
 }
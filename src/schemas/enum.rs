@@ -8,6 +8,8 @@ use crate::ConstValue;
 use crate::Context;
 use crate::Result;
 use crate::Validator;
+use crate::fixes::FixSuggestion;
+use crate::utils::format_const_like;
 use crate::utils::format_vec;
 use crate::utils::format_yaml_data;
 
@@ -41,6 +43,33 @@ impl TryFrom<&MarkedYaml<'_>> for EnumSchema {
     }
 }
 
+impl EnumSchema {
+    /// The single enum member equal to the string `value` when compared case-insensitively.
+    /// Returns `None` if the value is not a string or if zero or several members match.
+    fn case_insensitive_match(&self, value: &ConstValue) -> Option<&ConstValue> {
+        let mut matches = self.case_insensitive_matches(value);
+        let first = matches.next()?;
+        if matches.next().is_some() {
+            None
+        } else {
+            Some(first)
+        }
+    }
+
+    /// The string enum members equal to the string `value` when compared case-insensitively
+    fn case_insensitive_matches<'a>(
+        &'a self,
+        value: &ConstValue,
+    ) -> impl Iterator<Item = &'a ConstValue> {
+        self.r#enum
+            .iter()
+            .filter(move |member| match (member, value) {
+                (ConstValue::String(m), ConstValue::String(s)) => m.eq_ignore_ascii_case(s),
+                _ => false,
+            })
+    }
+}
+
 pub fn load_enum_values(values: &AnnotatedSequence<MarkedYaml>) -> Result<Vec<ConstValue>> {
     values.iter().map(|v| v.try_into()).collect()
 }
@@ -64,14 +93,11 @@ impl Validator for EnumSchema {
             }
         };
         debug!("[EnumSchema] const_value: {const_value}");
-        for value in &self.r#enum {
-            debug!("[EnumSchema] value: {value}");
-            if value.eq(&const_value) {
-                return Ok(());
-            }
-        }
-        if !self.r#enum.contains(&const_value) {
-            let value_str = format_yaml_data(data);
+        let accepted = self.r#enum.iter().any(|member| member.accepts(value))
+            || (context.options.case_insensitive_enums
+                && self.case_insensitive_matches(&const_value).next().is_some());
+        if !accepted {
+            let value_str = format_const_like(data);
             let enum_values = self
                 .r#enum
                 .iter()
@@ -80,7 +106,16 @@ impl Validator for EnumSchema {
                 .join(", ");
             let error = format!("Value {value_str} is not in the enum: [{enum_values}]");
             debug!("[EnumSchema] error: {error}");
-            context.add_error(value, error);
+            let suggestion = self
+                .case_insensitive_match(&const_value)
+                .map(|member| FixSuggestion::replace(context.pointer(), member.clone()));
+            context.add_keyword_error_with_suggestion(
+                value,
+                error,
+                "enum",
+                format!("[{enum_values}]"),
+                suggestion,
+            );
         }
         Ok(())
     }
@@ -105,6 +140,28 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_case_insensitive_enums_option() {
+        let root_schema = loader::load_from_str("enum: [Red, Green]").unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "red", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error,
+            "Value \"red\" is not in the enum: [\"Red\", \"Green\"]"
+        );
+        drop(errors);
+
+        let options = crate::ValidationOptions::default().case_insensitive_enums(true);
+        for accepted in ["red", "Red", "GREEN"] {
+            let context =
+                crate::Engine::evaluate_with_options(&root_schema, accepted, &options).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = crate::Engine::evaluate_with_options(&root_schema, "blue", &options).unwrap();
+        assert!(context.has_errors());
+    }
+
     #[test]
     fn test_loading_enum_schema() {
         let schema = r#"
@@ -145,4 +202,49 @@ mod tests {
             "Value \"blue\" is not in the enum: [\"red\", \"amber\", \"green\"]"
         );
     }
+
+    #[test]
+    fn test_enum_compares_like_const() {
+        let root = loader::load_from_str("enum: [1, {a: 1, b: 2}]").unwrap();
+        for accepted in ["1.0", "{b: 2, a: 1}"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = crate::Engine::evaluate(&root, "{a: 1}", false).unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn test_enum_of_mixed_scalars_error() {
+        let root = loader::load_from_str("enum: [red, amber, green, null, 42, true]").unwrap();
+        for accepted in ["red", "null", "42", "true"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = crate::Engine::evaluate(&root, "blue", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            r#"[1:1] .: Value "blue" is not in the enum: ["red", "amber", "green", null, 42, true]"#
+        );
+    }
+
+    #[test]
+    fn test_enum_of_string_list_and_map() {
+        let root = loader::load_from_str("enum: [auto, [1, 2], {a: 1}]").unwrap();
+        for accepted in ["auto", "[1, 2]", "- 1\n- 2", "a: 1"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for rejected in ["[2, 1]", "[1, 2, 3]", "a: 2", "{a: 1, b: 2}", "[auto]"] {
+            let context = crate::Engine::evaluate(&root, rejected, false).unwrap();
+            assert!(context.has_errors(), "{rejected}");
+        }
+        let context = crate::Engine::evaluate(&root, "[2, 1]", false).unwrap();
+        assert_eq!(
+            context.errors.borrow()[0].error,
+            r#"Value [2, 1] is not in the enum: ["auto", [1, 2], {"a": 1}]"#
+        );
+    }
 }
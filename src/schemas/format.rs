@@ -4,7 +4,8 @@ use std::str::FromStr;
 /// Represents a JSON Schema `format` value for string validation.
 ///
 /// Known formats are validated; unknown formats are annotation-only
-/// (they always pass validation).
+/// (they always pass validation). `json` is a non-standard extension for strings holding
+/// embedded JSON documents.
 #[derive(Clone, PartialEq, Eq)]
 pub enum StringFormat {
     DateTime,
@@ -26,6 +27,7 @@ pub enum StringFormat {
     JsonPointer,
     RelativeJsonPointer,
     Regex,
+    Json,
     Unknown(String),
 }
 
@@ -53,6 +55,7 @@ impl FromStr for StringFormat {
             "json-pointer" => StringFormat::JsonPointer,
             "relative-json-pointer" => StringFormat::RelativeJsonPointer,
             "regex" => StringFormat::Regex,
+            "json" => StringFormat::Json,
             other => StringFormat::Unknown(other.to_string()),
         })
     }
@@ -80,6 +83,7 @@ impl fmt::Display for StringFormat {
             StringFormat::JsonPointer => write!(f, "json-pointer"),
             StringFormat::RelativeJsonPointer => write!(f, "relative-json-pointer"),
             StringFormat::Regex => write!(f, "regex"),
+            StringFormat::Json => write!(f, "json"),
             StringFormat::Unknown(s) => write!(f, "{s}"),
         }
     }
@@ -115,6 +119,7 @@ fn capitalize_variant(format: &StringFormat) -> &'static str {
         StringFormat::JsonPointer => "JsonPointer",
         StringFormat::RelativeJsonPointer => "RelativeJsonPointer",
         StringFormat::Regex => "Regex",
+        StringFormat::Json => "Json",
         StringFormat::Unknown(_) => unreachable!(),
     }
 }
@@ -145,6 +150,7 @@ mod tests {
             ("json-pointer", StringFormat::JsonPointer),
             ("relative-json-pointer", StringFormat::RelativeJsonPointer),
             ("regex", StringFormat::Regex),
+            ("json", StringFormat::Json),
         ];
         for (input, expected) in cases {
             let parsed: StringFormat = input.parse().unwrap();
@@ -183,6 +189,7 @@ mod tests {
             "json-pointer",
             "relative-json-pointer",
             "regex",
+            "json",
         ];
         for input in cases {
             let parsed: StringFormat = input.parse().unwrap();
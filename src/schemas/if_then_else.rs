@@ -8,7 +8,6 @@ use saphyr::MarkedYaml;
 use saphyr::YamlData;
 
 use crate::Context;
-use crate::Error;
 use crate::Result;
 use crate::Validator;
 use crate::YamlSchema;
@@ -83,12 +82,10 @@ impl Validator for IfThenElseSchema {
             self.if_schema
         );
         let if_context = context.get_sub_context_fresh_eval();
-        let if_result = self.if_schema.validate(&if_context, value);
+        let if_result =
+            context.with_schema_segment(&["if"], || self.if_schema.validate(&if_context, value));
 
-        let if_passed = match if_result {
-            Ok(()) | Err(Error::FailFast) => !if_context.has_errors(),
-            Err(e) => return Err(e),
-        };
+        let if_passed = context.branch_matched("if", value, &if_context, if_result)?;
 
         if if_passed {
             if let (Some(p), Some(f)) = (&context.object_evaluated, &if_context.object_evaluated) {
@@ -101,10 +98,10 @@ impl Validator for IfThenElseSchema {
                 pcell.borrow_mut().merge_from(&snap);
             }
             if let Some(then_s) = &self.then_schema {
-                then_s.validate(context, value)?;
+                context.with_schema_segment(&["then"], || then_s.validate(context, value))?;
             }
         } else if let Some(else_s) = &self.else_schema {
-            else_s.validate(context, value)?;
+            context.with_schema_segment(&["else"], || else_s.validate(context, value))?;
         }
 
         Ok(())
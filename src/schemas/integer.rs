@@ -6,6 +6,7 @@ use saphyr::YamlData;
 
 use crate::Number;
 use crate::Result;
+use crate::loader::complex_key_error;
 use crate::schemas::NumericBounds;
 use crate::utils::format_marker;
 use crate::utils::humanize_yaml_data;
@@ -45,18 +46,20 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for IntegerSchema {
                         schema.bounds.maximum = Some(value.try_into()?);
                     }
                     "exclusiveMinimum" => {
-                        schema.bounds.exclusive_minimum = Some(value.try_into()?);
+                        schema.bounds.load_exclusive_minimum(value)?;
                     }
                     "exclusiveMaximum" => {
-                        schema.bounds.exclusive_maximum = Some(value.try_into()?);
+                        schema.bounds.load_exclusive_maximum(value)?;
                     }
                     "multipleOf" => {
-                        schema.bounds.multiple_of = Some(value.try_into()?);
+                        schema.bounds.multiple_of = Some(NumericBounds::load_multiple_of(value)?);
                     }
                     _ => {
                         debug!("Unsupported key for `type: integer`: {}", key);
                     }
                 }
+            } else if let YamlData::Mapping(_) | YamlData::Sequence(_) = &key.data {
+                return Err(complex_key_error(key));
             } else {
                 return Err(expected_scalar!(
                     "{} Expected string key, got {:?}",
@@ -65,6 +68,7 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for IntegerSchema {
                 ));
             }
         }
+        schema.bounds.check_draft04_bounds()?;
         Ok(schema)
     }
 }
@@ -87,19 +91,19 @@ impl Validator for IntegerSchema {
                     self.bounds
                         .validate(context, value, Number::Integer(f as i64));
                 } else {
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!("Expected an integer, but got: {}", humanize_yaml_data(data)),
                     );
                 }
             } else {
-                context.add_error(
+                context.add_type_error(
                     value,
                     format!("Expected a number, but got: {}", humanize_yaml_data(data)),
                 );
             }
         } else {
-            context.add_error(
+            context.add_type_error(
                 value,
                 format!(
                     "Expected a scalar value, but got: {}",
@@ -282,4 +286,98 @@ mod tests {
             Some("The description".to_string())
         );
     }
+
+    #[test]
+    fn test_unknown_keys_and_annotations_are_ignored() {
+        let root = crate::loader::load_from_str(
+            r#"
+            type: integer
+            title: Port
+            description: The port to listen on
+            default: 8080
+            examples: [80, 443]
+            $comment: Privileged ports need root
+            enum: [80, 443, 8080]
+            x-unknown-extension: true
+            minimum: 1
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(&root, "443", false).unwrap();
+        assert!(!context.has_errors());
+        let context = crate::Engine::evaluate(&root, "81", false).unwrap();
+        assert!(context.has_errors());
+
+        // The integer-only path ignores keys it doesn't know
+        let doc = MarkedYaml::load_from_str(
+            "maximum: 10
+unknownKey: 1",
+        )
+        .unwrap();
+        let schema = IntegerSchema::try_from(doc.first().unwrap()).unwrap();
+        assert_eq!(schema.bounds.maximum, Some(Number::Integer(10)));
+    }
+
+    #[test]
+    fn test_multiple_of_zero_fails_to_load() {
+        let err = crate::loader::load_from_str("type: integer\nmultipleOf: 0").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [2, 12] 'multipleOf' must be greater than 0, but got: 0"
+        );
+        let err = crate::loader::load_from_str("type: integer\nmultipleOf: -0.5").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'multipleOf' must be greater than 0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_fractional_multiple_of() {
+        // 7 / 0.7 is 10.000000000000002 in floating point
+        let root = crate::loader::load_from_str("type: integer\nmultipleOf: 0.7").unwrap();
+        for accepted in ["0", "7", "-14", "70"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = crate::Engine::evaluate(&root, "3", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number is not a multiple of 0.7!");
+    }
+
+    #[test]
+    fn test_draft04_exclusive_maximum() {
+        let root =
+            crate::loader::load_from_str("type: integer\nmaximum: 10\nexclusiveMaximum: true")
+                .unwrap();
+        let context = crate::Engine::evaluate(&root, "10", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number must be less than 10");
+        drop(errors);
+        let context = crate::Engine::evaluate(&root, "9", false).unwrap();
+        assert!(!context.has_errors());
+    }
+
+    #[test]
+    fn test_inclusive_bounds_accept_boundaries() {
+        let root = crate::loader::load_from_str("type: integer\nminimum: 1\nmaximum: 10").unwrap();
+        for accepted in ["1", "10", "1.0", "10.0", "5"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for (rejected, error) in [
+            ("0", "Number must be greater than or equal to 1"),
+            ("0.0", "Number must be greater than or equal to 1"),
+            ("11", "Number must be less than or equal to 10"),
+            ("11.0", "Number must be less than or equal to 10"),
+        ] {
+            let context = crate::Engine::evaluate(&root, rejected, false).unwrap();
+            let errors = context.errors.borrow();
+            assert_eq!(errors.len(), 1, "{rejected}");
+            assert_eq!(errors[0].error, error, "{rejected}");
+        }
+    }
 }
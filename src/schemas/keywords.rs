@@ -0,0 +1,434 @@
+//! The JSON Schema keywords, and how each nests further schemas, so a schema document can be
+//! checked for unknown keywords (usually typos such as `minmum: 3`) and formats in one place
+
+use saphyr::MarkedYaml;
+use saphyr::Scalar;
+use saphyr::YamlData;
+
+use crate::Error;
+use crate::schemas::StringFormat;
+use crate::utils::format_marker;
+
+/// Keywords whose value is a single schema
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "contentSchema",
+    "else",
+    "if",
+    "items",
+    "not",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+];
+
+/// Keywords whose value is an array of schemas (`items` may also be one, in draft-04 to 2019-09)
+const SCHEMA_ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "items", "oneOf", "prefixItems"];
+
+/// Keywords whose value is a mapping of names to schemas (`dependencies` values may also be
+/// arrays of property names)
+const SCHEMA_MAP_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependencies",
+    "dependentSchemas",
+    "patternProperties",
+    "properties",
+];
+
+/// The other keywords, whose values aren't schemas
+const VALUE_KEYWORDS: &[&str] = &[
+    "$anchor",
+    "$comment",
+    "$dynamicAnchor",
+    "$dynamicRef",
+    "$id",
+    "$ref",
+    "$schema",
+    "$vocabulary",
+    "const",
+    "contentEncoding",
+    "contentMediaType",
+    "default",
+    "dependentRequired",
+    "deprecated",
+    "description",
+    "enum",
+    "examples",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "format",
+    "maxContains",
+    "maximum",
+    "maxItems",
+    "maxLength",
+    "maxProperties",
+    "minContains",
+    "minimum",
+    "minItems",
+    "minLength",
+    "minProperties",
+    "multipleOf",
+    "pattern",
+    "readOnly",
+    "required",
+    "title",
+    "type",
+    "uniqueItems",
+    "writeOnly",
+];
+
+/// Keywords that constrain an object as a whole, and whose values aren't schemas, so a property
+/// with one of these names is likely the keyword placed one level too deep. (Meta-schemas define
+/// properties named after the keywords that take schemas, such as `additionalProperties`.)
+const OBJECT_KEYWORDS: &[&str] = &["maxProperties", "minProperties", "required"];
+
+/// Keywords that only apply to strings
+const STRING_KEYWORDS: &[&str] = &["maxLength", "minLength", "pattern"];
+
+/// Keywords that only apply to numbers
+const NUMBER_KEYWORDS: &[&str] = &[
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "maximum",
+    "minimum",
+    "multipleOf",
+];
+
+/// A keyword that is likely at the wrong nesting level, see [`misplaced_keywords`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisplacedKeyword {
+    pub message: String,
+    /// Whether the schema can't be meant as written, so strict loading rejects it. Otherwise the
+    /// schema may well be right, e.g. a property genuinely named `required`.
+    pub certain: bool,
+}
+
+/// Whether `key` is a JSON Schema keyword, or an `x-` extension
+pub fn is_known_keyword(key: &str) -> bool {
+    key.starts_with("x-")
+        || [
+            SCHEMA_KEYWORDS,
+            SCHEMA_ARRAY_KEYWORDS,
+            SCHEMA_MAP_KEYWORDS,
+            VALUE_KEYWORDS,
+        ]
+        .iter()
+        .any(|keywords| keywords.contains(&key))
+}
+
+/// Describe each unknown keyword in the schema document `schema` and all of its subschemas, in
+/// document order, e.g. `[3, 4] Unknown keyword 'minmum' (did you mean 'minimum'?)`
+pub fn unknown_keywords(schema: &MarkedYaml) -> Vec<String> {
+    let mut found = Vec::new();
+    for_each_key(schema, &mut |_, name, key, _| {
+        if !is_known_keyword(name) {
+            let mut message = format!(
+                "{} Unknown keyword '{name}'",
+                format_marker(&key.span.start)
+            );
+            if let Some(suggestion) = closest_keyword(name) {
+                message.push_str(&format!(" (did you mean '{suggestion}'?)"));
+            }
+            found.push(message);
+        }
+    });
+    found
+}
+
+/// Describe each `format` in the schema document `schema` and all of its subschemas that isn't
+/// one of the [`StringFormat`]s, e.g. `[3, 12] Unknown format 'int32'`
+pub fn unknown_formats(schema: &MarkedYaml) -> Vec<String> {
+    let mut found = Vec::new();
+    for_each_key(schema, &mut |_, name, _, value| {
+        if name == "format"
+            && let YamlData::Value(Scalar::String(format)) = &value.data
+            && let Ok(StringFormat::Unknown(_)) = format.parse()
+        {
+            found.push(format!(
+                "{} Unknown format '{format}'",
+                format_marker(&value.span.start)
+            ));
+        }
+    });
+    found
+}
+
+/// Describe each keyword in the schema document `schema` and all of its subschemas that is likely
+/// at the wrong nesting level: a property under `properties` named after an object keyword, such
+/// as `required`, and a string or number keyword, such as `minLength`, in a `type: object` schema.
+///
+/// Non-schema values under `properties` fail to load, see
+/// [`misplaced_property_error`].
+pub fn misplaced_keywords(schema: &MarkedYaml) -> Vec<MisplacedKeyword> {
+    let mut found = Vec::new();
+    for_each_key(schema, &mut |parent, name, key, value| {
+        if name == "properties"
+            && let YamlData::Mapping(properties) = &value.data
+        {
+            for property in properties.keys() {
+                if let YamlData::Value(Scalar::String(property_name)) = &property.data
+                    && OBJECT_KEYWORDS.contains(&property_name.as_ref())
+                {
+                    found.push(MisplacedKeyword {
+                        message: misplaced_property_message(property, property_name),
+                        certain: false,
+                    });
+                }
+            }
+        }
+        let applies_to = if STRING_KEYWORDS.contains(&name) {
+            "strings"
+        } else if NUMBER_KEYWORDS.contains(&name) {
+            "numbers"
+        } else {
+            return;
+        };
+        if schema_type(parent) == Some("object") {
+            found.push(MisplacedKeyword {
+                message: format!(
+                    "{} '{name}' applies to {applies_to}, but is in a schema of type 'object'; \
+                     did you mean to place it in the schema of a property?",
+                    format_marker(&key.span.start)
+                ),
+                certain: true,
+            });
+        }
+    });
+    found
+}
+
+/// The error for a property under `properties` whose value isn't a schema. When the property is
+/// named after a keyword, e.g. `required: [name]`, it says so.
+pub fn misplaced_property_error(key: &MarkedYaml, name: &str, value: &MarkedYaml) -> Error {
+    if !name.starts_with("x-") && is_known_keyword(name) {
+        Error::SchemaLoadingError(misplaced_property_message(key, name))
+    } else {
+        generic_error!(
+            "properties: Expected a mapping for \"{}\", but got: {:?}",
+            name,
+            value
+        )
+    }
+}
+
+fn misplaced_property_message(key: &MarkedYaml, name: &str) -> String {
+    format!(
+        "{} '{name}' under 'properties' defines a property named '{name}'; did you mean to \
+         place it at the object level?",
+        format_marker(&key.span.start)
+    )
+}
+
+/// The `type` of the schema `schema`, when it's a single type name
+fn schema_type<'a>(schema: &'a MarkedYaml) -> Option<&'a str> {
+    let YamlData::Mapping(mapping) = &schema.data else {
+        return None;
+    };
+    mapping
+        .iter()
+        .find_map(|(key, value)| match (&key.data, &value.data) {
+            (YamlData::Value(Scalar::String(key)), YamlData::Value(Scalar::String(type_name)))
+                if key == "type" =>
+            {
+                Some(type_name.as_ref())
+            }
+            _ => None,
+        })
+}
+
+/// Call `f` with the schema, name, key and value of each entry of the schema `schema` and all
+/// of its subschemas, in document order
+fn for_each_key<'a>(
+    schema: &'a MarkedYaml,
+    f: &mut impl FnMut(&'a MarkedYaml, &'a str, &'a MarkedYaml, &'a MarkedYaml),
+) {
+    let YamlData::Mapping(mapping) = &schema.data else {
+        return;
+    };
+    for (key, value) in mapping.iter() {
+        let YamlData::Value(Scalar::String(key_str)) = &key.data else {
+            continue;
+        };
+        let key_str = key_str.as_ref();
+        f(schema, key_str, key, value);
+        if key_str.starts_with("x-") {
+            continue;
+        }
+        if SCHEMA_KEYWORDS.contains(&key_str) {
+            for_each_key(value, f);
+        }
+        if SCHEMA_ARRAY_KEYWORDS.contains(&key_str)
+            && let YamlData::Sequence(schemas) = &value.data
+        {
+            for schema in schemas {
+                for_each_key(schema, f);
+            }
+        }
+        if SCHEMA_MAP_KEYWORDS.contains(&key_str)
+            && let YamlData::Mapping(schemas) = &value.data
+        {
+            for schema in schemas.values() {
+                for_each_key(schema, f);
+            }
+        }
+    }
+}
+
+/// The keyword `key` is most likely a typo of: the closest one within an edit distance of 2
+/// (1 for short keys), ignoring case
+fn closest_keyword(key: &str) -> Option<&'static str> {
+    let key = key.to_ascii_lowercase();
+    let max_distance = if key.len() <= 4 { 1 } else { 2 };
+    [
+        SCHEMA_KEYWORDS,
+        SCHEMA_ARRAY_KEYWORDS,
+        SCHEMA_MAP_KEYWORDS,
+        VALUE_KEYWORDS,
+    ]
+    .iter()
+    .flat_map(|keywords| keywords.iter())
+    .map(|keyword| (edit_distance(&key, &keyword.to_ascii_lowercase()), *keyword))
+    .filter(|(distance, _)| *distance <= max_distance)
+    .min()
+    .map(|(_, keyword)| keyword)
+}
+
+/// The Levenshtein distance between `a` and `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use saphyr::LoadableYamlNode;
+
+    use super::*;
+
+    fn unknown(schema: &str) -> Vec<String> {
+        let docs = MarkedYaml::load_from_str(schema).unwrap();
+        unknown_keywords(docs.first().unwrap())
+    }
+
+    #[test]
+    fn test_unknown_keywords_in_nested_schemas() {
+        let schema = r#"
+type: object
+minProperties: 1
+properties:
+  port:
+    type: integer
+    minmum: 1
+  tags:
+    type: array
+    items:
+      type: string
+      patern: "^[a-z]+$"
+x-internal: true
+anyOf:
+  - required: [port]
+  - requierd: [tags]
+$defs:
+  name:
+    type: string
+    foo: bar
+const:
+  notAKeyword: 1
+"#;
+        assert_eq!(
+            unknown(schema),
+            vec![
+                "[7, 4] Unknown keyword 'minmum' (did you mean 'minimum'?)",
+                "[12, 6] Unknown keyword 'patern' (did you mean 'pattern'?)",
+                "[16, 4] Unknown keyword 'requierd' (did you mean 'required'?)",
+                "[20, 4] Unknown keyword 'foo'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shipped_meta_schema_has_no_unknown_keywords() {
+        let schema = std::fs::read_to_string("yaml-schema.yaml").unwrap();
+        assert_eq!(unknown(&schema), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_unknown_formats() {
+        let docs = MarkedYaml::load_from_str(
+            "format: date\nproperties:\n  id:\n    format: int32\n  tags:\n    items:\n      format: ipv9\n",
+        )
+        .unwrap();
+        assert_eq!(
+            unknown_formats(docs.first().unwrap()),
+            vec![
+                "[4, 12] Unknown format 'int32'",
+                "[7, 14] Unknown format 'ipv9'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_misplaced_keywords() {
+        let docs = MarkedYaml::load_from_str(
+            r#"
+type: object
+minLength: 3
+properties:
+  name:
+    type: string
+    minLength: 1
+  required:
+    type: boolean
+  port:
+    type: object
+    maximum: 65535
+"#,
+        )
+        .unwrap();
+        let misplaced = misplaced_keywords(docs.first().unwrap());
+        assert_eq!(
+            misplaced,
+            vec![
+                MisplacedKeyword {
+                    message: "[3, 0] 'minLength' applies to strings, but is in a schema of type \
+                              'object'; did you mean to place it in the schema of a property?"
+                        .to_string(),
+                    certain: true,
+                },
+                MisplacedKeyword {
+                    message: "[8, 2] 'required' under 'properties' defines a property named \
+                              'required'; did you mean to place it at the object level?"
+                        .to_string(),
+                    certain: false,
+                },
+                MisplacedKeyword {
+                    message: "[12, 4] 'maximum' applies to numbers, but is in a schema of type \
+                              'object'; did you mean to place it in the schema of a property?"
+                        .to_string(),
+                    certain: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("minimum", "minimum"), 0);
+        assert_eq!(edit_distance("minmum", "minimum"), 1);
+        assert_eq!(edit_distance("requierd", "required"), 2);
+        assert_eq!(edit_distance("", "type"), 4);
+    }
+}
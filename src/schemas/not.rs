@@ -6,6 +6,7 @@ use saphyr::MarkedYaml;
 use saphyr::YamlData;
 
 use crate::Context;
+use crate::Error;
 use crate::Result;
 use crate::Validator;
 use crate::YamlSchema;
@@ -57,22 +58,78 @@ impl Validator for NotSchema {
         );
 
         // Create a sub-context to validate against the inner schema
-        let sub_context = context.get_sub_context();
-        let sub_result = self.not.validate(&sub_context, value);
-
-        match sub_result {
-            Ok(()) | Err(crate::Error::FailFast) => {
-                // If the inner schema validates successfully, then this is an error for 'not'
-                if !sub_context.has_errors() {
-                    context.add_error(value, "Value matches schema in `not`");
-                    fail_fast!(context);
-                }
+        context.with_schema_segment(&["not"], || {
+            let sub_context = context.get_sub_context();
+            let sub_result = match self.not.validate(&sub_context, value) {
+                // Unlike a failing `anyOf` or `oneOf` branch, an inner schema that can't be
+                // evaluated (e.g. an unresolvable `$ref`) must not make `not` pass
+                Err(e) if !matches!(e, Error::FailFast) => return Err(e),
+                result => result,
+            };
+
+            // If the inner schema validates successfully, then this is an error for 'not'. Errors
+            // from the inner schema stay on the sub-context.
+            if context.branch_matched("not", value, &sub_context, sub_result)? {
+                context.add_error(value, "Value must not match the `not` schema");
+                fail_fast!(context);
             }
-            Err(e) => return Err(e),
-        }
 
-        // If we get here, then the inner schema failed validation, which means
-        // this 'not' validation succeeds
-        Ok(())
+            // If we get here, then the inner schema failed validation, which means
+            // this 'not' validation succeeds
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Engine;
+    use crate::loader;
+
+    #[test]
+    fn test_not_type_string() {
+        let root_schema = loader::load_from_str("not:\n  type: string").unwrap();
+        let context = Engine::evaluate(&root_schema, "42", false).unwrap();
+        assert!(!context.has_errors());
+        assert!(context.errors.borrow().is_empty());
+
+        let context = Engine::evaluate(&root_schema, "x", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Value must not match the `not` schema");
+    }
+
+    #[test]
+    fn test_not_inside_object_keeps_inner_errors_out() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+                not:
+                  enum: [root, admin]
+            "#,
+        )
+        .unwrap();
+        let context = Engine::evaluate(&root_schema, "name: alice", false).unwrap();
+        assert!(context.errors.borrow().is_empty());
+
+        let context = Engine::evaluate(&root_schema, "name: admin", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "name");
+        assert_eq!(errors[0].error, "Value must not match the `not` schema");
+    }
+
+    #[test]
+    fn test_not_unresolvable_ref_is_an_error() {
+        let root_schema =
+            loader::load_from_str("not:\n  $ref: \"https://example.invalid/x.yaml\"").unwrap();
+        let err = Engine::evaluate(&root_schema, "a: 1", false).unwrap_err();
+        assert!(
+            err.to_string().contains("https://example.invalid/x.yaml"),
+            "{err}"
+        );
     }
 }
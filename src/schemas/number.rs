@@ -8,6 +8,7 @@ use saphyr::YamlData;
 
 use crate::Number;
 use crate::Result;
+use crate::loader::complex_key_error;
 use crate::schemas::NumericBounds;
 use crate::utils::format_hash_map;
 use crate::utils::format_marker;
@@ -33,13 +34,13 @@ impl Validator for NumberSchema {
                 self.bounds
                     .validate(context, value, Number::Float(ordered_float.into_inner()));
             } else {
-                context.add_error(
+                context.add_type_error(
                     value,
                     format!("Expected a number, but got: {}", humanize_yaml_data(data)),
                 );
             }
         } else {
-            context.add_error(
+            context.add_type_error(
                 value,
                 format!(
                     "Expected a scalar value, but got: {}",
@@ -81,13 +82,13 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for NumberSchema {
                         schema.bounds.maximum = Some(value.try_into()?);
                     }
                     "exclusiveMinimum" => {
-                        schema.bounds.exclusive_minimum = Some(value.try_into()?);
+                        schema.bounds.load_exclusive_minimum(value)?;
                     }
                     "exclusiveMaximum" => {
-                        schema.bounds.exclusive_maximum = Some(value.try_into()?);
+                        schema.bounds.load_exclusive_maximum(value)?;
                     }
                     "multipleOf" => {
-                        schema.bounds.multiple_of = Some(value.try_into()?);
+                        schema.bounds.multiple_of = Some(NumericBounds::load_multiple_of(value)?);
                     }
                     "type" => {
                         if let YamlData::Value(Scalar::String(s)) = &value.data {
@@ -115,6 +116,8 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for NumberSchema {
                         debug!("Unsupported key for type: number: {}", key);
                     }
                 }
+            } else if let YamlData::Mapping(_) | YamlData::Sequence(_) = &key.data {
+                return Err(complex_key_error(key));
             } else {
                 return Err(expected_scalar!(
                     "{} Expected string key, got {:?}",
@@ -123,6 +126,7 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for NumberSchema {
                 ));
             }
         }
+        schema.bounds.check_draft04_bounds()?;
         Ok(schema)
     }
 }
@@ -154,6 +158,12 @@ impl std::fmt::Debug for NumberSchema {
                 exclusive_maximum.to_string(),
             );
         }
+        if let Some(exclusive) = self.bounds.draft04_exclusive_minimum {
+            h.insert("exclusiveMinimum".to_string(), exclusive.to_string());
+        }
+        if let Some(exclusive) = self.bounds.draft04_exclusive_maximum {
+            h.insert("exclusiveMaximum".to_string(), exclusive.to_string());
+        }
         if let Some(multiple_of) = self.bounds.multiple_of {
             h.insert("multipleOf".to_string(), multiple_of.to_string());
         }
@@ -371,4 +381,186 @@ mod tests {
             .expect("validate() failed!");
         assert!(context.has_errors());
     }
+
+    #[test]
+    fn test_multiple_of_zero_fails_to_load() {
+        let err = crate::loader::load_from_str("type: number\nmultipleOf: 0").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [2, 12] 'multipleOf' must be greater than 0, but got: 0"
+        );
+        let err = crate::loader::load_from_str("type: number\nmultipleOf: -0.5").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'multipleOf' must be greater than 0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_draft04_exclusive_minimum() {
+        let root = crate::loader::load_from_str("type: number\nminimum: 0\nexclusiveMinimum: true")
+            .unwrap();
+        let context = crate::Engine::evaluate(&root, "0", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number must be greater than 0");
+        assert_eq!(errors[0].keyword.as_deref(), Some("exclusiveMinimum"));
+        drop(errors);
+        let context = crate::Engine::evaluate(&root, "0.5", false).unwrap();
+        assert!(!context.has_errors());
+
+        let root =
+            crate::loader::load_from_str("type: number\nminimum: 0\nexclusiveMinimum: false")
+                .unwrap();
+        let context = crate::Engine::evaluate(&root, "0", false).unwrap();
+        assert!(!context.has_errors());
+    }
+
+    #[test]
+    fn test_draft04_exclusive_minimum_requires_minimum() {
+        let err = crate::loader::load_from_str("type: number\nexclusiveMinimum: true").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: 'exclusiveMinimum: true' requires 'minimum'"
+        );
+    }
+
+    #[test]
+    fn test_number_enum() {
+        let root = crate::loader::load_from_str("type: number\nenum: [1.5, 2.0]").unwrap();
+        for accepted in ["1.5", "2", "2.0"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = crate::Engine::evaluate(&root, "2.5", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Value 2.5 is not in the enum: [1.5, 2]");
+    }
+
+    #[test]
+    fn test_float_multiple_of() {
+        let root = crate::loader::load_from_str("type: number\nmultipleOf: 0.5").unwrap();
+        for accepted in ["0", "1.5", "-2.5", "3"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = crate::Engine::evaluate(&root, "0.3", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number is not a multiple of 0.5!");
+
+        // 0.3 / 0.1 isn't exactly 3 in floating point
+        let root = crate::loader::load_from_str("type: number\nmultipleOf: 0.1").unwrap();
+        for accepted in ["0.3", "0.7", "1.1", "12.3"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for rejected in ["0.35", "0.01"] {
+            let context = crate::Engine::evaluate(&root, rejected, false).unwrap();
+            assert!(context.has_errors(), "{rejected}");
+        }
+    }
+
+    #[test]
+    fn test_float_exclusive_maximum_and_multiple_of_from_schema() {
+        let root = crate::loader::load_from_str("type: number\nexclusiveMaximum: 1.5").unwrap();
+        let context = crate::Engine::evaluate(&root, "1.5", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number must be less than 1.5");
+        assert_eq!(errors[0].keyword.as_deref(), Some("exclusiveMaximum"));
+        drop(errors);
+        let context = crate::Engine::evaluate(&root, "1.49", false).unwrap();
+        assert!(!context.has_errors());
+
+        let root = crate::loader::load_from_str("type: number\nmultipleOf: 0.1").unwrap();
+        let context = crate::Engine::evaluate(&root, "0.3", false).unwrap();
+        assert!(!context.has_errors());
+    }
+
+    #[test]
+    fn test_nan_is_outside_of_bounds() {
+        for schema in [
+            "type: number\nminimum: 0",
+            "type: number\nmaximum: 0",
+            "type: number\nexclusiveMinimum: 0",
+            "type: number\nexclusiveMaximum: 0",
+            "type: number\nmultipleOf: 0.5",
+        ] {
+            let root = crate::loader::load_from_str(schema).unwrap();
+            let context = crate::Engine::evaluate(&root, ".nan", false).unwrap();
+            let errors = context.errors.borrow();
+            assert_eq!(errors.len(), 1, "{schema}");
+            assert_eq!(errors[0].error, "Value is NaN", "{schema}");
+        }
+
+        // Reported once, whatever the number of constraints
+        let root =
+            crate::loader::load_from_str("type: number\nminimum: 0\nmaximum: 10\nmultipleOf: 0.5")
+                .unwrap();
+        let context = crate::Engine::evaluate(&root, ".nan", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].keyword.as_deref(), Some("minimum"));
+
+        // Without constraints, NaN is a number
+        let root = crate::loader::load_from_str("type: number").unwrap();
+        let context = crate::Engine::evaluate(&root, ".nan", false).unwrap();
+        assert!(!context.has_errors());
+    }
+
+    #[test]
+    fn test_infinities_against_bounds() {
+        let root = crate::loader::load_from_str("type: number\nminimum: 0").unwrap();
+        let context = crate::Engine::evaluate(&root, ".inf", false).unwrap();
+        assert!(!context.has_errors());
+        let context = crate::Engine::evaluate(&root, "-.inf", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number must be greater than or equal to 0");
+        drop(errors);
+
+        let root = crate::loader::load_from_str("type: number\nexclusiveMaximum: 1000000").unwrap();
+        let context = crate::Engine::evaluate(&root, "-.inf", false).unwrap();
+        assert!(!context.has_errors());
+        let context = crate::Engine::evaluate(&root, ".inf", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Number must be less than 1000000");
+        drop(errors);
+
+        let root = crate::loader::load_from_str("type: number\nmultipleOf: 1").unwrap();
+        for value in [".inf", "-.inf"] {
+            let context = crate::Engine::evaluate(&root, value, false).unwrap();
+            assert!(context.has_errors(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_inclusive_bounds_accept_boundaries() {
+        // Integer bounds against integer and float values
+        let root = crate::loader::load_from_str("type: number\nminimum: 1\nmaximum: 10").unwrap();
+        for accepted in ["1", "10", "1.0", "10.0"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for rejected in ["0", "11", "0.5", "10.5"] {
+            let context = crate::Engine::evaluate(&root, rejected, false).unwrap();
+            assert!(context.has_errors(), "{rejected}");
+        }
+
+        // Float bounds
+        let root =
+            crate::loader::load_from_str("type: number\nminimum: 1.5\nmaximum: 2.5").unwrap();
+        for accepted in ["1.5", "2.5", "2"] {
+            let context = crate::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        for rejected in ["1.4", "2.6", "1", "3"] {
+            let context = crate::Engine::evaluate(&root, rejected, false).unwrap();
+            assert!(context.has_errors(), "{rejected}");
+        }
+    }
 }
@@ -1,58 +1,187 @@
 use std::cmp::Ordering;
 
 use saphyr::MarkedYaml;
+use saphyr::Scalar;
+use saphyr::YamlData;
 
 use crate::Number;
+use crate::Result;
+use crate::utils::format_marker;
 use crate::validation::Context;
 
 /// Shared numeric bound constraints used by both `IntegerSchema` and `NumberSchema`.
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct NumericBounds {
     pub minimum: Option<Number>,
     pub maximum: Option<Number>,
     pub exclusive_minimum: Option<Number>,
     pub exclusive_maximum: Option<Number>,
     pub multiple_of: Option<Number>,
+    /// A draft-04 boolean `exclusiveMinimum`; `Some(true)` makes `minimum` exclusive
+    pub draft04_exclusive_minimum: Option<bool>,
+    /// A draft-04 boolean `exclusiveMaximum`; `Some(true)` makes `maximum` exclusive
+    pub draft04_exclusive_maximum: Option<bool>,
 }
 
 impl NumericBounds {
+    /// Load a `multipleOf` value, which must be strictly greater than 0.
+    pub(crate) fn load_multiple_of(value: &MarkedYaml) -> Result<Number> {
+        let multiple_of: Number = value.try_into()?;
+        if multiple_of > Number::integer(0) {
+            Ok(multiple_of)
+        } else {
+            Err(schema_loading_error!(
+                "{} 'multipleOf' must be greater than 0, but got: {}",
+                format_marker(&value.span.start),
+                multiple_of
+            ))
+        }
+    }
+
+    /// Load an `exclusiveMinimum` value: a number, or a draft-04 boolean modifying `minimum`
+    pub(crate) fn load_exclusive_minimum(&mut self, value: &MarkedYaml) -> Result<()> {
+        if let YamlData::Value(Scalar::Boolean(exclusive)) = &value.data {
+            self.draft04_exclusive_minimum = Some(*exclusive);
+        } else {
+            self.exclusive_minimum = Some(value.try_into()?);
+        }
+        Ok(())
+    }
+
+    /// Load an `exclusiveMaximum` value: a number, or a draft-04 boolean modifying `maximum`
+    pub(crate) fn load_exclusive_maximum(&mut self, value: &MarkedYaml) -> Result<()> {
+        if let YamlData::Value(Scalar::Boolean(exclusive)) = &value.data {
+            self.draft04_exclusive_maximum = Some(*exclusive);
+        } else {
+            self.exclusive_maximum = Some(value.try_into()?);
+        }
+        Ok(())
+    }
+
+    /// Check that a draft-04 `exclusiveMinimum: true`/`exclusiveMaximum: true` has the bound it
+    /// modifies, once the whole mapping has been loaded
+    pub(crate) fn check_draft04_bounds(&self) -> Result<()> {
+        if self.draft04_exclusive_minimum == Some(true) && self.minimum.is_none() {
+            return Err(schema_loading_error!(
+                "'exclusiveMinimum: true' requires 'minimum'"
+            ));
+        }
+        if self.draft04_exclusive_maximum == Some(true) && self.maximum.is_none() {
+            return Err(schema_loading_error!(
+                "'exclusiveMaximum: true' requires 'maximum'"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether any `exclusiveMinimum`/`exclusiveMaximum` uses the draft-04 boolean form
+    pub fn has_draft04_exclusive(&self) -> bool {
+        self.draft04_exclusive_minimum.is_some() || self.draft04_exclusive_maximum.is_some()
+    }
+
+    /// Whether any `exclusiveMinimum`/`exclusiveMaximum` uses the numeric form (draft-06 and later)
+    pub fn has_numeric_exclusive(&self) -> bool {
+        self.exclusive_minimum.is_some() || self.exclusive_maximum.is_some()
+    }
+
+    /// The first configured constraint, as its keyword and value
+    fn first_constraint(&self) -> Option<(&'static str, Number)> {
+        [
+            ("minimum", self.minimum),
+            ("exclusiveMinimum", self.exclusive_minimum),
+            ("maximum", self.maximum),
+            ("exclusiveMaximum", self.exclusive_maximum),
+            ("multipleOf", self.multiple_of),
+        ]
+        .into_iter()
+        .find_map(|(keyword, bound)| bound.map(|bound| (keyword, bound)))
+    }
+
     /// Validate `actual` against all configured bounds, reporting errors to `context`.
+    ///
+    /// NaN fails any bound or `multipleOf` with a single "Value is NaN" error. Infinities compare
+    /// beyond every finite bound, so `.inf` fails a `maximum` but passes a `minimum`, and are not
+    /// a multiple of anything.
     pub fn validate(&self, context: &Context, value: &MarkedYaml, actual: Number) {
+        if let Number::Float(f) = actual
+            && f.is_nan()
+        {
+            if let Some((keyword, bound)) = self.first_constraint() {
+                context.add_keyword_error(value, "Value is NaN", keyword, bound);
+            }
+            return;
+        }
         if let Some(exclusive_min) = self.exclusive_minimum
             && actual.partial_cmp(&exclusive_min) != Some(Ordering::Greater)
         {
-            context.add_error(
+            context.add_keyword_error(
                 value,
                 format!("Number must be greater than {exclusive_min}"),
+                "exclusiveMinimum",
+                exclusive_min,
             );
         }
         if let Some(minimum) = self.minimum
-            && actual < minimum
+            && self.draft04_exclusive_minimum == Some(true)
+            && actual.partial_cmp(&minimum) != Some(Ordering::Greater)
+        {
+            context.add_keyword_error(
+                value,
+                format!("Number must be greater than {minimum}"),
+                "exclusiveMinimum",
+                minimum,
+            );
+        } else if let Some(minimum) = self.minimum
+            && actual.partial_cmp(&minimum).is_none_or(Ordering::is_lt)
         {
-            context.add_error(
+            context.add_keyword_error(
                 value,
                 format!("Number must be greater than or equal to {minimum}"),
+                "minimum",
+                minimum,
             );
         }
 
         if let Some(exclusive_max) = self.exclusive_maximum
             && actual.partial_cmp(&exclusive_max) != Some(Ordering::Less)
         {
-            context.add_error(value, format!("Number must be less than {exclusive_max}"));
+            context.add_keyword_error(
+                value,
+                format!("Number must be less than {exclusive_max}"),
+                "exclusiveMaximum",
+                exclusive_max,
+            );
         }
         if let Some(maximum) = self.maximum
-            && actual > maximum
+            && self.draft04_exclusive_maximum == Some(true)
+            && actual.partial_cmp(&maximum) != Some(Ordering::Less)
         {
-            context.add_error(
+            context.add_keyword_error(
+                value,
+                format!("Number must be less than {maximum}"),
+                "exclusiveMaximum",
+                maximum,
+            );
+        } else if let Some(maximum) = self.maximum
+            && actual.partial_cmp(&maximum).is_none_or(Ordering::is_gt)
+        {
+            context.add_keyword_error(
                 value,
                 format!("Number must be less than or equal to {maximum}"),
+                "maximum",
+                maximum,
             );
         }
 
         if let Some(multiple) = self.multiple_of
             && !actual.is_multiple_of(multiple)
         {
-            context.add_error(value, format!("Number is not a multiple of {multiple}!"));
+            context.add_keyword_error(
+                value,
+                format!("Number is not a multiple of {multiple}!"),
+                "multipleOf",
+                multiple,
+            );
         }
     }
 }
@@ -12,23 +12,33 @@ use saphyr::YamlData;
 use crate::Error;
 use crate::Result;
 use crate::YamlSchema;
+use crate::loader::complex_key_error;
 use crate::loader::load_integer_marked;
 use crate::loader::marked_yaml_mapping_key_to_string;
 use crate::schemas::BooleanOrSchema;
+use crate::schemas::keywords::misplaced_property_error;
 use crate::utils::format_annotated_mapping;
 use crate::utils::format_marker;
 use crate::utils::linked_hash_map;
+use crate::utils::mistyped_keyword_error;
 
 /// A pattern property entry: a pre-compiled regex paired with its schema.
 #[derive(Debug)]
 pub struct PatternProperty {
     pub regex: Regex,
     pub schema: YamlSchema,
+    /// Extension `x-min-matches`: the minimum number of keys that must match the pattern
+    pub min_matches: Option<usize>,
+    /// Extension `x-max-matches`: the maximum number of keys that may match the pattern
+    pub max_matches: Option<usize>,
 }
 
 impl PartialEq for PatternProperty {
     fn eq(&self, other: &Self) -> bool {
-        self.regex.as_str() == other.regex.as_str() && self.schema == other.schema
+        self.regex.as_str() == other.regex.as_str()
+            && self.schema == other.schema
+            && self.min_matches == other.min_matches
+            && self.max_matches == other.max_matches
     }
 }
 
@@ -99,11 +109,11 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for ObjectSchema {
                             Some(load_pattern_properties_marked(value)?);
                     }
                     "propertyNames" => {
-                        if value.data.is_mapping() {
+                        if value.data.is_mapping() || value.data.is_boolean() {
                             object_schema.property_names = Some(value.try_into()?);
                         } else {
                             return Err(unsupported_type!(
-                                "propertyNames: Expected a mapping (subschema), but got: {:?}",
+                                "propertyNames: Expected a mapping (subschema) or boolean, but got: {:?}",
                                 value
                             ));
                         }
@@ -126,9 +136,10 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for ObjectSchema {
                                 .collect::<Result<Vec<String>>>()?;
                             object_schema.required = Some(required);
                         } else {
-                            return Err(unsupported_type!(
-                                "required: Expected an array, but got: {:?}",
-                                value
+                            return Err(mistyped_keyword_error(
+                                "required",
+                                "a sequence of property names",
+                                value,
                             ));
                         }
                     }
@@ -152,6 +163,16 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for ObjectSchema {
                                     s
                                 ));
                             }
+                        } else if let YamlData::Sequence(values) = &value.data {
+                            if !values
+                                .iter()
+                                .any(|v| v.data == MarkedYaml::value_from_str("object").data)
+                            {
+                                return Err(unsupported_type!(
+                                    "Expected type: object, but got: {:?}",
+                                    value
+                                ));
+                            }
                         } else {
                             return Err(expected_type_is_string!(value));
                         }
@@ -160,6 +181,8 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for ObjectSchema {
                         debug!("Unsupported key for type: object: {}", s);
                     }
                 }
+            } else if let YamlData::Mapping(_) | YamlData::Sequence(_) = &key.data {
+                return Err(complex_key_error(key));
             } else {
                 return Err(expected_scalar!(
                     "{} Expected a scalar key, got: {:?}",
@@ -178,14 +201,12 @@ fn load_properties_marked<'r>(value: &MarkedYaml<'r>) -> Result<LinkedHashMap<St
         for (key, value) in mapping.iter() {
             let key_string = marked_yaml_mapping_key_to_string(key)?;
             if value.data.is_mapping() {
-                let schema: YamlSchema = value.try_into()?;
+                let schema: YamlSchema = value.try_into().map_err(|e: Error| {
+                    e.in_nested_schema(format!("properties.{key_string}"), value)
+                })?;
                 properties.insert(key_string, schema);
             } else {
-                return Err(generic_error!(
-                    "properties: Expected a mapping for \"{}\", but got: {:?}",
-                    key_string,
-                    value
-                ));
+                return Err(misplaced_property_error(key, &key_string, value));
             }
         }
         Ok(properties)
@@ -198,16 +219,45 @@ fn load_properties_marked<'r>(value: &MarkedYaml<'r>) -> Result<LinkedHashMap<St
     }
 }
 
+/// Load an `x-min-matches` / `x-max-matches` limit, which must be a non-negative integer
+fn load_match_limit(keyword: &str, value: &MarkedYaml) -> Result<usize> {
+    match &value.data {
+        YamlData::Value(Scalar::Integer(i)) if *i >= 0 => Ok(*i as usize),
+        _ => Err(mistyped_keyword_error(
+            keyword,
+            "a non-negative integer",
+            value,
+        )),
+    }
+}
+
 fn load_pattern_properties_marked<'r>(value: &MarkedYaml<'r>) -> Result<Vec<PatternProperty>> {
     if let YamlData::Mapping(mapping) = &value.data {
         let mut pattern_properties = Vec::new();
         for (key, value) in mapping.iter() {
             let pattern = marked_yaml_mapping_key_to_string(key)?;
-            let regex = Regex::new(pattern.as_ref())
-                .map_err(|_e| Error::InvalidRegularExpression(pattern.clone()))?;
-            if value.data.is_mapping() {
+            let regex = Regex::new(pattern.as_ref()).map_err(|e| {
+                Error::InvalidRegularExpression(format!(
+                    "{} patternProperties '{}': {}",
+                    format_marker(&key.span.start),
+                    pattern,
+                    e
+                ))
+            })?;
+            if let YamlData::Mapping(schema_mapping) = &value.data {
                 let schema: YamlSchema = value.try_into()?;
-                pattern_properties.push(PatternProperty { regex, schema });
+                let match_limit = |keyword: &str| {
+                    schema_mapping
+                        .get(&MarkedYaml::value_from_str(keyword))
+                        .map(|v| load_match_limit(keyword, v))
+                        .transpose()
+                };
+                pattern_properties.push(PatternProperty {
+                    regex,
+                    schema,
+                    min_matches: match_limit("x-min-matches")?,
+                    max_matches: match_limit("x-max-matches")?,
+                });
             } else {
                 return Err(generic_error!(
                     "patternProperties: Expected a mapping for \"{}\", but got: {:?}",
@@ -303,18 +353,12 @@ fn load_dependent_schemas_marked<'r>(
 
 fn load_additional_properties_marked<'r>(marked_yaml: &MarkedYaml<'r>) -> Result<BooleanOrSchema> {
     match &marked_yaml.data {
-        YamlData::Value(scalar) => match scalar {
-            Scalar::Boolean(b) => Ok(BooleanOrSchema::Boolean(*b)),
-            _ => Err(generic_error!(
-                "{} Expected a boolean scalar, but got: {:?}",
-                format_marker(&marked_yaml.span.start),
-                scalar
-            )),
-        },
+        YamlData::Value(Scalar::Boolean(b)) => Ok(BooleanOrSchema::Boolean(*b)),
         YamlData::Mapping(_mapping) => marked_yaml.try_into().map(BooleanOrSchema::schema),
-        _ => Err(unsupported_type!(
-            "Expected type: boolean or mapping, but got: {:?}",
-            marked_yaml
+        _ => Err(mistyped_keyword_error(
+            "additionalProperties",
+            "a boolean or a mapping",
+            marked_yaml,
         )),
     }
 }
@@ -400,7 +444,12 @@ impl ObjectSchemaBuilder {
     {
         let regex = Regex::new(pattern.as_ref())
             .unwrap_or_else(|e| panic!("Invalid regex pattern '{}': {e}", pattern.as_ref()));
-        let entry = PatternProperty { regex, schema };
+        let entry = PatternProperty {
+            regex,
+            schema,
+            min_matches: None,
+            max_matches: None,
+        };
         if let Some(pattern_properties) = self.0.pattern_properties.as_mut() {
             pattern_properties.push(entry);
         } else {
@@ -568,6 +617,43 @@ office_number: 201",
         assert!(ObjectSchema::try_from(doc.first().unwrap()).is_err());
     }
 
+    #[test]
+    fn test_additional_properties_quoted_boolean_hint() {
+        let err = loader::load_from_str(
+            r#"
+        type: object
+        additionalProperties: "false"
+        "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: [3, 30] 'additionalProperties' expects a boolean or a mapping, \
+             but got a string: \"false\" (hint: \"false\" is a quoted string; write \
+             `additionalProperties: false` without quotes for a boolean)"
+        );
+    }
+
+    #[test]
+    fn test_additional_properties_sequence_message() {
+        let err = loader::load_from_str("type: object\nadditionalProperties: [a]").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("[2, 22]"), "{message}");
+        assert!(message.contains("but got a sequence"), "{message}");
+        assert!(!message.contains("hint"), "{message}");
+    }
+
+    #[test]
+    fn test_required_mistyped_hint() {
+        let err = loader::load_from_str("type: object\nrequired: name").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("'required' expects a sequence of property names, but got a string"),
+            "{message}"
+        );
+        assert!(message.contains("e.g. `required: [name]`"), "{message}");
+    }
+
     #[test]
     fn test_property_names_validation_accepts_integer_keys() {
         let yaml = r#"
@@ -630,6 +716,77 @@ office_number: 201",
         assert!(ctx.has_errors());
     }
 
+    #[test]
+    fn test_property_names_false_allows_only_empty_objects() {
+        let root = loader::load_from_str("type: object\npropertyNames: false").unwrap();
+        let context = crate::Engine::evaluate(&root, "{}", false).unwrap();
+        assert!(!context.has_errors());
+
+        let context = crate::Engine::evaluate(&root, "a: 1", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "a");
+        assert_eq!(
+            errors[0].error,
+            "Property name 'a' is not allowed by propertyNames: false"
+        );
+    }
+
+    #[test]
+    fn test_property_names_length_and_enum() {
+        let root = loader::load_from_str(
+            r#"
+            type: object
+            propertyNames:
+              maxLength: 5
+              enum: [alpha, beta, epsilon]
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(&root, "alpha: 1\nbeta: 2", false).unwrap();
+        assert!(!context.has_errors());
+        let context = crate::Engine::evaluate(&root, "gamma: 1", false).unwrap();
+        assert!(context.has_errors());
+        // In the enum but too long
+        let context = crate::Engine::evaluate(&root, "epsilon: 1", false).unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn test_property_names_format_reports_offending_key() {
+        let root = loader::load_from_str(
+            r#"
+            type: object
+            propertyNames:
+              format: email
+              maxLength: 20
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(
+            &root,
+            "ops@example.com: 1
+not-an-email: 2
+longer.than.twenty@example.com: 3",
+            false,
+        )
+        .unwrap();
+        let errors: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        // Each error points at the offending key itself
+        assert_eq!(
+            errors,
+            vec![
+                r#"[2:1] .not-an-email: String "not-an-email" is not a valid "email""#,
+                "[3:1] .longer.than.twenty@example.com: String is too long! (max length: 20)",
+            ]
+        );
+    }
+
     #[test]
     fn test_dependent_required_loads() {
         let yaml = r#"
@@ -678,4 +835,24 @@ office_number: 201",
         let ds = os.dependent_schemas.as_ref().unwrap();
         assert!(ds.contains_key("foo"));
     }
+
+    #[test]
+    fn test_invalid_pattern_properties_regex_fails_to_load() {
+        let err = loader::load_from_str(
+            r#"
+            type: object
+            patternProperties:
+              "^worker-(":
+                type: string
+            "#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidRegularExpression(_)), "{err:?}");
+        let message = err.to_string();
+        assert!(
+            message
+                .starts_with("Invalid regular expression: [4, 14] patternProperties '^worker-(':"),
+            "{message}"
+        );
+    }
 }
@@ -5,7 +5,6 @@ use saphyr::MarkedYaml;
 use saphyr::YamlData;
 
 use crate::Context;
-use crate::Error;
 use crate::Result;
 use crate::Validator;
 use crate::YamlSchema;
@@ -51,7 +50,7 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for OneOfSchema {
                     "[OneOfSchema#try_from] marked_yaml: {}",
                     format_yaml_data(&marked_yaml.data)
                 );
-                let one_of = loader::load_array_of_schemas_marked(marked_yaml)?;
+                let one_of = loader::load_array_of_schemas_marked("oneOf", marked_yaml)?;
                 Ok(OneOfSchema { one_of })
             }
             None => Err(generic_error!("No `oneOf` key found!")),
@@ -61,12 +60,14 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for OneOfSchema {
 
 impl Validator for crate::schemas::OneOfSchema {
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
-        let one_of_is_valid = validate_one_of(context, &self.one_of, value)?;
-        if !one_of_is_valid {
-            context.add_error(value, "None of the schemas in `oneOf` matched!");
-            fail_fast!(context);
-        }
-        Ok(())
+        context.with_schema_segment(&["oneOf"], || {
+            let one_of_is_valid = validate_one_of(context, &self.one_of, value)?;
+            if !one_of_is_valid {
+                context.add_error(value, "None of the schemas in `oneOf` matched!");
+                fail_fast!(context);
+            }
+            Ok(())
+        })
     }
 }
 
@@ -79,33 +80,33 @@ pub fn validate_one_of(
     let mut winning_obj = None;
     let mut winning_arr: Option<ArrayUnevaluatedAnnotations> = None;
 
-    for schema in schemas {
+    for (i, schema) in schemas.iter().enumerate() {
+        if schema.type_excludes(context, value) {
+            debug!("[OneOf] Skipping schema {schema}: its type can't match");
+            continue;
+        }
         debug!(
             "[OneOf] Validating value: {:?} against schema: {}",
             &value.data, schema
         );
         let sub_context = context.get_sub_context_fresh_eval();
-        let sub_result = schema.validate(&sub_context, value);
-        match sub_result {
-            Ok(()) | Err(Error::FailFast) => {
-                debug!(
-                    "[OneOf] sub_context.errors: {}",
-                    sub_context.errors.borrow().len()
-                );
-                if sub_context.has_errors() {
-                    continue;
-                }
+        let sub_result =
+            context.with_schema_segment(&[i.to_string()], || schema.validate(&sub_context, value));
+        debug!(
+            "[OneOf] sub_context.errors: {}",
+            sub_context.errors.borrow().len()
+        );
+        if !context.branch_matched("oneOf", value, &sub_context, sub_result)? {
+            continue;
+        }
 
-                match_count += 1;
-                if match_count == 1 {
-                    winning_obj = sub_context.object_evaluated.as_ref().map(|o| o.snapshot());
-                    winning_arr = sub_context
-                        .array_unevaluated
-                        .as_ref()
-                        .map(|a| a.borrow().clone());
-                }
-            }
-            Err(e) => return Err(e),
+        match_count += 1;
+        if match_count == 1 {
+            winning_obj = sub_context.object_evaluated.as_ref().map(|o| o.snapshot());
+            winning_arr = sub_context
+                .array_unevaluated
+                .as_ref()
+                .map(|a| a.borrow().clone());
         }
     }
 
@@ -227,7 +228,13 @@ mod tests {
         let result = root_schema.validate(&context, value);
         assert!(result.is_ok());
         assert!(!context.has_errors());
+
+        // The array items are validated against `#/$defs/schema`
+        let context = crate::Engine::evaluate(&root_schema, "- type: string", false).unwrap();
         assert!(!context.has_errors());
+        let context =
+            crate::Engine::evaluate(&root_schema, "- type: string\n- type: bogus", false).unwrap();
+        assert!(context.has_errors());
     }
 
     #[test]
@@ -259,4 +266,46 @@ mod tests {
         assert!(result.is_ok());
         assert!(!context.has_errors());
     }
+
+    #[test]
+    fn test_branches_pruned_by_type() {
+        let mut schema = String::from("oneOf:\n");
+        for i in 0..19 {
+            schema.push_str(&format!("  - type: string\n    const: s{i}\n"));
+        }
+        schema.push_str("  - type: object\n    required: [id]\n");
+        let root_schema = loader::load_from_str(&schema).expect("Failed to load schema");
+
+        let validated_branches = |value: &str| {
+            let before = crate::validation::FRESH_SUB_CONTEXTS.with(|n| n.get());
+            let context = crate::Engine::evaluate(&root_schema, value, false).unwrap();
+            let after = crate::validation::FRESH_SUB_CONTEXTS.with(|n| n.get());
+            let errors: Vec<String> = context
+                .errors
+                .borrow()
+                .iter()
+                .map(|e| e.to_string())
+                .collect();
+            (after - before, errors)
+        };
+
+        // Only the `type: object` branch is validated against a mapping
+        assert_eq!(validated_branches("id: 1"), (1, vec![]));
+        assert_eq!(
+            validated_branches("name: x"),
+            (
+                1,
+                vec!["[1:1] .: None of the schemas in `oneOf` matched!".to_string()]
+            )
+        );
+        // and none against an integer
+        assert_eq!(
+            validated_branches("42"),
+            (
+                0,
+                vec!["[1:1] .: None of the schemas in `oneOf` matched!".to_string()]
+            )
+        );
+        assert_eq!(validated_branches("s7"), (19, vec![]));
+    }
 }
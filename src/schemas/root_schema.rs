@@ -1,5 +1,7 @@
 //! RootSchema represents the root document in a schema document.
 
+use std::collections::HashMap;
+
 use jsonptr::Pointer;
 use log::debug;
 use saphyr::MarkedYaml;
@@ -7,9 +9,11 @@ use saphyr::Scalar;
 use saphyr::YamlData;
 use url::Url;
 
+use crate::Engine;
 use crate::Error;
 use crate::Result;
 use crate::YamlSchema;
+use crate::fixes::path_to_pointer;
 use crate::loader::marked_yaml_to_string;
 use crate::validation::Context;
 use crate::validation::Validator;
@@ -17,12 +21,20 @@ use crate::validation::Validator;
 /// A RootSchema represents the root document in a schema document, and includes additional
 /// fields such as `$schema` that are not allowed in subschemas. It also provides a way to
 /// resolve references to other schemas.
+///
+/// A RootSchema owns its data rather than borrowing from the schema source, and is `Send + Sync`,
+/// so it can be shared across threads in an `Arc`.
 #[derive(Debug, PartialEq)]
 pub struct RootSchema {
     pub meta_schema: Option<String>,
     pub schema: YamlSchema,
     /// Base URI for resolving relative `$ref` values (from file path, URL, or `$id`).
     pub base_uri: Option<Url>,
+    /// JSON Pointers to the subschemas declaring each `$anchor`, keyed by anchor name
+    pub anchors: HashMap<String, String>,
+    /// Problems found while loading that didn't stop it, such as unknown keywords when loaded
+    /// with [`UnknownKeys::Warn`](crate::loader::UnknownKeys::Warn)
+    pub warnings: Vec<String>,
 }
 
 impl RootSchema {
@@ -32,18 +44,44 @@ impl RootSchema {
             meta_schema: None,
             schema: YamlSchema::Empty,
             base_uri: None,
+            anchors: HashMap::new(),
+            warnings: Vec::new(),
         }
     }
 
-    /// Create a new RootSchema with a given schema
+    /// Create a new RootSchema with a given schema, without checking it. If an `$anchor` is
+    /// declared more than once, the first declaration wins and the duplicate is recorded on
+    /// [`warnings`](Self::warnings); use [`try_new`](Self::try_new) to reject it instead.
     pub fn new(schema: YamlSchema) -> Self {
+        let (anchors, duplicates) = index_anchors(&schema);
         Self {
             meta_schema: None,
             schema,
             base_uri: None,
+            anchors,
+            warnings: duplicates,
         }
     }
 
+    /// Create a new RootSchema with a given schema, failing if an `$anchor` is declared more
+    /// than once in the document.
+    pub fn try_new(meta_schema: Option<String>, schema: YamlSchema) -> Result<Self> {
+        let (anchors, duplicates) = index_anchors(&schema);
+        if let Some(duplicate) = duplicates.into_iter().next() {
+            return Err(schema_loading_error!("{}", duplicate));
+        }
+        if let Some(meta_schema) = &meta_schema {
+            check_exclusive_bounds(meta_schema, &schema)?;
+        }
+        Ok(Self {
+            meta_schema,
+            schema,
+            base_uri: None,
+            anchors,
+            warnings: Vec::new(),
+        })
+    }
+
     /// Returns the `$id` of the schema's Subschema, if present.
     pub fn id(&self) -> Option<String> {
         match &self.schema {
@@ -60,31 +98,189 @@ impl RootSchema {
             .unwrap_or_else(|| fallback.to_string())
     }
 
-    /// Resolve a JSON Pointer to an element in the schema.
+    /// A heuristic for whether this was loaded from an actual schema, rather than a data file
+    /// passed by mistake (e.g. `name: John`), which loads as a schema that accepts any value.
+    ///
+    /// Returns `true` if the root has `$schema`, `$id`, `$ref`, `$defs`, `type`, a combinator
+    /// (`allOf`, `anyOf`, `oneOf`, `not`, `if`), `const`, `enum`, or keywords of a specific type
+    /// such as `properties` or `minLength`, or is `true` or `false`. Since unknown keys are
+    /// ignored, the empty schema `{}` loads like a data file and doesn't count.
+    pub fn looks_like_schema(&self) -> bool {
+        let YamlSchema::Subschema(subschema) = &self.schema else {
+            return true;
+        };
+        self.meta_schema.is_some()
+            || subschema.metadata_and_annotations.id.is_some()
+            || subschema.r#ref.is_some()
+            || subschema.defs.is_some()
+            || !subschema.r#type.is_none()
+            || subschema.any_of.is_some()
+            || subschema.all_of.is_some()
+            || subschema.one_of.is_some()
+            || subschema.not.is_some()
+            || subschema.if_then_else.is_some()
+            || subschema.r#const.is_some()
+            || subschema.r#enum.is_some()
+            || subschema.array_schema.is_some()
+            || subschema.integer_schema.is_some()
+            || subschema.number_schema.is_some()
+            || subschema.object_schema.is_some()
+            || subschema.string_schema.is_some()
+    }
+
+    /// Validate a YAML document against this schema, returning [`Error::ValidationFailed`] with
+    /// all collected errors if validation fails. Warnings alone do not cause a failure.
+    pub fn validate_strict(&self, yaml: &str) -> Result<()> {
+        Engine::evaluate(self, yaml, false)?.into_result()
+    }
+
+    /// Iterate over the root-level `$defs` as `(name, schema)` pairs.
+    ///
+    /// Definitions are yielded in declaration order, i.e. the order in which they appear in the
+    /// schema document. Yields nothing if the root schema has no `$defs`.
+    pub fn defs(&self) -> impl Iterator<Item = (&str, &YamlSchema)> {
+        let defs = match &self.schema {
+            YamlSchema::Subschema(subschema) => subschema.defs.as_ref(),
+            _ => None,
+        };
+        defs.into_iter()
+            .flat_map(|defs| defs.iter().map(|(name, schema)| (name.as_str(), schema)))
+    }
+
+    /// Look up a root-level `$defs` entry by its bare name (`foo`) or by a `#/$defs/foo` pointer
+    pub fn get_def(&self, name: &str) -> Option<&YamlSchema> {
+        let name = match name.strip_prefix("#/$defs/") {
+            Some(escaped) => escaped.replace("~1", "/").replace("~0", "~"),
+            None => name.to_string(),
+        };
+        self.defs()
+            .find(|(def_name, _)| *def_name == name)
+            .map(|(_, schema)| schema)
+    }
+
+    /// Resolve a JSON Pointer to an element in the schema. The empty pointer (`#`) resolves to
+    /// the root schema itself.
     pub fn resolve(&self, pointer: &Pointer) -> Option<&YamlSchema> {
         let components = pointer.components().collect::<Vec<_>>();
         debug!("[RootSchema#resolve] components: {components:?}");
-        components.first().and_then(|component| {
-            debug!("[RootSchema#resolve] component: {component:?}");
-            match component {
-                jsonptr::Component::Root => {
-                    let components = &components[1..];
-                    components.first().and_then(|component| {
-                        debug!("[RootSchema#resolve] component: {component:?}");
-                        match component {
-                            jsonptr::Component::Root => unimplemented!(),
-                            jsonptr::Component::Token(token) => {
-                                self.schema.resolve(Some(token), &components[1..])
-                            }
-                        }
-                    })
-                }
-                jsonptr::Component::Token(token) => {
-                    self.schema.resolve(Some(token), &components[1..])
-                }
+        self.schema.resolve_components(&components)
+    }
+
+    /// Resolve the subschema declaring the given `$anchor`, wherever it appears in the document
+    pub fn resolve_anchor(&self, anchor: &str) -> Option<&YamlSchema> {
+        let pointer = self.anchors.get(anchor)?;
+        self.resolve(Pointer::parse(pointer).ok()?)
+    }
+
+    /// Resolve a URI fragment: a JSON Pointer (`/$defs/name`, or empty for the root) or a plain
+    /// `$anchor` name
+    pub fn resolve_fragment(&self, fragment: &str) -> Option<&YamlSchema> {
+        if fragment.is_empty() || fragment.starts_with('/') {
+            self.resolve(Pointer::parse(fragment).ok()?)
+        } else {
+            self.resolve_anchor(fragment)
+        }
+    }
+}
+
+/// Whether the `$schema` dialect spells `exclusiveMinimum`/`exclusiveMaximum` as draft-04
+/// booleans (`Some(true)`) or as numbers (`Some(false)`), or `None` if the dialect is unknown
+fn uses_draft04_exclusive_bounds(meta_schema: &str) -> Option<bool> {
+    if meta_schema.contains("draft-04") || meta_schema.contains("draft-03") {
+        Some(true)
+    } else if ["draft-06", "draft-07", "2019-09", "2020-12"]
+        .iter()
+        .any(|draft| meta_schema.contains(draft))
+    {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Reject `exclusiveMinimum`/`exclusiveMaximum` in the form the `$schema` dialect doesn't use.
+/// Schemas with an unknown dialect may use either form.
+fn check_exclusive_bounds(meta_schema: &str, schema: &YamlSchema) -> Result<()> {
+    fn walk(
+        schema: &YamlSchema,
+        path: &mut Vec<String>,
+        meta_schema: &str,
+        draft04: bool,
+    ) -> Result<()> {
+        let YamlSchema::Subschema(subschema) = schema else {
+            return Ok(());
+        };
+        let bounds = [
+            subschema.integer_schema.as_ref().map(|s| &s.bounds),
+            subschema.number_schema.as_ref().map(|s| &s.bounds),
+        ];
+        for bounds in bounds.into_iter().flatten() {
+            if draft04 && bounds.has_numeric_exclusive() {
+                return Err(schema_loading_error!(
+                    "Numeric exclusiveMinimum/exclusiveMaximum at #{} is not draft-04 syntax ($schema: {}); use `true` with minimum/maximum",
+                    path_to_pointer(path),
+                    meta_schema
+                ));
             }
-        })
+            if !draft04 && bounds.has_draft04_exclusive() {
+                return Err(schema_loading_error!(
+                    "Boolean exclusiveMinimum/exclusiveMaximum at #{} is draft-04 syntax, but $schema is {}; use a number",
+                    path_to_pointer(path),
+                    meta_schema
+                ));
+            }
+        }
+        for (tokens, child) in subschema.child_schemas() {
+            let depth = path.len();
+            path.extend(tokens);
+            walk(child, path, meta_schema, draft04)?;
+            path.truncate(depth);
+        }
+        Ok(())
+    }
+    match uses_draft04_exclusive_bounds(meta_schema) {
+        Some(draft04) => walk(schema, &mut Vec::new(), meta_schema, draft04),
+        None => Ok(()),
+    }
+}
+
+/// Map each `$anchor` to the JSON Pointer of its first declaration, with a message for each
+/// later declaration of the same anchor
+fn index_anchors(schema: &YamlSchema) -> (HashMap<String, String>, Vec<String>) {
+    let mut anchors: HashMap<String, String> = HashMap::new();
+    let mut duplicates = Vec::new();
+    for (anchor, pointer) in collect_anchors(schema) {
+        match anchors.get(&anchor) {
+            Some(existing) => duplicates.push(format!(
+                "Duplicate $anchor {anchor:?} at #{existing} and #{pointer}"
+            )),
+            None => {
+                anchors.insert(anchor, pointer);
+            }
+        }
     }
+    (anchors, duplicates)
+}
+
+/// Collect `(anchor, JSON Pointer)` pairs for every `$anchor` in the schema, in document order
+fn collect_anchors(schema: &YamlSchema) -> Vec<(String, String)> {
+    fn walk(schema: &YamlSchema, path: &mut Vec<String>, anchors: &mut Vec<(String, String)>) {
+        let YamlSchema::Subschema(subschema) = schema else {
+            return;
+        };
+        if let Some(anchor) = &subschema.anchor {
+            anchors.push((anchor.clone(), path_to_pointer(path)));
+        }
+        for (tokens, child) in subschema.child_schemas() {
+            let depth = path.len();
+            path.extend(tokens);
+            walk(child, path, anchors);
+            path.truncate(depth);
+        }
+    }
+    let mut anchors = Vec::new();
+    walk(schema, &mut Vec::new(), &mut anchors);
+    anchors
 }
 
 impl<'r> TryFrom<&MarkedYaml<'r>> for RootSchema {
@@ -93,16 +289,8 @@ impl<'r> TryFrom<&MarkedYaml<'r>> for RootSchema {
     fn try_from(marked_yaml: &MarkedYaml<'r>) -> Result<Self> {
         match &marked_yaml.data {
             YamlData::Value(scalar) => match scalar {
-                Scalar::Boolean(r#bool) => Ok(Self {
-                    meta_schema: None,
-                    schema: YamlSchema::BooleanLiteral(*r#bool),
-                    base_uri: None,
-                }),
-                Scalar::Null => Ok(RootSchema {
-                    meta_schema: None,
-                    schema: YamlSchema::Null,
-                    base_uri: None,
-                }),
+                Scalar::Boolean(r#bool) => Ok(RootSchema::new(YamlSchema::BooleanLiteral(*r#bool))),
+                Scalar::Null => Ok(RootSchema::new(YamlSchema::Null)),
                 _ => Err(generic_error!(
                     "[loader#load_from_doc] Don't know how to a handle scalar: {:?}",
                     scalar
@@ -118,11 +306,7 @@ impl<'r> TryFrom<&MarkedYaml<'r>> for RootSchema {
                     .transpose()?;
 
                 let schema = YamlSchema::try_from(marked_yaml)?;
-                Ok(RootSchema {
-                    meta_schema,
-                    schema,
-                    base_uri: None,
-                })
+                RootSchema::try_new(meta_schema, schema)
             }
             _ => Err(generic_error!(
                 "[loader#load_from_doc] Don't know how to load: {:?}",
@@ -133,7 +317,525 @@ impl<'r> TryFrom<&MarkedYaml<'r>> for RootSchema {
 }
 
 impl Validator for RootSchema {
+    /// Validate `value`, recording errors on `context`. Stopping early in fail-fast mode isn't an
+    /// error: the errors found so far are on the context.
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
-        self.schema.validate(context, value)
+        match self.schema.validate(context, value) {
+            Err(Error::FailFast) => Ok(()),
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use saphyr::LoadableYamlNode;
+
+    use crate::loader;
+    use crate::schemas::IntegerSchema;
+    use crate::schemas::Subschema;
+
+    use super::*;
+
+    #[test]
+    fn test_defs_iterates_in_declaration_order() {
+        let root_schema = loader::load_from_str(
+            r#"
+            $defs:
+              zebra:
+                type: string
+              apple:
+                type: integer
+              mango:
+                type: boolean
+              banana:
+                type: number
+              cherry:
+                type: object
+            "#,
+        )
+        .unwrap();
+        let names: Vec<&str> = root_schema.defs().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["zebra", "apple", "mango", "banana", "cherry"]);
+        let (_, apple) = root_schema.defs().nth(1).unwrap();
+        assert_eq!(apple, &YamlSchema::from(IntegerSchema::default()));
+    }
+
+    fn resolve<'s>(root_schema: &'s RootSchema, pointer: &str) -> Option<&'s YamlSchema> {
+        root_schema.resolve(Pointer::parse(pointer).unwrap())
+    }
+
+    #[test]
+    fn test_resolve_nested_pointers() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              address:
+                type: object
+                properties:
+                  street:
+                    type: string
+                    description: street
+              tags:
+                type: array
+                items:
+                  type: string
+                  description: tag
+                prefixItems:
+                  - type: integer
+                    description: first
+            anyOf:
+              - type: object
+                description: any0
+              - type: array
+                description: any1
+            $defs:
+              thing:
+                type: object
+                properties:
+                  name:
+                    type: string
+                    description: thing name
+                $defs:
+                  inner:
+                    type: boolean
+                    description: inner
+            "#,
+        )
+        .unwrap();
+        let description = |pointer: &str| match resolve(&root_schema, pointer) {
+            Some(YamlSchema::Subschema(s)) => s.metadata_and_annotations.description.clone(),
+            _ => None,
+        };
+        assert_eq!(
+            description("/properties/address/properties/street").as_deref(),
+            Some("street")
+        );
+        assert_eq!(
+            description("/properties/tags/items").as_deref(),
+            Some("tag")
+        );
+        assert_eq!(
+            description("/properties/tags/prefixItems/0").as_deref(),
+            Some("first")
+        );
+        assert_eq!(description("/anyOf/1").as_deref(), Some("any1"));
+        assert_eq!(
+            description("/$defs/thing/properties/name").as_deref(),
+            Some("thing name")
+        );
+        assert_eq!(
+            description("/$defs/thing/$defs/inner").as_deref(),
+            Some("inner")
+        );
+        assert!(resolve(&root_schema, "/anyOf/2").is_none());
+        assert!(resolve(&root_schema, "/properties/tags/prefixItems/x").is_none());
+        assert!(resolve(&root_schema, "/properties/missing").is_none());
+        assert!(resolve(&root_schema, "/$defs").is_none());
+        assert_eq!(resolve(&root_schema, ""), Some(&root_schema.schema));
+    }
+
+    #[test]
+    fn test_resolve_escaped_pointer_tokens() {
+        let root_schema = loader::load_from_str(
+            r#"
+            $defs:
+              "a/b":
+                type: string
+              "c~d":
+                type: integer
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            resolve(&root_schema, "/$defs/a~1b"),
+            Some(&YamlSchema::typed_string(Default::default()))
+        );
+        assert_eq!(
+            resolve(&root_schema, "/$defs/c~0d"),
+            Some(&YamlSchema::from(IntegerSchema::default()))
+        );
+        assert!(resolve(&root_schema, "/$defs/a/b").is_none());
+    }
+
+    #[test]
+    fn test_validate_strict() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              a:
+                type: string
+              b:
+                type: integer
+              c:
+                type: boolean
+              d:
+                type: number
+            "#,
+        )
+        .unwrap();
+        assert!(root_schema.validate_strict("a: x\nb: 1").is_ok());
+
+        let err = root_schema
+            .validate_strict("a: 1\nb: x\nc: 1\nd: x")
+            .unwrap_err();
+        let Error::ValidationFailed(errors) = &err else {
+            panic!("Expected ValidationFailed, got: {err:?}");
+        };
+        assert_eq!(errors.len(), 4);
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["a", "b", "c", "d"]);
+        let message = err.to_string();
+        assert!(
+            message.starts_with("Validation failed with 4 errors\n"),
+            "{message}"
+        );
+        assert!(message.contains(".a: Expected a string"), "{message}");
+        assert!(!message.contains(".d:"), "{message}");
+        assert!(message.ends_with("... and 1 more"), "{message}");
+    }
+
+    #[test]
+    fn test_into_result_ignores_warnings() {
+        let docs = saphyr::MarkedYaml::load_from_str("value").unwrap();
+        let value = docs.first().unwrap();
+
+        let context = Context::default();
+        context.add_warning(value, "just a warning");
+        assert!(!context.has_errors());
+        assert!(context.into_result().is_ok());
+
+        let context = Context::default();
+        context.add_warning(value, "just a warning");
+        context.add_error(value, "a real error");
+        let Err(Error::ValidationFailed(errors)) = context.into_result() else {
+            panic!("Expected ValidationFailed");
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "a real error");
+    }
+
+    #[test]
+    fn test_on_error_callback_fires_in_order() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              a:
+                type: string
+              b:
+                anyOf:
+                  - type: integer
+                  - type: boolean
+              c:
+                type: integer
+                minimum: 10
+            "#,
+        )
+        .unwrap();
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let context = Context::with_root_schema(&root_schema, false)
+            .on_error(move |e| sink.lock().unwrap().push(e.path.clone()));
+        let docs = saphyr::MarkedYaml::load_from_str("a: 1\nb: true\nc: 5").unwrap();
+        root_schema.validate(&context, &docs[0]).unwrap();
+
+        // The failing `anyOf` branch (`type: integer`) is not reported
+        assert_eq!(*seen.lock().unwrap(), vec!["a", "c"]);
+        let collected: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(|e| e.path.clone())
+            .collect();
+        assert_eq!(*seen.lock().unwrap(), collected);
+    }
+
+    #[test]
+    fn test_looks_like_schema() {
+        for schema in [
+            "type: object",
+            "properties:\n  name:\n    type: string",
+            "$schema: https://json-schema.org/draft/2020-12/schema",
+            "anyOf:\n  - type: string\n  - type: integer",
+            "$ref: \"#/$defs/a\"\n$defs:\n  a:\n    type: string",
+            "enum: [a, b]",
+            "minLength: 1",
+            "true",
+        ] {
+            let root_schema = loader::load_from_str(schema).unwrap();
+            assert!(root_schema.looks_like_schema(), "{schema}");
+        }
+
+        // Data files load as schemas that accept anything
+        for data in [
+            "name: John\nage: 42",
+            "description: A description, but nothing else",
+        ] {
+            let root_schema = loader::load_from_str(data).unwrap();
+            assert!(!root_schema.looks_like_schema(), "{data}");
+        }
+    }
+
+    #[test]
+    fn test_defs_empty_without_defs() {
+        assert_eq!(RootSchema::empty().defs().count(), 0);
+        let root_schema = loader::load_from_str("type: string").unwrap();
+        assert_eq!(root_schema.defs().count(), 0);
+    }
+
+    #[test]
+    fn test_get_def_by_name_or_pointer() {
+        let root_schema = loader::load_from_str(
+            r#"
+            $defs:
+              foo:
+                type: string
+              a/b:
+                type: integer
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            root_schema.get_def("foo"),
+            Some(YamlSchema::Subschema(_))
+        ));
+        assert!(root_schema.get_def("#/$defs/foo").is_some());
+        assert!(root_schema.get_def("#/$defs/a~1b").is_some());
+        assert!(root_schema.get_def("a/b").is_some());
+        assert!(root_schema.get_def("bar").is_none());
+        assert!(root_schema.get_def("#/$defs/bar").is_none());
+        assert!(RootSchema::empty().get_def("foo").is_none());
+    }
+
+    #[test]
+    fn test_additional_properties_ref_to_def() {
+        let root_schema = loader::load_from_str(
+            r##"
+            type: object
+            properties:
+              name:
+                type: string
+            additionalProperties:
+              $ref: "#/$defs/foo"
+            $defs:
+              foo:
+                type: integer
+            "##,
+        )
+        .unwrap();
+        let context = Engine::evaluate(&root_schema, "name: a\ncount: 3", false).unwrap();
+        assert!(!context.has_errors());
+        let context = Engine::evaluate(&root_schema, "name: a\ncount: three", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "count");
+    }
+
+    #[test]
+    fn test_anchor_deep_in_properties_referenced_from_sibling() {
+        let root_schema = loader::load_from_str(
+            r##"
+            type: object
+            properties:
+              shipping:
+                type: object
+                properties:
+                  address:
+                    type: object
+                    properties:
+                      postcode:
+                        $anchor: postcode
+                        type: string
+                        pattern: "^[0-9]{5}$"
+              billing:
+                type: object
+                properties:
+                  postcode:
+                    $ref: "#postcode"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            root_schema.anchors.get("postcode").map(String::as_str),
+            Some("/properties/shipping/properties/address/properties/postcode")
+        );
+        assert!(root_schema.resolve_anchor("postcode").is_some());
+        assert!(root_schema.resolve_anchor("missing").is_none());
+
+        let context =
+            Engine::evaluate(&root_schema, "billing:\n  postcode: \"12345\"", false).unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+        let context =
+            Engine::evaluate(&root_schema, "billing:\n  postcode: \"1234\"", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "billing.postcode");
+    }
+
+    #[test]
+    fn test_anchors_in_defs_and_combinators() {
+        let root_schema = loader::load_from_str(
+            r##"
+            $defs:
+              id:
+                $anchor: identifier
+                type: integer
+                minimum: 1
+            type: object
+            properties:
+              owner:
+                $ref: "#identifier"
+              tags:
+                type: array
+                items:
+                  $ref: "#tag"
+              extra:
+                anyOf:
+                  - type: "null"
+                  - $anchor: tag
+                    type: string
+                    minLength: 2
+            "##,
+        )
+        .unwrap();
+        assert_eq!(
+            root_schema.anchors.get("identifier").map(String::as_str),
+            Some("/$defs/id")
+        );
+        assert_eq!(
+            root_schema.anchors.get("tag").map(String::as_str),
+            Some("/properties/extra/anyOf/1")
+        );
+
+        let context = Engine::evaluate(&root_schema, "owner: 7\ntags: [ab, cd]", false).unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+        let context = Engine::evaluate(&root_schema, "owner: 0\ntags: [a]", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert_eq!(errors[0].path, "owner");
+        assert_eq!(errors[1].path, "tags.0");
+    }
+
+    #[test]
+    fn test_duplicate_anchor_fails_to_load() {
+        let err = loader::load_from_str(
+            r#"
+            $defs:
+              a:
+                $anchor: thing
+                type: string
+            properties:
+              b:
+                $anchor: thing
+                type: integer
+            "#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: Duplicate $anchor \"thing\" at #/$defs/a and #/properties/b"
+        );
+    }
+
+    #[test]
+    fn test_new_records_duplicate_anchor_as_warning() {
+        let anchored = || {
+            YamlSchema::subschema(Subschema {
+                anchor: Some("thing".to_string()),
+                ..Default::default()
+            })
+        };
+        let schema = Subschema {
+            defs: Some(
+                [("a".to_string(), anchored()), ("b".to_string(), anchored())]
+                    .into_iter()
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        let root_schema = RootSchema::new(YamlSchema::subschema(schema));
+        assert_eq!(root_schema.anchors["thing"], "/$defs/a");
+        assert_eq!(
+            root_schema.warnings,
+            vec!["Duplicate $anchor \"thing\" at #/$defs/a and #/$defs/b"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_anchor_fails_to_load() {
+        let err = loader::load_from_str("$anchor: \"#bad\"").unwrap_err();
+        assert!(err.to_string().contains("Invalid $anchor"), "{err}");
+    }
+
+    #[test]
+    fn test_exclusive_bounds_follow_meta_schema() {
+        let draft04 = r#"
+            $schema: http://json-schema.org/draft-04/schema#
+            type: object
+            properties:
+              port:
+                type: integer
+                minimum: 0
+                exclusiveMinimum: true
+            "#;
+        let root = loader::load_from_str(draft04).unwrap();
+        assert!(
+            Engine::evaluate(&root, "port: 0", false)
+                .unwrap()
+                .has_errors()
+        );
+
+        let err = loader::load_from_str(&draft04.replace("true", "0")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error loading schema: Numeric exclusiveMinimum/exclusiveMaximum at #/properties/port is not draft-04 syntax ($schema: http://json-schema.org/draft-04/schema#); use `true` with minimum/maximum"
+        );
+
+        let err = loader::load_from_str(&draft04.replace("draft-04", "draft/2020-12")).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "Boolean exclusiveMinimum/exclusiveMaximum at #/properties/port is draft-04 syntax"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<RootSchema>();
+
+        let root_schema = std::sync::Arc::new(
+            loader::load_from_str(
+                r#"
+                type: object
+                properties:
+                  port:
+                    type: integer
+                    maximum: 65535
+                required:
+                  - port
+                "#,
+            )
+            .unwrap(),
+        );
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let root_schema = std::sync::Arc::clone(&root_schema);
+                std::thread::spawn(move || {
+                    let good = format!("port: {}", 8080 + i);
+                    let bad = format!("port: {}", 70000 + i);
+                    let good = Engine::evaluate(&root_schema, &good, false).unwrap();
+                    let bad = Engine::evaluate(&root_schema, &bad, false).unwrap();
+                    !good.has_errors() && bad.has_errors()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap());
+        }
     }
 }
@@ -19,6 +19,13 @@ pub struct StringSchema {
     pub max_length: Option<usize>,
     pub pattern: Option<Regex>,
     pub format: Option<StringFormat>,
+    /// `contentEncoding`, e.g. `base64`; an annotation, validated if known with
+    /// [`ValidationOptions::content_assertions`](crate::ValidationOptions::content_assertions)
+    pub content_encoding: Option<String>,
+    /// `contentMediaType`, e.g. `application/json`; like `content_encoding`
+    pub content_media_type: Option<String>,
+    /// Extension `x-trim`: validate the value with surrounding whitespace trimmed
+    pub trim: bool,
 }
 
 impl std::fmt::Debug for StringSchema {
@@ -36,6 +43,15 @@ impl std::fmt::Debug for StringSchema {
         if let Some(format) = &self.format {
             h.insert("format".to_string(), format.to_string());
         }
+        if let Some(content_encoding) = &self.content_encoding {
+            h.insert("contentEncoding".to_string(), content_encoding.clone());
+        }
+        if let Some(content_media_type) = &self.content_media_type {
+            h.insert("contentMediaType".to_string(), content_media_type.clone());
+        }
+        if self.trim {
+            h.insert("x-trim".to_string(), "true".to_string());
+        }
         write!(f, "StringSchema {}", format_hash_map(&h))
     }
 }
@@ -52,6 +68,9 @@ impl PartialEq for StringSchema {
             && self.max_length == other.max_length
             && are_patterns_equivalent(&self.pattern, &other.pattern)
             && self.format == other.format
+            && self.content_encoding == other.content_encoding
+            && self.content_media_type == other.content_media_type
+            && self.trim == other.trim
     }
 }
 
@@ -97,7 +116,14 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for StringSchema {
                     }
                     "pattern" => {
                         if let YamlData::Value(Scalar::String(s)) = &value.data {
-                            let regex = regex::Regex::new(s.as_ref())?;
+                            let regex = regex::Regex::new(s.as_ref()).map_err(|e| {
+                                crate::Error::InvalidRegularExpression(format!(
+                                    "{} pattern '{}': {}",
+                                    format_marker(&value.span.start),
+                                    s,
+                                    e
+                                ))
+                            })?;
                             string_schema.pattern = Some(regex);
                         } else {
                             return Err(unsupported_type!(
@@ -120,6 +146,32 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for StringSchema {
                             ));
                         }
                     }
+                    "contentEncoding" | "contentMediaType" => {
+                        if let YamlData::Value(Scalar::String(s)) = &value.data {
+                            let s = Some(s.to_string());
+                            if key == "contentEncoding" {
+                                string_schema.content_encoding = s;
+                            } else {
+                                string_schema.content_media_type = s;
+                            }
+                        } else {
+                            return Err(unsupported_type!(
+                                "{} expected string, but got: {:?}",
+                                key,
+                                value
+                            ));
+                        }
+                    }
+                    "x-trim" => {
+                        if let YamlData::Value(Scalar::Boolean(b)) = &value.data {
+                            string_schema.trim = *b;
+                        } else {
+                            return Err(unsupported_type!(
+                                "x-trim expected boolean, but got: {:?}",
+                                value
+                            ));
+                        }
+                    }
                     // Maybe this should be handled by the base schema?
                     "type" => {
                         if let YamlData::Value(Scalar::String(s)) = &value.data {
@@ -147,6 +199,8 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for StringSchema {
                         debug!("[StringSchema] Unsupported key for `type: string`: {key}");
                     }
                 }
+            } else if let YamlData::Mapping(_) | YamlData::Sequence(_) = &key.data {
+                return Err(loader::complex_key_error(key));
             } else {
                 return Err(expected_scalar!(
                     "{} Expected a scalar key, got: {:?}",
@@ -173,8 +227,14 @@ impl std::fmt::Display for StringSchema {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "StringSchema {{ min_length: {:?}, max_length: {:?}, pattern: {:?}, format: {:?} }}",
-            self.min_length, self.max_length, self.pattern, self.format
+            "StringSchema {{ min_length: {:?}, max_length: {:?}, pattern: {:?}, format: {:?}, content_encoding: {:?}, content_media_type: {:?}, trim: {:?} }}",
+            self.min_length,
+            self.max_length,
+            self.pattern,
+            self.format,
+            self.content_encoding,
+            self.content_media_type,
+            self.trim
         )
     }
 }
@@ -215,4 +275,19 @@ impl StringSchemaBuilder {
         self.0.format = Some(format);
         self
     }
+
+    pub fn content_encoding<S: Into<String>>(&mut self, content_encoding: S) -> &mut Self {
+        self.0.content_encoding = Some(content_encoding.into());
+        self
+    }
+
+    pub fn content_media_type<S: Into<String>>(&mut self, content_media_type: S) -> &mut Self {
+        self.0.content_media_type = Some(content_media_type.into());
+        self
+    }
+
+    pub fn trim(&mut self, trim: bool) -> &mut Self {
+        self.0.trim = trim;
+        self
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 use std::fmt::Display;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use hashlink::LinkedHashMap;
 use jsonptr::Token;
@@ -31,6 +31,7 @@ use crate::schemas::ObjectSchema;
 use crate::schemas::OneOfSchema;
 use crate::schemas::StringSchema;
 use crate::utils::format_annotated_mapping;
+use crate::utils::format_const_like;
 use crate::utils::format_linked_hash_map;
 use crate::utils::format_marked_yaml;
 use crate::utils::format_marker;
@@ -39,6 +40,44 @@ use crate::utils::format_vec;
 use crate::utils::format_yaml_data;
 use crate::utils::scalar_to_string;
 use crate::validation::ArrayUnevaluatedAnnotations;
+use crate::validation::ValidationError;
+
+/// Keywords that, without a `type`, load an [`ArraySchema`] applied to sequences only.
+const INFERRED_ARRAY_KEYWORDS: &[&str] = &[
+    "contains",
+    "items",
+    "maxContains",
+    "maxItems",
+    "minContains",
+    "minItems",
+    "prefixItems",
+    "uniqueItems",
+];
+
+/// Keywords that, without a `type`, load a [`NumberSchema`] applied to numbers only.
+const INFERRED_NUMBER_KEYWORDS: &[&str] = &[
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "maximum",
+    "minimum",
+    "multipleOf",
+];
+
+/// Keywords that, without a `type`, load an [`ObjectSchema`] applied to mappings only.
+const INFERRED_OBJECT_KEYWORDS: &[&str] = &[
+    "additionalProperties",
+    "dependentRequired",
+    "dependentSchemas",
+    "maxProperties",
+    "minProperties",
+    "patternProperties",
+    "properties",
+    "propertyNames",
+    "required",
+];
+
+/// Keywords that, without a `type`, load a [`StringSchema`] applied to strings only.
+const INFERRED_STRING_KEYWORDS: &[&str] = &["maxLength", "minLength", "pattern"];
 
 /// YamlSchema is the base of the validation model
 #[derive(Debug, PartialEq)]
@@ -93,18 +132,61 @@ impl YamlSchema {
     }
 
     /// Resolve a portion of a JSON Pointer to an element in the schema.
+    ///
+    /// `key` is the keyword token to step into (e.g. `properties`), and `components` are the
+    /// remaining pointer components that follow it. With no `key`, `components` are resolved
+    /// starting from this schema.
     pub fn resolve(
         &self,
         key: Option<&Token>,
         components: &[jsonptr::Component],
     ) -> Option<&YamlSchema> {
         debug!("[YamlSchema#resolve] self: {self}, key: {key:?}, components: {components:?}");
-        if components.is_empty() {
-            return Some(self);
+        match key {
+            None => self.resolve_components(components),
+            Some(token) => match self {
+                YamlSchema::Subschema(subschema) => subschema.resolve(Some(token), components),
+                _ => None,
+            },
         }
-        match self {
-            YamlSchema::Subschema(subschema) => subschema.resolve(key, components),
-            _ => None,
+    }
+
+    /// Whether this schema's `type` alone rules out `value`, so an `anyOf` / `oneOf` branch can be
+    /// counted as non-matching without validating it.
+    ///
+    /// Conservative: schemas with `$ref`, composition keywords, or a `type` whose outcome depends
+    /// on more than the YAML kind of `value` are never excluded, and neither is anything when
+    /// suppression rules could turn the type error into a suppressed one.
+    pub(crate) fn type_excludes(&self, context: &Context, value: &MarkedYaml) -> bool {
+        let YamlSchema::Subschema(subschema) = self else {
+            return false;
+        };
+        if subschema.r#ref.is_some()
+            || subschema.any_of.is_some()
+            || subschema.all_of.is_some()
+            || subschema.one_of.is_some()
+            || subschema.not.is_some()
+            || subschema.if_then_else.is_some()
+            || !context.options.suppressions.is_empty()
+        {
+            return false;
+        }
+        let types: Vec<&str> = match &subschema.r#type {
+            SchemaType::None => return false,
+            SchemaType::Single(s) => vec![s],
+            SchemaType::Multiple(values) => values.iter().map(|s| s.as_ref()).collect(),
+        };
+        !types
+            .iter()
+            .any(|t| type_could_match(t, value, context.options.null_as_empty_string))
+    }
+
+    /// Resolve the pointer components relative to this schema. Empty components resolve to `self`.
+    pub fn resolve_components(&self, components: &[jsonptr::Component]) -> Option<&YamlSchema> {
+        match components.split_first() {
+            None => Some(self),
+            Some((jsonptr::Component::Token(token), rest)) => self.resolve(Some(token), rest),
+            Some((jsonptr::Component::Root, rest)) => self.resolve_components(rest),
         }
     }
 }
@@ -162,6 +244,7 @@ impl From<StringSchema> for YamlSchema {
 
 impl Validator for YamlSchema {
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
+        context.count_check(|counts| counts.validators += 1);
         debug!("[YamlSchema] self: {self}");
         debug!(
             "[YamlSchema] Validating value: {}",
@@ -186,8 +269,27 @@ impl Validator for YamlSchema {
             }
             YamlSchema::Subschema(subschema) => {
                 debug!("[YamlSchema#validate] Validating subschema: {subschema:?}");
-                subschema.validate(context, value)?;
-                Ok(())
+                let Some(annotations) = &context.annotations else {
+                    return subschema.validate(context, value);
+                };
+                // A schema's annotations, and those of its subschemas, only count if it matched
+                let annotations_before = annotations.borrow().len();
+                let errors_before = context.errors.borrow().len();
+                let metadata = &subschema.metadata_and_annotations;
+                if let Some(title) = &metadata.title {
+                    context.add_annotation("title", title);
+                }
+                if let Some(description) = &metadata.description {
+                    context.add_annotation("description", description);
+                }
+                let result = subschema.validate(context, value);
+                let failed = context.errors.borrow()[errors_before..]
+                    .iter()
+                    .any(ValidationError::is_error);
+                if failed || result.is_err() {
+                    annotations.borrow_mut().truncate(annotations_before);
+                }
+                result
             }
         }
     }
@@ -360,43 +462,274 @@ pub struct Subschema {
 }
 
 impl Subschema {
+    pub fn builder() -> SubschemaBuilder {
+        SubschemaBuilder::new()
+    }
+
     /// Resolve a portion of a JSON Pointer to an element in the schema.
+    ///
+    /// `token` is the keyword to step into. Keywords holding a map of schemas (`$defs`,
+    /// `properties`, `dependentSchemas`) consume the next component as the entry name, and
+    /// keywords holding an array of schemas (`prefixItems`, `anyOf`, `allOf`, `oneOf`) consume it
+    /// as an index. The remaining components are resolved against the selected schema.
     pub fn resolve(
         &self,
         token: Option<&Token>,
         components: &[jsonptr::Component],
     ) -> Option<&YamlSchema> {
         debug!("[Subschema#resolve] self: {self}, token: {token:?}, components: {components:?}");
-        if let Some(token) = token {
-            let s = token.decoded();
-            debug!("[Subschema#resolve] key: {s}");
-            match s.as_ref() {
-                "$defs" => {
-                    debug!("[Subschema#resolve] Resolving $defs");
-                    if let Some(defs) = self.defs.as_ref() {
-                        debug!("[Subschema#resolve] defs: {}", format_linked_hash_map(defs));
-                        if let Some(component) = components.first() {
-                            debug!("[Subschema#resolve] component: {component:?}");
-                            if let jsonptr::Component::Token(next_token) = component {
-                                let decoded = next_token.decoded();
-                                debug!("[Subschema#resolve] decoded: {decoded}");
-                                debug!("[Subschema#resolve] defs: {defs:?}");
-                                if let Some(schema) = defs.get(decoded.as_ref()) {
-                                    debug!("[Subschema#resolve] schema: {schema:?}");
-                                    return schema.resolve(Some(next_token), &components[1..]);
-                                }
-                            }
-                        }
-                    }
+        let keyword = token?.decoded();
+        debug!("[Subschema#resolve] key: {keyword}");
+        let (next, rest) = match components.split_first() {
+            Some((jsonptr::Component::Token(next), rest)) => (Some(next), rest),
+            _ => (None, components),
+        };
+        let object_schema = self.object_schema.as_ref();
+        let array_schema = self.array_schema.as_ref();
+        let (schema, rest) = match keyword.as_ref() {
+            "$defs" => (lookup_named(self.defs.as_ref(), next)?, rest),
+            "properties" => (
+                lookup_named(object_schema.and_then(|o| o.properties.as_ref()), next)?,
+                rest,
+            ),
+            "dependentSchemas" => (
+                lookup_named(
+                    object_schema.and_then(|o| o.dependent_schemas.as_ref()),
+                    next,
+                )?,
+                rest,
+            ),
+            "prefixItems" => (
+                lookup_indexed(array_schema.and_then(|a| a.prefix_items.as_ref()), next)?,
+                rest,
+            ),
+            "anyOf" => (
+                lookup_indexed(self.any_of.as_ref().map(|s| &s.any_of), next)?,
+                rest,
+            ),
+            "allOf" => (
+                lookup_indexed(self.all_of.as_ref().map(|s| &s.all_of), next)?,
+                rest,
+            ),
+            "oneOf" => (
+                lookup_indexed(self.one_of.as_ref().map(|s| &s.one_of), next)?,
+                rest,
+            ),
+            "items" => match array_schema.and_then(|a| a.items.as_ref())? {
+                BooleanOrSchema::Schema(schema) => (schema, components),
+                BooleanOrSchema::Boolean(_) => return None,
+            },
+            "additionalProperties" => {
+                match object_schema.and_then(|o| o.additional_properties.as_ref())? {
+                    BooleanOrSchema::Schema(schema) => (schema, components),
+                    BooleanOrSchema::Boolean(_) => return None,
                 }
-                "anyOf" => {}
-                _ => (),
+            }
+            "contains" => (array_schema.and_then(|a| a.contains.as_ref())?, components),
+            "propertyNames" => (
+                object_schema.and_then(|o| o.property_names.as_ref())?,
+                components,
+            ),
+            "patternProperties" => {
+                let pattern = next?.decoded();
+                let pattern_property = object_schema
+                    .and_then(|o| o.pattern_properties.as_ref())?
+                    .iter()
+                    .find(|pp| pp.regex.as_str() == pattern.as_ref())?;
+                (&pattern_property.schema, rest)
+            }
+            "not" => (self.not.as_ref().map(|n| n.not.as_ref())?, components),
+            "if" => (
+                self.if_then_else.as_ref().map(|i| i.if_schema.as_ref())?,
+                components,
+            ),
+            "then" => (
+                self.if_then_else
+                    .as_ref()
+                    .and_then(|i| i.then_schema.as_deref())?,
+                components,
+            ),
+            "else" => (
+                self.if_then_else
+                    .as_ref()
+                    .and_then(|i| i.else_schema.as_deref())?,
+                components,
+            ),
+            "unevaluatedProperties" => match self.unevaluated_properties.as_ref()? {
+                BooleanOrSchema::Schema(schema) => (schema, components),
+                BooleanOrSchema::Boolean(_) => return None,
+            },
+            "unevaluatedItems" => match self.unevaluated_items.as_ref()? {
+                BooleanOrSchema::Schema(schema) => (schema, components),
+                BooleanOrSchema::Boolean(_) => return None,
+            },
+            _ => return None,
+        };
+        debug!("[Subschema#resolve] schema: {schema}");
+        schema.resolve_components(rest)
+    }
+
+    /// The schemas directly nested in this one, each with the JSON Pointer tokens that
+    /// [`resolve`](Self::resolve) accepts to reach it from this schema.
+    pub fn child_schemas(&self) -> Vec<(Vec<String>, &YamlSchema)> {
+        fn named<'s>(
+            children: &mut Vec<(Vec<String>, &'s YamlSchema)>,
+            keyword: &str,
+            map: Option<&'s LinkedHashMap<String, YamlSchema>>,
+        ) {
+            for (name, schema) in map.into_iter().flatten() {
+                children.push((vec![keyword.to_string(), name.clone()], schema));
+            }
+        }
+        fn indexed<'s>(
+            children: &mut Vec<(Vec<String>, &'s YamlSchema)>,
+            keyword: &str,
+            schemas: Option<&'s Vec<YamlSchema>>,
+        ) {
+            for (index, schema) in schemas.into_iter().flatten().enumerate() {
+                children.push((vec![keyword.to_string(), index.to_string()], schema));
+            }
+        }
+        fn single<'s>(
+            children: &mut Vec<(Vec<String>, &'s YamlSchema)>,
+            keyword: &str,
+            schema: Option<&'s YamlSchema>,
+        ) {
+            if let Some(schema) = schema {
+                children.push((vec![keyword.to_string()], schema));
             }
         }
-        None
+        fn boolean_or_schema(value: Option<&BooleanOrSchema>) -> Option<&YamlSchema> {
+            match value? {
+                BooleanOrSchema::Schema(schema) => Some(schema),
+                BooleanOrSchema::Boolean(_) => None,
+            }
+        }
+
+        let mut children = Vec::new();
+        named(&mut children, "$defs", self.defs.as_ref());
+        if let Some(object_schema) = &self.object_schema {
+            named(
+                &mut children,
+                "properties",
+                object_schema.properties.as_ref(),
+            );
+            for pp in object_schema.pattern_properties.iter().flatten() {
+                children.push((
+                    vec![
+                        "patternProperties".to_string(),
+                        pp.regex.as_str().to_string(),
+                    ],
+                    &pp.schema,
+                ));
+            }
+            named(
+                &mut children,
+                "dependentSchemas",
+                object_schema.dependent_schemas.as_ref(),
+            );
+            single(
+                &mut children,
+                "additionalProperties",
+                boolean_or_schema(object_schema.additional_properties.as_ref()),
+            );
+            single(
+                &mut children,
+                "propertyNames",
+                object_schema.property_names.as_ref(),
+            );
+        }
+        if let Some(array_schema) = &self.array_schema {
+            indexed(
+                &mut children,
+                "prefixItems",
+                array_schema.prefix_items.as_ref(),
+            );
+            single(
+                &mut children,
+                "items",
+                boolean_or_schema(array_schema.items.as_ref()),
+            );
+            single(&mut children, "contains", array_schema.contains.as_ref());
+        }
+        indexed(
+            &mut children,
+            "anyOf",
+            self.any_of.as_ref().map(|s| &s.any_of),
+        );
+        indexed(
+            &mut children,
+            "allOf",
+            self.all_of.as_ref().map(|s| &s.all_of),
+        );
+        indexed(
+            &mut children,
+            "oneOf",
+            self.one_of.as_ref().map(|s| &s.one_of),
+        );
+        single(
+            &mut children,
+            "not",
+            self.not.as_ref().map(|n| n.not.as_ref()),
+        );
+        if let Some(if_then_else) = &self.if_then_else {
+            single(&mut children, "if", Some(if_then_else.if_schema.as_ref()));
+            single(&mut children, "then", if_then_else.then_schema.as_deref());
+            single(&mut children, "else", if_then_else.else_schema.as_deref());
+        }
+        single(
+            &mut children,
+            "unevaluatedProperties",
+            boolean_or_schema(self.unevaluated_properties.as_ref()),
+        );
+        single(
+            &mut children,
+            "unevaluatedItems",
+            boolean_or_schema(self.unevaluated_items.as_ref()),
+        );
+        children
     }
 }
 
+/// Load an `$anchor` value, which must be a plain name: a letter or `_` followed by letters,
+/// digits, `-`, `_` or `.`
+fn load_anchor(value: &MarkedYaml) -> Result<String> {
+    let anchor = marked_yaml_to_string(value, "$anchor must be a string")?;
+    let mut chars = anchor.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(anchor)
+    } else {
+        Err(schema_loading_error!(
+            "{} Invalid $anchor: {:?} (must start with a letter or '_', followed by letters, digits, '-', '_' or '.')",
+            format_marker(&value.span.start),
+            anchor
+        ))
+    }
+}
+
+/// Look up a named entry (e.g. in `$defs` or `properties`) by the decoded pointer token
+fn lookup_named<'s>(
+    map: Option<&'s LinkedHashMap<String, YamlSchema>>,
+    name: Option<&Token>,
+) -> Option<&'s YamlSchema> {
+    let decoded = name?.decoded();
+    debug!("[Subschema#resolve] decoded: {decoded}");
+    map?.get(decoded.as_ref())
+}
+
+/// Look up an entry in an array of schemas (e.g. `anyOf` or `prefixItems`) by pointer index
+fn lookup_indexed<'s>(
+    schemas: Option<&'s Vec<YamlSchema>>,
+    index: Option<&Token>,
+) -> Option<&'s YamlSchema> {
+    let index: usize = index?.decoded().parse().ok()?;
+    schemas?.get(index)
+}
+
 // Try to load a Subschema from a MarkedYaml. Delegate to the TryFrom<&AnnotatedMapping<'_>> for mappings.
 // If the MarkedYaml is not a mapping, returns an error.
 impl<'r> TryFrom<&MarkedYaml<'r>> for Subschema {
@@ -428,7 +761,10 @@ fn try_load_defs<'r>(marked_yaml: &MarkedYaml<'r>) -> Result<LinkedHashMap<Strin
             .iter()
             .try_fold(LinkedHashMap::new(), |mut acc, (key, value)| {
                 let key = marked_yaml_mapping_key_to_string(key)?;
-                acc.insert(key, value.try_into()?);
+                let schema = value
+                    .try_into()
+                    .map_err(|e: Error| e.in_nested_schema(format!("$defs.{key}"), value))?;
+                acc.insert(key, schema);
                 Ok(acc)
             })
     } else {
@@ -451,6 +787,12 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for Subschema {
         let metadata_and_annotations = MetadataAndAnnotations::try_from(mapping)?;
         debug!("[Subschema#try_from] metadata_and_annotations: {metadata_and_annotations}");
 
+        // $anchor
+        let anchor = mapping
+            .get(&MarkedYaml::value_from_str("$anchor"))
+            .map(load_anchor)
+            .transpose()?;
+
         // $defs
         let defs: Option<LinkedHashMap<String, YamlSchema>> = mapping
             .get(&MarkedYaml::value_from_str("$defs"))
@@ -603,21 +945,28 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for Subschema {
             }
         }
 
-        // When `type` is omitted but `properties` is present, treat as `type: object` (JSON Schema-style).
-        if r#type.is_none() && mapping.contains_key(&MarkedYaml::value_from_str("properties")) {
-            r#type = SchemaType::new("object");
-            object_schema = ObjectSchema::try_from(mapping).map(Some)?;
-        }
-
-        // When `type` is omitted but string validation keywords are present, treat as `type: string`
-        // so `pattern` / `minLength` / `maxLength` are not ignored (JSON Schema-style).
-        if r#type.is_none()
-            && (mapping.contains_key(&MarkedYaml::value_from_str("pattern"))
-                || mapping.contains_key(&MarkedYaml::value_from_str("minLength"))
-                || mapping.contains_key(&MarkedYaml::value_from_str("maxLength")))
-        {
-            r#type = SchemaType::new("string");
-            string_schema = StringSchema::try_from(mapping).map(Some)?;
+        // When `type` is omitted, keywords that only apply to one kind of value are still loaded
+        // (JSON Schema-style), e.g. `properties` constrains mappings, so the names they match
+        // count as evaluated for `unevaluatedProperties` in a parent schema. Values of other
+        // kinds are not constrained by them, see `validate_by_inferred_types`.
+        if r#type.is_none() {
+            let has_any = |keywords: &[&str]| {
+                keywords
+                    .iter()
+                    .any(|k| mapping.contains_key(&MarkedYaml::value_from_str(k)))
+            };
+            if has_any(INFERRED_ARRAY_KEYWORDS) {
+                array_schema = ArraySchema::try_from(mapping).map(Some)?;
+            }
+            if has_any(INFERRED_NUMBER_KEYWORDS) {
+                number_schema = NumberSchema::try_from(mapping).map(Some)?;
+            }
+            if has_any(INFERRED_OBJECT_KEYWORDS) {
+                object_schema = ObjectSchema::try_from(mapping).map(Some)?;
+            }
+            if has_any(INFERRED_STRING_KEYWORDS) {
+                string_schema = StringSchema::try_from(mapping).map(Some)?;
+            }
         }
 
         let unevaluated_properties = mapping
@@ -654,7 +1003,7 @@ impl<'r> TryFrom<&AnnotatedMapping<'r, MarkedYaml<'r>>> for Subschema {
             string_schema,
             unevaluated_properties,
             unevaluated_items,
-            anchor: None,
+            anchor,
         })
     }
 }
@@ -716,21 +1065,31 @@ impl Validator for Subschema {
             if let Some(root_schema) = context.root_schema {
                 if let Some(ref_path) = ref_name.strip_prefix("#") {
                     if context.is_resolving_ref(ref_name, value) {
-                        context.add_error(value, format!("Circular $ref detected: {ref_name}"));
+                        context.with_schema_segment(&["$ref"], || {
+                            context.add_error(value, format!("Circular $ref detected: {ref_name}"))
+                        });
                         return Ok(());
                     }
-                    let pointer = jsonptr::Pointer::parse(ref_path)?;
-                    debug!("[Subschema] Pointer: {pointer}");
-                    let schema = root_schema.resolve(pointer);
+                    // `#/...` is a JSON Pointer; `#name` refers to an `$anchor`
+                    let schema = if ref_path.is_empty() || ref_path.starts_with('/') {
+                        let pointer = jsonptr::Pointer::parse(ref_path)?;
+                        debug!("[Subschema] Pointer: {pointer}");
+                        root_schema.resolve(pointer)
+                    } else {
+                        root_schema.resolve_anchor(ref_path)
+                    };
                     if let Some(schema) = schema {
                         debug!("[Subschema] Found {ref_path}: {schema}");
                         context.begin_resolving_ref(ref_name, value);
-                        let result = schema.validate(context, value);
+                        let result = context
+                            .with_schema_segment(&["$ref"], || schema.validate(context, value));
                         context.end_resolving_ref(ref_name, value);
                         result?;
                     } else {
                         error!("[Subschema] Cannot find definition: {ref_path}");
-                        context.add_error(value, format!("Schema {ref_path} not found"));
+                        context.with_schema_segment(&["$ref"], || {
+                            context.add_error(value, format!("Schema {ref_path} not found"))
+                        });
                     }
                 } else {
                     // External ref: resolve, load schema if needed, resolve fragment
@@ -754,7 +1113,9 @@ impl Validator for Subschema {
                     };
                     let ref_key = resolved_url.to_string();
                     if context.is_resolving_ref(&ref_key, value) {
-                        context.add_error(value, format!("Circular $ref detected: {ref_name}"));
+                        context.with_schema_segment(&["$ref"], || {
+                            context.add_error(value, format!("Circular $ref detected: {ref_name}"))
+                        });
                         return Ok(());
                     }
                     let doc_url = {
@@ -762,60 +1123,76 @@ impl Validator for Subschema {
                         u.set_fragment(None);
                         u.to_string()
                     };
-                    let fragment = resolved_url.fragment().and_then(|f| {
-                        let s = if f.starts_with('/') {
-                            f.to_string()
-                        } else {
-                            format!("/{f}")
-                        };
-                        if s.is_empty() || s == "/" {
-                            None
-                        } else {
-                            Some(s)
-                        }
-                    });
+                    let fragment = resolved_url
+                        .fragment()
+                        .filter(|f| !f.is_empty() && *f != "/")
+                        .map(|f| f.to_string());
                     {
                         let mut schemas = context.schemas.borrow_mut();
                         if !schemas.contains_key(&doc_url) {
-                            let loaded = load_external_schema(&doc_url)?;
-                            let schema_rc = Rc::new(loaded);
+                            let remote =
+                                doc_url.starts_with("http://") || doc_url.starts_with("https://");
+                            if remote && !context.options.allow_remote_refs {
+                                return Err(generic_error!(
+                                    "Cannot resolve $ref {}: fetching {} over the network is disabled (enable `allow_remote_refs` in the validation options)",
+                                    ref_name,
+                                    doc_url
+                                ));
+                            }
+                            let loaded = load_external_schema(&doc_url).map_err(|e| {
+                                generic_error!(
+                                    "Failed to load $ref {} from {}: {}",
+                                    ref_name,
+                                    doc_url,
+                                    e
+                                )
+                            })?;
+                            let schema_rc = Arc::new(loaded);
                             let key = schema_rc.cache_key(&doc_url);
-                            schemas.insert(key.clone(), Rc::clone(&schema_rc));
+                            schemas.insert(key.clone(), Arc::clone(&schema_rc));
                             if key != doc_url {
                                 schemas.insert(doc_url.clone(), schema_rc);
                             }
                         }
                     }
-                    let schemas = context.schemas.borrow();
-                    let schema = schemas.get(&doc_url).ok_or_else(|| {
-                        generic_error!("Schema {doc_url} not in cache after load")
-                    })?;
-                    let pointer_opt = fragment
-                        .as_ref()
-                        .map(|frag| jsonptr::Pointer::parse(frag))
-                        .transpose()?;
-                    let target = match &pointer_opt {
-                        Some(pointer) => schema.resolve(pointer),
+                    // Clone the Arc out so the cache isn't borrowed while validating, since nested
+                    // external refs may need to load more schemas into it.
+                    let schema =
+                        context
+                            .schemas
+                            .borrow()
+                            .get(&doc_url)
+                            .cloned()
+                            .ok_or_else(|| {
+                                generic_error!("Schema {doc_url} not in cache after load")
+                            })?;
+                    let target = match &fragment {
+                        Some(fragment) => schema.resolve_fragment(fragment),
                         None => Some(&schema.schema),
                     };
                     if let Some(target) = target {
+                        // Local `$ref`s inside the external document resolve against that document
+                        let external_context = context.with_root(&schema);
                         context.begin_resolving_ref(&ref_key, value);
-                        let result = target.validate(context, value);
+                        let result = context.with_schema_segment(&["$ref"], || {
+                            target.validate(&external_context, value)
+                        });
                         context.end_resolving_ref(&ref_key, value);
                         result?;
                     } else {
                         error!("[Subschema] Cannot find definition: {:?}", fragment);
-                        context.add_error(
+                        context.with_schema_segment(&["$ref"], || context.add_error(
                             value,
-                            format!("Schema {:?} not found in {doc_url}", fragment),
-                        );
+                            format!(
+                                "Cannot resolve $ref {ref_name}: fragment #{} not found in {doc_url}",
+                                fragment.as_deref().unwrap_or_default()
+                            ),
+                        ));
                     }
                 }
                 return Ok(());
             } else {
-                return Err(generic_error!(
-                    "Subschema has a reference, but no root schema was provided!"
-                ));
+                return Err(Error::MissingRootSchema);
             }
         }
 
@@ -849,7 +1226,7 @@ impl Validator for Subschema {
         }
 
         match &self.r#type {
-            SchemaType::None => (),
+            SchemaType::None => self.validate_by_inferred_types(&ctx, value)?,
             SchemaType::Single(s) => self.validate_by_type(&ctx, s.as_ref(), value)?,
             SchemaType::Multiple(values) => {
                 debug!(
@@ -859,14 +1236,14 @@ impl Validator for Subschema {
                 let mut any_matched = false;
                 for s in values {
                     let sub_context = ctx.get_sub_context();
-                    self.validate_by_type(&sub_context, s.as_ref(), value)?;
-                    if !sub_context.has_errors() {
+                    let result = self.validate_by_type(&sub_context, s.as_ref(), value);
+                    if ctx.branch_matched("type", value, &sub_context, result)? {
                         any_matched = true;
                         break;
                     }
                 }
                 if !any_matched {
-                    ctx.add_error(
+                    ctx.add_type_error(
                         value,
                         format!("None of type: [{}] matched", values.join(", ")),
                     );
@@ -877,13 +1254,15 @@ impl Validator for Subschema {
         if let Some(r#const) = &self.r#const
             && !r#const.accepts(value)
         {
-            ctx.add_error(
+            ctx.add_keyword_error(
                 value,
                 format!(
-                    "Expected const: {:#?}, but got: {}",
-                    r#const,
-                    format_yaml_data(&value.data)
+                    "Value {} does not match const: {}",
+                    format_const_like(&value.data),
+                    r#const
                 ),
+                "const",
+                r#const,
             );
         }
 
@@ -898,6 +1277,27 @@ impl Validator for Subschema {
     }
 }
 
+/// Whether a value of `value`'s YAML kind can pass `type: {r#type}`. Unknown types and node
+/// kinds are assumed to match, so they are always validated.
+fn type_could_match(r#type: &str, value: &MarkedYaml, null_as_empty_string: bool) -> bool {
+    match (&value.data, r#type) {
+        (YamlData::Value(Scalar::Null), "null") => true,
+        (YamlData::Value(Scalar::Null), "string") => null_as_empty_string,
+        (YamlData::Value(Scalar::Boolean(_)), "boolean") => true,
+        (YamlData::Value(Scalar::Integer(_) | Scalar::FloatingPoint(_)), "integer" | "number") => {
+            true
+        }
+        (YamlData::Value(Scalar::String(_)), "string") => true,
+        (YamlData::Mapping(_), "object") => true,
+        (YamlData::Sequence(_), "array") => true,
+        (
+            YamlData::Value(_) | YamlData::Mapping(_) | YamlData::Sequence(_),
+            "null" | "boolean" | "integer" | "number" | "string" | "object" | "array",
+        ) => false,
+        _ => true,
+    }
+}
+
 impl Subschema {
     fn validation_context_for_instance<'r>(base: &Context<'r>, value: &MarkedYaml) -> Context<'r> {
         match &value.data {
@@ -930,13 +1330,8 @@ impl Subschema {
             for (k, v) in mapping.iter() {
                 let key_string = match &k.data {
                     YamlData::Value(scalar) => scalar_to_string(scalar),
-                    _ => {
-                        return Err(expected_scalar!(
-                            "[{}] Expected a scalar object key, got: {:?}",
-                            format_marker(&k.span.start),
-                            k.data
-                        ));
-                    }
+                    // Complex keys are reported by the object validator
+                    _ => continue,
                 };
                 if key_string == "$schema" {
                     continue;
@@ -945,18 +1340,17 @@ impl Subschema {
                     continue;
                 }
                 let prop_ctx = ctx.append_path(&key_string);
-                match u {
+                ctx.with_schema_segment(&["unevaluatedProperties"], || match u {
                     BooleanOrSchema::Boolean(false) => {
                         ctx.add_error(
                             v,
                             format!("Unevaluated property '{key_string}' is not allowed!"),
                         );
+                        Ok(())
                     }
-                    BooleanOrSchema::Boolean(true) => {}
-                    BooleanOrSchema::Schema(s) => {
-                        s.validate(&prop_ctx, v)?;
-                    }
-                }
+                    BooleanOrSchema::Boolean(true) => Ok(()),
+                    BooleanOrSchema::Schema(s) => s.validate(&prop_ctx, v),
+                })?;
             }
         }
 
@@ -972,24 +1366,23 @@ impl Subschema {
                 return Ok(());
             }
             let indices = ann.indices_requiring_unevaluated(seq.len());
-            let err_before = ctx.errors.borrow().len();
+            let err_before = ctx.error_count();
             for i in indices.iter().copied() {
                 let item = &seq[i];
-                let item_ctx = ctx.append_path(i.to_string());
-                match u {
+                let item_ctx = ctx.append_index(i);
+                ctx.with_schema_segment(&["unevaluatedItems"], || match u {
                     BooleanOrSchema::Boolean(false) => {
                         ctx.add_error(
                             item,
                             format!("Unevaluated array item at index {i} is not allowed!"),
                         );
+                        Ok(())
                     }
-                    BooleanOrSchema::Boolean(true) => {}
-                    BooleanOrSchema::Schema(s) => {
-                        s.validate(&item_ctx, item)?;
-                    }
-                }
+                    BooleanOrSchema::Boolean(true) => Ok(()),
+                    BooleanOrSchema::Schema(s) => s.validate(&item_ctx, item),
+                })?;
             }
-            if ctx.errors.borrow().len() == err_before
+            if ctx.error_count() == err_before
                 && !indices.is_empty()
                 && let Some(cell) = &ctx.array_unevaluated
             {
@@ -1002,6 +1395,40 @@ impl Subschema {
         Ok(())
     }
 
+    /// Without a `type`, applies the schema of the value's kind, if keywords such as `properties`
+    /// or `minimum` loaded one. Values of other kinds are accepted.
+    fn validate_by_inferred_types(&self, context: &Context, value: &MarkedYaml) -> Result<()> {
+        match &value.data {
+            YamlData::Mapping(_) => {
+                if let Some(object_schema) = &self.object_schema {
+                    object_schema.validate(context, value)?;
+                }
+            }
+            YamlData::Sequence(_) => {
+                if let Some(array_schema) = &self.array_schema {
+                    array_schema.validate(context, value)?;
+                }
+            }
+            YamlData::Value(Scalar::Integer(_) | Scalar::FloatingPoint(_)) => {
+                if let Some(number_schema) = &self.number_schema {
+                    number_schema.validate(context, value)?;
+                }
+            }
+            YamlData::Value(Scalar::String(_)) => {
+                if let Some(string_schema) = &self.string_schema {
+                    string_schema.validate(context, value)?;
+                }
+            }
+            YamlData::Value(Scalar::Null) if context.options.null_as_empty_string => {
+                if let Some(string_schema) = &self.string_schema {
+                    string_schema.validate(context, value)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn validate_by_type(
         &self,
         context: &Context,
@@ -1016,12 +1443,15 @@ impl Subschema {
                     array_schema.validate(context, value)?;
                 } else {
                     error!("[Subschema#validate_by_type] No array schema found");
-                    context.add_error(value, format!("No array schema found for type: {}", r#type));
+                    context.add_type_error(
+                        value,
+                        format!("No array schema found for type: {}", r#type),
+                    );
                 }
             }
             "boolean" => {
                 if !matches!(&value.data, YamlData::Value(Scalar::Boolean(_))) {
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!(
                             "Expected boolean, but got: {}",
@@ -1032,7 +1462,7 @@ impl Subschema {
             }
             "null" => {
                 if !matches!(&value.data, YamlData::Value(Scalar::Null)) {
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!("Expected null, but got: {}", format_yaml_data(&value.data)),
                     );
@@ -1044,7 +1474,7 @@ impl Subschema {
                     string_schema.validate(context, value)?;
                 } else {
                     error!("[Subschema#validate_by_type] No string schema found");
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!("No string schema found for type: {}", r#type),
                     );
@@ -1056,7 +1486,7 @@ impl Subschema {
                     number_schema.validate(context, value)?;
                 } else {
                     error!("[Subschema#validate_by_type] No number schema found");
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!("No number schema found for type: {}", r#type),
                     );
@@ -1068,7 +1498,7 @@ impl Subschema {
                     integer_schema.validate(context, value)?;
                 } else {
                     error!("[Subschema#validate_by_type] No integer schema found");
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!("No integer schema found for type: {}", r#type),
                     );
@@ -1080,7 +1510,7 @@ impl Subschema {
                     object_schema.validate(context, value)?;
                 } else {
                     error!("[Subschema#validate_by_type] No object schema found");
-                    context.add_error(
+                    context.add_type_error(
                         value,
                         format!("No object schema found for type: {}", r#type),
                     );
@@ -1088,13 +1518,94 @@ impl Subschema {
             }
             _ => {
                 error!("[Subschema#validate_by_type] Unsupported type: {}", r#type);
-                context.add_error(value, format!("Unsupported type: {}", r#type));
+                context.add_type_error(value, format!("Unsupported type: {}", r#type));
             }
         }
         Ok(())
     }
 }
 
+/// Builds a [`Subschema`] with combinators, e.g. an `anyOf` of two typed schemas:
+///
+/// ```
+/// use yaml_schema::Engine;
+/// use yaml_schema::RootSchema;
+/// use yaml_schema::YamlSchema;
+/// use yaml_schema::schemas::IntegerSchema;
+/// use yaml_schema::schemas::StringSchema;
+/// use yaml_schema::schemas::Subschema;
+///
+/// let schema = Subschema::builder()
+///     .any_of(vec![
+///         StringSchema::builder().min_length(1).build().into(),
+///         IntegerSchema::default().into(),
+///     ])
+///     .build();
+/// let root_schema = RootSchema::new(YamlSchema::subschema(schema));
+/// assert!(!Engine::evaluate(&root_schema, "name", false).unwrap().has_errors());
+/// assert!(!Engine::evaluate(&root_schema, "42", false).unwrap().has_errors());
+/// assert!(Engine::evaluate(&root_schema, "true", false).unwrap().has_errors());
+/// ```
+pub struct SubschemaBuilder(Subschema);
+
+impl Default for SubschemaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SubschemaBuilder {
+    pub fn new() -> Self {
+        Self(Subschema::default())
+    }
+
+    pub fn build(&mut self) -> Subschema {
+        std::mem::take(&mut self.0)
+    }
+
+    /// Set `type`, e.g. `SchemaType::new("object")`, with the default schema of each of the
+    /// types that hasn't been set yet
+    pub fn r#type(&mut self, r#type: SchemaType) -> &mut Self {
+        if r#type.is_or_contains("array") {
+            self.0.array_schema.get_or_insert_default();
+        }
+        if r#type.is_or_contains("integer") {
+            self.0.integer_schema.get_or_insert_default();
+        }
+        if r#type.is_or_contains("number") {
+            self.0.number_schema.get_or_insert_default();
+        }
+        if r#type.is_or_contains("object") {
+            self.0.object_schema.get_or_insert_default();
+        }
+        if r#type.is_or_contains("string") {
+            self.0.string_schema.get_or_insert_default();
+        }
+        self.0.r#type = r#type;
+        self
+    }
+
+    pub fn any_of(&mut self, any_of: Vec<YamlSchema>) -> &mut Self {
+        self.0.any_of = Some(AnyOfSchema { any_of });
+        self
+    }
+
+    pub fn one_of(&mut self, one_of: Vec<YamlSchema>) -> &mut Self {
+        self.0.one_of = Some(OneOfSchema { one_of });
+        self
+    }
+
+    pub fn all_of(&mut self, all_of: Vec<YamlSchema>) -> &mut Self {
+        self.0.all_of = Some(AllOfSchema { all_of });
+        self
+    }
+
+    pub fn not(&mut self, not: YamlSchema) -> &mut Self {
+        self.0.not = Some(NotSchema { not: Box::new(not) });
+        self
+    }
+}
+
 /// The `$id` and `$schema` metadata
 #[derive(Debug, Default, PartialEq)]
 pub struct MetadataAndAnnotations {
@@ -1106,6 +1617,8 @@ pub struct MetadataAndAnnotations {
     pub title: Option<String>,
     /// `description` annotation
     pub description: Option<String>,
+    /// `default` annotation
+    pub default: Option<ConstValue>,
 }
 
 impl MetadataAndAnnotations {
@@ -1114,6 +1627,7 @@ impl MetadataAndAnnotations {
             && self.schema.is_none()
             && self.title.is_none()
             && self.description.is_none()
+            && self.default.is_none()
     }
 }
 
@@ -1134,6 +1648,9 @@ impl std::fmt::Display for MetadataAndAnnotations {
             if let Some(description) = &self.description {
                 write!(f, "description: {description}, ")?;
             }
+            if let Some(default) = &self.default {
+                write!(f, "default: {default}, ")?;
+            }
             write!(f, " ")?;
         }
         write!(f, "}}")?;
@@ -1167,6 +1684,9 @@ impl TryFrom<&AnnotatedMapping<'_, MarkedYaml<'_>>> for MetadataAndAnnotations {
                             "description must be a string",
                         )?);
                     }
+                    "default" => {
+                        metadata_and_annotations.default = Some(ConstValue::try_from(value)?);
+                    }
                     _ => {
                         debug!("[MetadataAndAnnotations#try_from] Unknown key: {s}");
                     }
@@ -1208,6 +1728,40 @@ mod tests {
         assert_eq!(type_value, "boolean");
     }
 
+    #[test]
+    fn test_subschema_builder() {
+        let schema = Subschema::builder()
+            .r#type(SchemaType::new("string"))
+            .all_of(vec![StringSchema::builder().min_length(1).build().into()])
+            .one_of(vec![
+                StringSchema::builder().max_length(2).build().into(),
+                StringSchema::builder()
+                    .pattern(regex::Regex::new("^a").unwrap())
+                    .build()
+                    .into(),
+            ])
+            .not(YamlSchema::subschema(Subschema {
+                r#const: Some(ConstValue::string("no")),
+                ..Default::default()
+            }))
+            .build();
+        assert!(schema.any_of.is_none());
+        assert_eq!(schema.r#type, SchemaType::new("string"));
+        let root_schema = crate::RootSchema::new(YamlSchema::subschema(schema));
+        for (value, valid) in [
+            ("abc", true),
+            ("xy", true),
+            ("ab", false),
+            ("xyz", false),
+            ("no", false),
+            ("''", false),
+            ("1", false),
+        ] {
+            let context = engine::Engine::evaluate(&root_schema, value, false).unwrap();
+            assert_eq!(!context.has_errors(), valid, "{value}");
+        }
+    }
+
     #[test]
     fn test_metadata_and_annotations_try_from() {
         let yaml = r#"
@@ -1314,10 +1868,7 @@ mod tests {
         let YamlSchema::Subschema(sub) = &root.schema else {
             panic!("expected subschema");
         };
-        assert!(
-            sub.r#type.is_or_contains("object"),
-            "expected inferred type object"
-        );
+        assert!(sub.r#type.is_none());
         assert!(sub.object_schema.is_some());
 
         let ok = engine::Engine::evaluate(&root, "foo: bar", false).unwrap();
@@ -1325,6 +1876,36 @@ mod tests {
 
         let bad = engine::Engine::evaluate(&root, "other: x", false).unwrap();
         assert!(bad.has_errors());
+
+        // Without `type: object`, values that aren't mappings are not constrained
+        for value in ["[1, 2]", "12", "foo", "null"] {
+            let context = engine::Engine::evaluate(&root, value, false).unwrap();
+            assert!(!context.has_errors(), "{value} should be valid");
+        }
+    }
+
+    #[test]
+    fn keywords_without_type_only_apply_to_values_of_their_kind() {
+        let yaml = r#"
+        minimum: 2
+        maxItems: 1
+        maxLength: 3
+        "#;
+        let root = loader::load_from_str(yaml).unwrap();
+        for (value, valid) in [
+            ("3", true),
+            ("1", false),
+            ("1.5", false),
+            ("[1]", true),
+            ("[1, 2]", false),
+            ("abc", true),
+            ("abcd", false),
+            ("a: 1", true),
+            ("true", true),
+        ] {
+            let context = engine::Engine::evaluate(&root, value, false).unwrap();
+            assert_eq!(!context.has_errors(), valid, "{value}");
+        }
     }
 
     #[test]
@@ -1372,4 +1953,170 @@ mod tests {
         let bad = engine::Engine::evaluate(&root, "a: ok\nb: no", false).unwrap();
         assert!(bad.has_errors());
     }
+    #[test]
+    fn unevaluated_properties_sees_conditional_and_one_of_branches() {
+        let root = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              kind:
+                type: string
+            if:
+              properties:
+                kind:
+                  const: server
+            then:
+              properties:
+                port:
+                  type: integer
+            else:
+              properties:
+                path:
+                  type: string
+            oneOf:
+              - properties:
+                  a:
+                    type: string
+                required: [a]
+              - properties:
+                  b:
+                    type: integer
+                required: [b]
+            unevaluatedProperties: false
+            "#,
+        )
+        .unwrap();
+        let errors = |value: &str| -> Vec<String> {
+            let context = engine::Engine::evaluate(&root, value, false).unwrap();
+            let errors = context.errors.borrow();
+            errors.iter().map(|e| e.error.clone()).collect()
+        };
+        assert!(errors("kind: server\nport: 1\na: x").is_empty());
+        assert!(errors("kind: file\npath: x\nb: 1").is_empty());
+        // Only the branch of the conditional that applies evaluates its properties
+        assert_eq!(
+            errors("kind: server\npath: x\na: x"),
+            vec!["Unevaluated property 'path' is not allowed!"]
+        );
+        assert_eq!(
+            errors("kind: file\nport: 1\nb: 1"),
+            vec!["Unevaluated property 'port' is not allowed!"]
+        );
+        // `a` is only declared by the `oneOf` branch that fails
+        assert_eq!(
+            errors("kind: file\na: 1\nb: 1"),
+            vec!["Unevaluated property 'a' is not allowed!"]
+        );
+    }
+
+    #[test]
+    fn unevaluated_properties_sees_all_of_branches() {
+        let root = loader::load_from_str(
+            r#"
+            type: object
+            allOf:
+              - properties:
+                  a:
+                    type: string
+              - patternProperties:
+                  "^x-":
+                    type: integer
+            unevaluatedProperties: false
+            "#,
+        )
+        .unwrap();
+        let ok = engine::Engine::evaluate(&root, "a: ok\nx-count: 1", false).unwrap();
+        assert!(!ok.has_errors());
+        let bad = engine::Engine::evaluate(&root, "a: ok\nb: no", false).unwrap();
+        let errors = bad.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Unevaluated property 'b' is not allowed!");
+
+        // `additionalProperties` only sees the sibling `properties`, so the same document fails
+        let root = loader::load_from_str(
+            r#"
+            type: object
+            allOf:
+              - properties:
+                  a:
+                    type: string
+            additionalProperties: false
+            "#,
+        )
+        .unwrap();
+        let context = engine::Engine::evaluate(&root, "a: ok", false).unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn enum_without_type() {
+        let root = loader::load_from_str("enum: [red, 42, null]").unwrap();
+        for accepted in ["red", "42", "null", "~"] {
+            let context = engine::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{accepted}");
+        }
+        let context = engine::Engine::evaluate(&root, "green", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error,
+            r#"Value "green" is not in the enum: ["red", 42, null]"#
+        );
+    }
+
+    #[test]
+    fn const_without_type() {
+        let cases = [
+            (
+                "const: 42",
+                "42.0",
+                "43",
+                "Value 43 does not match const: 42",
+            ),
+            (
+                "const: null",
+                "~",
+                "''",
+                r#"Value "" does not match const: null"#,
+            ),
+            (
+                "const: true",
+                "true",
+                "1",
+                "Value 1 does not match const: true",
+            ),
+            (
+                "const: [1, 2]",
+                "[1, 2]",
+                "[2, 1]",
+                "Value [2, 1] does not match const: [1, 2]",
+            ),
+            (
+                "const: {a: 1}",
+                "a: 1",
+                "a: 2",
+                r#"Value {"a": 2} does not match const: {"a": 1}"#,
+            ),
+        ];
+        for (schema, accepted, rejected, message) in cases {
+            let root = loader::load_from_str(schema).unwrap();
+            let context = engine::Engine::evaluate(&root, accepted, false).unwrap();
+            assert!(!context.has_errors(), "{schema}: {accepted}");
+            let context = engine::Engine::evaluate(&root, rejected, false).unwrap();
+            let errors = context.errors.borrow();
+            assert_eq!(errors.len(), 1, "{schema}: {rejected}");
+            assert_eq!(errors[0].error, message);
+        }
+    }
+
+    #[test]
+    fn enum_with_integer_type() {
+        let root = loader::load_from_str("type: integer\nenum: [1, 2, 3]").unwrap();
+        let context = engine::Engine::evaluate(&root, "2", false).unwrap();
+        assert!(!context.has_errors());
+        let context = engine::Engine::evaluate(&root, "4", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Value 4 is not in the enum: [1, 2, 3]");
+    }
 }
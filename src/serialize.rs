@@ -0,0 +1,454 @@
+//! Serializing Rust values straight to YAML nodes, see
+//! [`Engine::evaluate_value`](crate::Engine::evaluate_value).
+//!
+//! Struct fields and map entries keep the order they're serialized in. There is no source text,
+//! so every node is marked at the default (zero) position.
+
+use std::borrow::Cow;
+
+use saphyr::AnnotatedMapping;
+use saphyr::MarkedYaml;
+use saphyr::Scalar;
+use saphyr::YamlData;
+use serde::Serialize;
+use serde::ser;
+
+/// Serializes `value` to a YAML node. Enum variants are serialized like `serde_json` does: unit
+/// variants as their name, others as a mapping from their name to their content.
+pub fn to_marked_yaml<T: Serialize + ?Sized>(value: &T) -> crate::Result<MarkedYaml<'static>> {
+    value
+        .serialize(NodeSerializer)
+        .map_err(|e| generic_error!("Failed to serialize the value: {}", e.0))
+}
+
+/// A failure reported by a `Serialize` implementation
+#[derive(Debug)]
+struct SerializeError(String);
+
+impl std::fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        SerializeError(msg.to_string())
+    }
+}
+
+type Node = MarkedYaml<'static>;
+
+fn node(data: YamlData<'static, Node>) -> Node {
+    MarkedYaml {
+        span: Default::default(),
+        data,
+    }
+}
+
+fn scalar(scalar: Scalar<'static>) -> Node {
+    node(YamlData::Value(scalar))
+}
+
+fn string(s: &str) -> Node {
+    scalar(Scalar::String(Cow::Owned(s.to_string())))
+}
+
+/// An integer wider than YAML's 64-bit integers, as a float if it doesn't fit
+fn wide_integer(exact: Option<i64>, approximate: f64) -> Node {
+    match exact {
+        Some(i) => scalar(Scalar::Integer(i)),
+        None => scalar(Scalar::FloatingPoint(approximate.into())),
+    }
+}
+
+/// A mapping with the single entry `variant: value`, for enum variants with content
+fn variant(variant: &'static str, value: Node) -> Node {
+    let mut mapping = AnnotatedMapping::new();
+    mapping.insert(string(variant), value);
+    node(YamlData::Mapping(mapping))
+}
+
+struct NodeSerializer;
+
+impl ser::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, SerializeError> {
+        Ok(scalar(Scalar::Boolean(v)))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Node, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Node, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Node, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Node, SerializeError> {
+        Ok(scalar(Scalar::Integer(v)))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Node, SerializeError> {
+        Ok(wide_integer(i64::try_from(v).ok(), v as f64))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Node, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Node, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Node, SerializeError> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Node, SerializeError> {
+        self.serialize_u128(v.into())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Node, SerializeError> {
+        Ok(wide_integer(i64::try_from(v).ok(), v as f64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Node, SerializeError> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Node, SerializeError> {
+        Ok(scalar(Scalar::FloatingPoint(v.into())))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node, SerializeError> {
+        Ok(string(v.encode_utf8(&mut [0; 4])))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Node, SerializeError> {
+        Ok(string(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Node, SerializeError> {
+        Ok(node(YamlData::Sequence(
+            v.iter()
+                .map(|b| scalar(Scalar::Integer((*b).into())))
+                .collect(),
+        )))
+    }
+
+    fn serialize_none(self) -> Result<Node, SerializeError> {
+        Ok(scalar(Scalar::Null))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Node, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node, SerializeError> {
+        Ok(scalar(Scalar::Null))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Node, SerializeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Node, SerializeError> {
+        Ok(string(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Node, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, SerializeError> {
+        Ok(self::variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, SerializeError> {
+        Ok(SeqSerializer {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer {
+            variant: None,
+            mapping: AnnotatedMapping::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer, SerializeError> {
+        Ok(MapSerializer {
+            variant: Some(variant),
+            mapping: AnnotatedMapping::new(),
+            key: None,
+        })
+    }
+}
+
+struct SeqSerializer {
+    variant: Option<&'static str>,
+    items: Vec<Node>,
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.items.push(value.serialize(NodeSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Node {
+        let sequence = node(YamlData::Sequence(self.items));
+        match self.variant {
+            Some(name) => variant(name, sequence),
+            None => sequence,
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+struct MapSerializer {
+    variant: Option<&'static str>,
+    mapping: AnnotatedMapping<'static, Node>,
+    /// The key of the entry whose value is serialized next
+    key: Option<Node>,
+}
+
+impl MapSerializer {
+    fn insert<T: Serialize + ?Sized>(
+        &mut self,
+        key: Node,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        self.mapping.insert(key, value.serialize(NodeSerializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Node {
+        let mapping = node(YamlData::Mapping(self.mapping));
+        match self.variant {
+            Some(name) => variant(name, mapping),
+            None => mapping,
+        }
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.key = Some(key.serialize(NodeSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert(string(key), value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = Node;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.insert(string(key), value)
+    }
+
+    fn end(self) -> Result<Node, SerializeError> {
+        Ok(self.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::utils::format_const_like;
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i32, i32),
+        Rect { w: u8, h: u8 },
+    }
+
+    fn format<T: Serialize + ?Sized>(value: &T) -> String {
+        format_const_like(&to_marked_yaml(value).unwrap().data)
+    }
+
+    #[test]
+    fn test_to_marked_yaml() {
+        assert_eq!(
+            format(&vec![Shape::Empty, Shape::Circle(1.5)]),
+            r#"["Empty", {"Circle": 1.5}]"#
+        );
+        assert_eq!(format(&Shape::Point(1, -2)), r#"{"Point": [1, -2]}"#);
+        assert_eq!(
+            format(&Shape::Rect { w: 3, h: 2 }),
+            r#"{"Rect": {"w": 3, "h": 2}}"#
+        );
+        assert_eq!(
+            format(&BTreeMap::from([(2, 'b'), (1, 'a')])),
+            r#"{"1": "a", "2": "b"}"#
+        );
+        assert_eq!(
+            format(&(None::<u8>, u64::MAX, -1i128)),
+            "[null, 18446744073709552000, -1]"
+        );
+    }
+}
@@ -95,6 +95,18 @@ pub fn format_yaml_data<'a>(data: &saphyr::YamlData<'a, saphyr::MarkedYaml<'a>>)
     }
 }
 
+/// Formats a value for `const` and `enum` errors. Scalars are formatted as by
+/// [`format_yaml_data`]; sequences and mappings like [`ConstValue`](crate::ConstValue)s, without
+/// source positions.
+pub fn format_const_like<'a>(data: &saphyr::YamlData<'a, saphyr::MarkedYaml<'a>>) -> String {
+    match data {
+        saphyr::YamlData::Value(_) => format_yaml_data(data),
+        _ => crate::ConstValue::try_from(data)
+            .map(|value| value.to_string())
+            .unwrap_or_else(|_| format_yaml_data(data)),
+    }
+}
+
 /// Formats a saphyr::Marker as a string. Displays the line and column as a pair of numbers, separated by a comma.
 pub fn format_marker(marker: &saphyr::Marker) -> String {
     format!("[{}, {}]", marker.line(), marker.col())
@@ -154,6 +166,66 @@ pub fn humanize_yaml_data<'input>(data: &YamlData<'input, MarkedYaml<'input>>) -
     }
 }
 
+/// Reported for a mapping key that is itself a mapping or sequence, e.g. `? [a, b]: value`
+pub(crate) const COMPLEX_KEY_MESSAGE: &str =
+    "Complex mapping keys are not supported by this schema validator";
+
+/// Describes the kind of a YAML node for error messages, e.g. "a sequence" or "a string"
+pub fn describe_yaml_kind<'input>(data: &YamlData<'input, MarkedYaml<'input>>) -> &'static str {
+    match data {
+        YamlData::Value(Scalar::Null) => "null",
+        YamlData::Value(Scalar::Boolean(_)) => "a boolean",
+        YamlData::Value(Scalar::Integer(_)) => "an integer",
+        YamlData::Value(Scalar::FloatingPoint(_)) => "a float",
+        YamlData::Value(Scalar::String(_)) => "a string",
+        YamlData::Sequence(_) => "a sequence",
+        YamlData::Mapping(_) => "a mapping",
+        YamlData::Tagged(_, _) => "a tagged node",
+        _ => "an unsupported node",
+    }
+}
+
+/// Builds a schema loading error for a keyword whose value has the wrong kind of node, e.g.
+/// `[3, 22] 'additionalProperties' expects a boolean or a mapping, but got a string: "false"`.
+///
+/// Common authoring mistakes get a hint appended: a quoted boolean (`"false"`), a bare type name
+/// where a schema is expected (`items: string`), or a single property name where `required`
+/// expects a list.
+pub fn mistyped_keyword_error(
+    keyword: &str,
+    expected: &str,
+    marked_yaml: &MarkedYaml,
+) -> crate::Error {
+    let mut message = format!(
+        "{} '{keyword}' expects {expected}, but got {}: {}",
+        format_marker(&marked_yaml.span.start),
+        describe_yaml_kind(&marked_yaml.data),
+        format_yaml_data(&marked_yaml.data)
+    );
+    if let YamlData::Value(Scalar::String(s)) = &marked_yaml.data {
+        let hint = match s.as_ref() {
+            "true" | "false" if expected.contains("boolean") => Some(format!(
+                "\"{s}\" is a quoted string; write `{keyword}: {s}` without quotes for a boolean"
+            )),
+            "string" | "number" | "integer" | "boolean" | "object" | "array" | "null"
+                if expected.contains("mapping") =>
+            {
+                Some(format!(
+                    "to constrain the type, write `{keyword}: {{ type: {s} }}`"
+                ))
+            }
+            _ if keyword == "required" => Some(format!(
+                "'required' takes a list of property names, e.g. `required: [{s}]`"
+            )),
+            _ => None,
+        };
+        if let Some(hint) = hint {
+            message.push_str(&format!(" (hint: {hint})"));
+        }
+    }
+    crate::Error::SchemaLoadingError(message)
+}
+
 /// Formats a vector of values as a string, by joining them with commas
 pub fn format_vec<V>(vec: &[V]) -> String
 where
@@ -230,6 +302,47 @@ pub fn filter_mapping<'a>(
     Ok(filtered_mapping.into_iter().collect())
 }
 
+/// Whether `path`'s segments match the glob `pattern`'s segments: a `**` segment matches any
+/// number of segments, and within one segment `*` matches any run of characters and `?` any
+/// single character. Used for both directory globs and suppression paths.
+pub(crate) fn glob_matches<P: AsRef<str>, S: AsRef<str>>(pattern: &[P], path: &[S]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first.as_ref() == "**" => {
+            (0..=path.len()).any(|skip| glob_matches(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((segment, path_rest)) => {
+                segment_matches(first.as_ref().as_bytes(), segment.as_ref().as_bytes())
+                    && glob_matches(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn segment_matches(pattern: &[u8], segment: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => segment.is_empty(),
+        Some((b'*', rest)) => {
+            (0..=segment.len()).any(|skip| segment_matches(rest, &segment[skip..]))
+        }
+        Some((b'?', rest)) => match segment.split_first() {
+            // `?` matches one character, i.e. one UTF-8 lead byte and its continuation bytes
+            Some(_) => {
+                let len = segment
+                    .iter()
+                    .skip(1)
+                    .take_while(|b| (**b & 0xC0) == 0x80)
+                    .count();
+                segment_matches(rest, &segment[1 + len..])
+            }
+            None => false,
+        },
+        Some((c, rest)) => segment.first() == Some(c) && segment_matches(rest, &segment[1..]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ordered_float::OrderedFloat;
@@ -355,4 +468,23 @@ mod tests {
             "expected Debug fallback for mapping, got {s:?}"
         );
     }
+
+    #[test]
+    fn test_glob_matches() {
+        let matches = |pattern: &str, path: &str| {
+            let pattern: Vec<&str> = pattern.split('/').collect();
+            let path: Vec<&str> = path.split('/').collect();
+            glob_matches(&pattern, &path)
+        };
+        assert!(matches("**/*.yaml", "a.yaml"));
+        assert!(matches("**/*.yaml", "configs/prod/a.yaml"));
+        assert!(!matches("**/*.yaml", "a.yaml.bak"));
+        assert!(matches("**/generated/**", "configs/generated/x/a.yaml"));
+        assert!(!matches("**/generated/**", "configs/generated.yaml"));
+        assert!(matches("*.y?ml", "a.yaml"));
+        assert!(matches("?.yaml", "é.yaml"));
+        assert!(!matches("*.y?ml", "configs/a.yaml"));
+        assert!(matches("servers/*/port", "servers/0/port"));
+        assert!(!matches("servers/*/port", "servers/0/1/port"));
+    }
 }
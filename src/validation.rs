@@ -3,36 +3,158 @@
 use saphyr::Marker;
 
 use crate::Result;
+use crate::fixes::FixSuggestion;
+use crate::fixes::path_to_pointer;
 
 pub(crate) mod annotations;
+pub(crate) mod content;
 mod context;
+mod extensions;
 pub(crate) mod formats;
 mod objects;
+mod observer;
+mod options;
+mod report;
+mod shared;
 mod strings;
 
+pub use annotations::Annotation;
 pub use annotations::ArrayUnevaluatedAnnotations;
 pub use annotations::ObjectEvaluatedNames;
+pub use context::CheckCounts;
 pub use context::Context;
+pub use context::ErrorCallback;
+#[cfg(test)]
+pub(crate) use context::FRESH_SUB_CONTEXTS;
+pub use context::ObserverRef;
+pub use extensions::Extensions;
+pub use observer::NodeKind;
+pub use observer::ValidationObserver;
+pub use observer::ValidationSummary;
+pub use options::Suppression;
+pub use options::ValidationOptions;
+pub use report::Report;
+pub use shared::Shared;
 
 /// A trait for validating a sahpyr::Yaml value against a schema
 pub trait Validator {
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()>;
 }
 
+/// The severity of a [`ValidationError`]. Only `Error` diagnostics make validation fail.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
 /// A validation error simply contains a path and an error message
 #[derive(Debug)]
 pub struct ValidationError {
-    /// The path to the value that caused the error
+    /// The path to the value that caused the error, its segments separated by "."
     pub path: String,
     /// The line and column of the value that caused the error
     pub marker: Option<Marker>,
     /// The error message
     pub error: String,
+    /// A mechanical fix for the error, when one can be confidently derived
+    pub suggestion: Option<FixSuggestion>,
+    /// Whether this is an error or only a warning
+    pub severity: Severity,
+    /// The failing rule as a one-line schema snippet, the keyword together with its value in
+    /// the schema, e.g. `{minLength: 5}`. Only recorded when
+    /// [`ValidationOptions::schema_fragments`] is enabled.
+    pub schema_fragment: Option<String>,
+    /// The schema keyword that produced the error, e.g. `minLength`, when known. Used as the
+    /// error code by [`Suppression`] rules.
+    pub keyword: Option<String>,
+    /// Whether a [`Suppression`] rule matched this error. Suppressed errors are kept for
+    /// reporting but don't fail validation.
+    pub suppressed: bool,
+    /// The 0-based index of the document of the YAML stream the error is in, when every
+    /// document is validated (see [`ValidationOptions::all_documents`]). Markers are absolute
+    /// within the stream, so line numbers point into the whole file either way.
+    pub document_index: Option<usize>,
+    /// The segments of `path`: the mapping keys and array indices from the document root to the
+    /// value, see [`instance_path`](Self::instance_path)
+    pub path_segments: Vec<String>,
+    /// The keywords leading from the root schema to the rule that produced the error, see
+    /// [`schema_path`](Self::schema_path)
+    pub schema_path_segments: Vec<String>,
+    /// Whether the [`Display`](std::fmt::Display) output includes the schema path, see
+    /// [`ValidationOptions::verbose`]
+    pub show_schema_path: bool,
+}
+
+impl ValidationError {
+    /// The path to the value that caused the error as an RFC 6901 JSON Pointer, e.g.
+    /// `/servers/0/port`. Unlike `path`, keys containing `.` or `/` are unambiguous.
+    pub fn instance_path(&self) -> String {
+        path_to_pointer(&self.path_segments)
+    }
+
+    /// The location of the schema rule that produced the error, as a URI fragment holding a
+    /// JSON Pointer into the root schema, e.g. `#/properties/servers/items/properties/port/maximum`.
+    /// `$ref`s are followed, so after a `$ref` segment the path continues in the referenced schema.
+    pub fn schema_path(&self) -> String {
+        format!("#{}", path_to_pointer(&self.schema_path_segments))
+    }
+
+    /// Whether this diagnostic fails validation: an unsuppressed error, not a warning
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error && !self.suppressed
+    }
+
+    /// This error as a JSON object, see its [`Serialize`](serde::Serialize) implementation
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
 }
 
-/// Display these ValidationErrors as "{path}: {error}"
+/// Serializes as an object with the `path`, `instance_path`, `schema_path`, `message`, `line`
+/// and `column` of the error, plus its `severity` and whether it was `suppressed`. `line` and
+/// `column` are 1-based, as in the [`Display`](std::fmt::Display) output, and `null` for errors
+/// without a location. Errors attributed to a document of a YAML stream also have its 0-based
+/// `document` index.
+impl serde::Serialize for ValidationError {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap as _;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("path", &self.path)?;
+        map.serialize_entry("instance_path", &self.instance_path())?;
+        map.serialize_entry("schema_path", &self.schema_path())?;
+        map.serialize_entry("message", &self.error)?;
+        map.serialize_entry("line", &self.marker.map(|m| m.line()))?;
+        map.serialize_entry("column", &self.marker.map(|m| m.col() + 1))?;
+        map.serialize_entry(
+            "severity",
+            match self.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+        )?;
+        map.serialize_entry("suppressed", &self.suppressed)?;
+        if let Some(document_index) = self.document_index {
+            map.serialize_entry("document", &document_index)?;
+        }
+        map.end()
+    }
+}
+
+/// Display these ValidationErrors as "{path}: {error}", with warnings prefixed by "warning: "
+/// and suppressed errors by "suppressed: ", followed by " (schema: {schema_path})" when verbose
 impl std::fmt::Display for ValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.suppressed {
+            write!(f, "suppressed: ")?;
+        } else if self.severity == Severity::Warning {
+            write!(f, "warning: ")?;
+        }
         if let Some(marker) = &self.marker {
             write!(
                 f,
@@ -41,10 +163,14 @@ impl std::fmt::Display for ValidationError {
                 marker.col() + 1, // contrary to the documentation, columns are 0-indexed
                 self.path,
                 self.error
-            )
+            )?;
         } else {
-            write!(f, ".{}: {}", self.path, self.error)
+            write!(f, ".{}: {}", self.path, self.error)?;
         }
+        if self.show_schema_path {
+            write!(f, " (schema: {})", self.schema_path())?;
+        }
+        Ok(())
     }
 }
 
@@ -78,4 +204,189 @@ mod tests {
         let error = errors.first().unwrap();
         assert_eq!(error.error, r#"Expected null, but got: "value""#);
     }
+
+    #[test]
+    fn test_errors_to_json() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+            "#,
+        )
+        .unwrap();
+        let context = crate::Engine::evaluate(&root_schema, "name: 42", false).unwrap();
+        assert_eq!(
+            context.errors_to_json(),
+            serde_json::json!([{
+                "path": "name",
+                "instance_path": "/name",
+                "schema_path": "#/properties/name/type",
+                "message": "Expected a string, but got: 42 (int)",
+                "line": 1,
+                "column": 7,
+                "severity": "error",
+                "suppressed": false,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_instance_path_of_nested_error() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              servers:
+                type: array
+                items:
+                  type: object
+                  properties:
+                    port:
+                      type: integer
+                    labels:
+                      type: object
+                      additionalProperties:
+                        type: string
+            "#,
+        )
+        .unwrap();
+        let yaml = "servers:\n  - port: 80\n  - port: http\n    labels:\n      a/b~c.d: 1\n";
+        let context = crate::Engine::evaluate(&root_schema, yaml, false).unwrap();
+        let errors = context.errors.borrow();
+        let paths: Vec<(String, String, String)> = errors
+            .iter()
+            .map(|e| (e.instance_path(), e.path.clone(), e.to_string()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    "/servers/1/port".to_string(),
+                    "servers.1.port".to_string(),
+                    "[3:11] .servers.1.port: Expected a number, but got: \"http\" (string)"
+                        .to_string()
+                ),
+                (
+                    "/servers/1/labels/a~1b~0c.d".to_string(),
+                    "servers.1.labels.a/b~c.d".to_string(),
+                    "[5:16] .servers.1.labels.a/b~c.d: Expected a string, but got: 1 (int)"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_path_of_nested_errors() {
+        let root_schema = crate::loader::load_from_str(
+            r##"
+            $defs:
+              port:
+                type: integer
+                maximum: 65535
+            type: object
+            properties:
+              servers:
+                type: array
+                items:
+                  type: object
+                  properties:
+                    port:
+                      $ref: "#/$defs/port"
+                    host:
+                      allOf:
+                        - type: string
+                        - minLength: 3
+              name:
+                anyOf:
+                  - type: string
+                  - type: integer
+            "##,
+        )
+        .unwrap();
+        let yaml = "servers:\n  - port: 70000\n    host: ab\nname: [1]\n";
+        let context = crate::Engine::evaluate(&root_schema, yaml, false).unwrap();
+        let schema_paths: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(ValidationError::schema_path)
+            .collect();
+        assert_eq!(
+            schema_paths,
+            vec![
+                "#/properties/servers/items/properties/port/$ref/maximum",
+                "#/properties/servers/items/properties/host/allOf/1/minLength",
+                "#/properties/name/anyOf",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verbose_display_includes_schema_path() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              port:
+                type: integer
+                maximum: 65535
+            "#,
+        )
+        .unwrap();
+        let options = crate::ValidationOptions::default().verbose(true);
+        let context =
+            crate::Engine::evaluate_with_options(&root_schema, "port: 70000", &options).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(
+            errors[0].to_string(),
+            "[1:7] .port: Number must be less than or equal to 65535 (schema: #/properties/port/maximum)"
+        );
+
+        let context = crate::Engine::evaluate(&root_schema, "port: 70000", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors[0].schema_path(), "#/properties/port/maximum");
+        assert!(!errors[0].to_string().contains("schema:"));
+    }
+
+    /// A custom keyword accepting only the strings registered in the context's extensions
+    struct AllowedEnvironment;
+
+    impl Validator for AllowedEnvironment {
+        fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
+            let Some(allowed) = context
+                .extensions()
+                .get::<std::collections::HashSet<String>>()
+            else {
+                return Ok(());
+            };
+            if let Some(s) = value.data.as_str()
+                && !allowed.contains(s)
+            {
+                context.add_error(value, format!("Unknown environment: {s}"));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_custom_validator_reads_extensions() {
+        let context = Context::default();
+        context
+            .extensions()
+            .insert(std::collections::HashSet::from(["prod".to_string()]));
+        let docs = saphyr::MarkedYaml::load_from_str("prod").unwrap();
+        AllowedEnvironment.validate(&context, &docs[0]).unwrap();
+        assert!(!context.has_errors());
+
+        // Sub-contexts see the values registered on their parent
+        let sub_context = context.get_sub_context();
+        let docs = saphyr::MarkedYaml::load_from_str("staging").unwrap();
+        AllowedEnvironment.validate(&sub_context, &docs[0]).unwrap();
+        let errors = sub_context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Unknown environment: staging");
+    }
 }
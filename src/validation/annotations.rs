@@ -1,13 +1,25 @@
-//! Annotation state for JSON Schema 2020-12 `unevaluatedProperties` / `unevaluatedItems`.
+//! Annotation state for JSON Schema 2020-12 `unevaluatedProperties` / `unevaluatedItems`, and
+//! the annotations collected from the schemas a value validates against.
 
-use std::cell::RefCell;
 use std::collections::HashSet;
-use std::rc::Rc;
+
+use crate::validation::Shared;
+
+/// An annotation keyword, such as `description`, of a schema that the value at `path` validated
+/// against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// The instance path, separated by "."
+    pub path: String,
+    /// The keyword, `title` or `description`
+    pub keyword: String,
+    pub value: String,
+}
 
 /// Successfully evaluated object property names at one instance (for `unevaluatedProperties`).
 #[derive(Debug, Clone, Default)]
 pub struct ObjectEvaluatedNames {
-    pub names: Rc<RefCell<HashSet<String>>>,
+    pub names: Shared<HashSet<String>>,
 }
 
 impl ObjectEvaluatedNames {
@@ -44,8 +56,8 @@ pub struct ArrayUnevaluatedAnnotations {
 }
 
 impl ArrayUnevaluatedAnnotations {
-    pub fn new_shared() -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Self::default()))
+    pub fn new_shared() -> Shared<Self> {
+        Shared::new(Self::default())
     }
 
     /// Indices that must still be validated by `unevaluatedItems` (JSON Schema 2020-12 §11.2).
@@ -0,0 +1,135 @@
+//! Validation of the `contentEncoding` and `contentMediaType` string keywords
+
+/// Validates a string against its `contentEncoding` and `contentMediaType`.
+///
+/// Returns `None` if valid, or `Some((keyword, error_message))` if invalid. Unknown encodings
+/// and media types always pass (annotation-only).
+pub fn validate_content(
+    content_encoding: Option<&str>,
+    content_media_type: Option<&str>,
+    value: &str,
+) -> Option<(&'static str, String)> {
+    let decoded;
+    let content = match content_encoding {
+        Some(encoding) if encoding.eq_ignore_ascii_case("base64") => match decode_base64(value) {
+            Some(bytes) => {
+                decoded = bytes;
+                decoded.as_slice()
+            }
+            None => {
+                return Some((
+                    "contentEncoding",
+                    "String could not be decoded as base64".to_string(),
+                ));
+            }
+        },
+        _ => value.as_bytes(),
+    };
+    // Ignore parameters such as `; charset=utf-8`
+    let media_type = content_media_type?
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim();
+    if media_type.eq_ignore_ascii_case("application/json") {
+        let stage = if content_encoding.is_some_and(|e| e.eq_ignore_ascii_case("base64")) {
+            "Decoded content"
+        } else {
+            "Content"
+        };
+        serde_json::from_slice::<serde_json::Value>(content)
+            .err()
+            .map(|e| {
+                (
+                    "contentMediaType",
+                    format!("{stage} is not valid JSON: {e}"),
+                )
+            })
+    } else {
+        None
+    }
+}
+
+/// Decodes standard (RFC 4648) padded base64, ignoring whitespace such as line breaks
+fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = value.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(digits.len() / 4 * 3);
+    let quads = digits.chunks(4);
+    let last = quads.len().saturating_sub(1);
+    for (i, quad) in quads.enumerate() {
+        let padding = quad.iter().rev().take_while(|&&b| b == b'=').count();
+        if padding > 2 || (padding > 0 && i != last) {
+            return None;
+        }
+        let mut n: u32 = 0;
+        for &b in &quad[..4 - padding] {
+            n = (n << 6) | u32::from(base64_value(b)?);
+        }
+        n <<= 6 * padding as u32;
+        let [_, b0, b1, b2] = n.to_be_bytes();
+        bytes.extend_from_slice(&[b0, b1, b2][..3 - padding]);
+    }
+    Some(bytes)
+}
+
+fn base64_value(b: u8) -> Option<u8> {
+    match b {
+        b'A'..=b'Z' => Some(b - b'A'),
+        b'a'..=b'z' => Some(b - b'a' + 26),
+        b'0'..=b'9' => Some(b - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64(""), Some(vec![]));
+        assert_eq!(decode_base64("aGVsbG8="), Some(b"hello".to_vec()));
+        assert_eq!(decode_base64("aGVs\nbG8h"), Some(b"hello!".to_vec()));
+        assert_eq!(decode_base64("aGk="), Some(b"hi".to_vec()));
+        assert_eq!(decode_base64("aA=="), Some(b"h".to_vec()));
+        assert_eq!(decode_base64("aGVsbG8"), None);
+        assert_eq!(decode_base64("aGk=aGk="), None);
+        assert_eq!(decode_base64("a==="), None);
+        assert_eq!(decode_base64("not base64!"), None);
+    }
+
+    #[test]
+    fn test_validate_content() {
+        assert_eq!(validate_content(Some("base64"), None, "aGVsbG8="), None);
+        assert_eq!(
+            validate_content(Some("base64"), None, "hello!"),
+            Some((
+                "contentEncoding",
+                "String could not be decoded as base64".to_string()
+            ))
+        );
+        // `{"a": 1}`
+        let json = "eyJhIjogMX0=";
+        assert_eq!(
+            validate_content(Some("base64"), Some("application/json"), json),
+            None
+        );
+        assert!(
+            validate_content(Some("base64"), Some("application/json"), "aGVsbG8=")
+                .is_some_and(|(keyword, _)| keyword == "contentMediaType")
+        );
+        assert_eq!(
+            validate_content(None, Some("application/json; charset=utf-8"), "[1, 2]"),
+            None
+        );
+        assert!(validate_content(None, Some("application/json"), "{").is_some());
+        // Unknown encodings and media types are annotations
+        assert_eq!(validate_content(Some("base32"), None, "!!"), None);
+        assert_eq!(validate_content(None, Some("text/html"), "<p"), None);
+    }
+}
@@ -1,16 +1,70 @@
-use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 
+use crate::Error;
 use crate::RootSchema;
 use crate::YamlSchema;
+use crate::directives::YamlVersion;
+use crate::fixes::FixSuggestion;
+use crate::fixes::path_to_pointer;
+use crate::schemas::BooleanOrSchema;
+use crate::validation::Annotation;
 use crate::validation::ArrayUnevaluatedAnnotations;
+use crate::validation::Extensions;
+use crate::validation::NodeKind;
 use crate::validation::ObjectEvaluatedNames;
+use crate::validation::Severity;
+use crate::validation::Shared;
 use crate::validation::ValidationError;
+use crate::validation::ValidationObserver;
+use crate::validation::ValidationOptions;
+
+type ErrorFn = dyn FnMut(&ValidationError) + Send;
+
+/// A callback invoked with each diagnostic as it is recorded, see [`Context::on_error`]
+#[derive(Clone)]
+pub struct ErrorCallback(Arc<Mutex<Box<ErrorFn>>>);
+
+impl std::fmt::Debug for ErrorCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ErrorCallback")
+    }
+}
+
+/// A [`ValidationObserver`] attached to a [`Context`]
+#[derive(Clone, Copy)]
+pub struct ObserverRef<'r>(pub &'r (dyn ValidationObserver + Sync));
+
+impl std::fmt::Debug for ObserverRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ObserverRef")
+    }
+}
+
+/// How much work a validation did, for finding out why a schema is slow, see
+/// [`Context::check_counts`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CheckCounts {
+    /// The number of times a schema was applied to a value, including subschemas
+    pub validators: usize,
+    /// The number of `pattern` and `patternProperties` regular expressions matched
+    pub regex_matches: usize,
+    /// The number of `$ref`s resolved
+    pub refs_resolved: usize,
+}
+
+#[cfg(test)]
+thread_local! {
+    /// The number of [`Context::get_sub_context_fresh_eval`] calls on this thread, so tests can
+    /// check how many branches were actually validated
+    pub(crate) static FRESH_SUB_CONTEXTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
 
 /// The validation context
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Context<'r> {
     /// We use an Option here so tests can be run without a root schema
     pub root_schema: Option<&'r RootSchema>,
@@ -18,43 +72,83 @@ pub struct Context<'r> {
     pub current_path: Vec<String>,
     pub stream_started: bool,
     pub stream_ended: bool,
-    pub errors: Rc<RefCell<Vec<ValidationError>>>,
+    pub errors: Shared<Vec<ValidationError>>,
     pub fail_fast: bool,
+    /// The options the validation was started with, shared by all contexts of a validation.
+    /// `fail_fast` above takes precedence over `options.fail_fast`.
+    pub options: Arc<ValidationOptions>,
+    /// The version declared by the document's `%YAML` directive, if any
+    pub yaml_version: Option<YamlVersion>,
+    /// The index of the document of the YAML stream being validated, recorded on each error,
+    /// see [`Context::child_for_document`]
+    pub document_index: Option<usize>,
     /// Tracks `($ref, value_position)` pairs currently being resolved to detect circular references.
     /// The value position is the byte offset of the YAML value's span start, so the same ref
     /// applied to a nested value is allowed (legitimate recursion) while the same ref
     /// on the same value is detected as a cycle.
-    pub resolving_refs: Rc<RefCell<HashSet<(String, usize)>>>,
+    pub resolving_refs: Shared<HashSet<(String, usize)>>,
     /// Cache of externally loaded schemas by absolute URI (without fragment) or `$id` when valid.
-    pub schemas: Rc<RefCell<HashMap<String, Rc<RootSchema>>>>,
+    pub schemas: Shared<HashMap<String, Arc<RootSchema>>>,
     /// Property names successfully evaluated for JSON Schema `unevaluatedProperties` (same instance).
     pub object_evaluated: Option<ObjectEvaluatedNames>,
     /// Array annotation state for JSON Schema `unevaluatedItems` (same instance).
-    pub array_unevaluated: Option<Rc<RefCell<ArrayUnevaluatedAnnotations>>>,
+    pub array_unevaluated: Option<Shared<ArrayUnevaluatedAnnotations>>,
+    /// The annotations of the schemas the value validated against, when collecting them, see
+    /// [`ValidationOptions::collect_annotations`](crate::ValidationOptions::collect_annotations)
+    pub annotations: Option<Shared<Vec<Annotation>>>,
+    /// The keywords (and property names, indices, ...) leading from the root schema to the
+    /// schema being validated against, see [`with_schema_segment`](Self::with_schema_segment).
+    /// Shared by all contexts of a validation.
+    pub schema_path: Shared<Vec<String>>,
+    /// State shared with custom validators, see [`extensions`](Self::extensions). Shared by all
+    /// contexts of a validation.
+    pub extension_values: Extensions,
+    /// The checks performed so far, when counting them, see
+    /// [`ValidationOptions::count_checks`](crate::ValidationOptions::count_checks). Shared by all
+    /// contexts of a validation.
+    pub check_counts: Option<Shared<CheckCounts>>,
+    /// Invoked for each diagnostic recorded in `errors`. Not shared with sub-contexts, whose
+    /// errors only count once they're merged back.
+    pub on_error: Option<ErrorCallback>,
+    /// Notified of nodes and errors; like `on_error`, not shared with sub-contexts
+    pub observer: Option<ObserverRef<'r>>,
 }
 
-impl Default for Context<'_> {
-    fn default() -> Self {
-        Self {
-            root_schema: None,
-            current_schema: None,
-            current_path: Vec::new(),
-            stream_started: false,
-            stream_ended: false,
-            errors: Rc::new(RefCell::new(Vec::new())),
-            fail_fast: false,
-            resolving_refs: Rc::new(RefCell::new(HashSet::new())),
-            schemas: Rc::new(RefCell::new(HashMap::new())),
-            object_evaluated: None,
-            array_unevaluated: None,
+impl<'r> Context<'r> {
+    /// Returns true if there are any errors in the context. Warnings are not counted.
+    pub fn has_errors(&self) -> bool {
+        self.errors.borrow().iter().any(ValidationError::is_error)
+    }
+
+    /// The number of errors in the context, not counting warnings and suppressed errors. Compare
+    /// it before and after validating something to tell whether that failed.
+    pub fn error_count(&self) -> usize {
+        self.errors.borrow().iter().filter(|e| e.is_error()).count()
+    }
+
+    /// Converts the outcome of a validation into a `Result`, returning
+    /// [`Error::ValidationFailed`] with every error-severity diagnostic if there are any.
+    pub fn into_result(self) -> crate::Result<()> {
+        let errors: Vec<ValidationError> = std::mem::take(&mut *self.errors.borrow_mut())
+            .into_iter()
+            .filter(ValidationError::is_error)
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ValidationFailed(errors))
         }
     }
-}
 
-impl<'r> Context<'r> {
-    /// Returns true if there are any errors in the context
-    pub fn has_errors(&self) -> bool {
-        !self.errors.borrow().is_empty()
+    /// All recorded errors and warnings as a JSON array, see [`ValidationError::to_json`]
+    pub fn errors_to_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(
+            self.errors
+                .borrow()
+                .iter()
+                .map(ValidationError::to_json)
+                .collect(),
+        )
     }
 
     /// Returns the current path as a string separated by "."
@@ -76,29 +170,76 @@ impl<'r> Context<'r> {
             current_path: self.current_path.clone(),
             stream_started: self.stream_started,
             stream_ended: self.stream_ended,
-            errors: Rc::new(RefCell::new(Vec::new())),
+            errors: Shared::default(),
             fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: self.document_index,
             resolving_refs: self.resolving_refs.clone(),
             schemas: self.schemas.clone(),
             object_evaluated: self.object_evaluated.clone(),
             array_unevaluated: self.array_unevaluated.clone(),
+            annotations: self.annotations.as_ref().map(|_| Shared::default()),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: None,
+            observer: None,
         }
     }
 
     /// Like [`get_sub_context`], but with fresh unevaluated annotation carriers (for `anyOf` / `oneOf` branches).
     pub fn get_sub_context_fresh_eval(&self) -> Context<'r> {
+        #[cfg(test)]
+        FRESH_SUB_CONTEXTS.with(|n| n.set(n.get() + 1));
         Context {
             root_schema: self.root_schema,
             current_schema: self.current_schema,
             current_path: self.current_path.clone(),
             stream_started: self.stream_started,
             stream_ended: self.stream_ended,
-            errors: Rc::new(RefCell::new(Vec::new())),
+            errors: Shared::default(),
             fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: self.document_index,
             resolving_refs: self.resolving_refs.clone(),
             schemas: self.schemas.clone(),
             object_evaluated: Some(ObjectEvaluatedNames::new()),
             array_unevaluated: Some(ArrayUnevaluatedAnnotations::new_shared()),
+            annotations: self.annotations.as_ref().map(|_| Shared::default()),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: None,
+            observer: None,
+        }
+    }
+
+    /// A context for validating the document at `index` of a YAML stream, which records its
+    /// errors in this context's `errors`, tagged with the index
+    pub fn child_for_document(&self, index: usize) -> Context<'r> {
+        Context {
+            root_schema: self.root_schema,
+            current_schema: self.current_schema,
+            current_path: Vec::new(),
+            stream_started: self.stream_started,
+            stream_ended: self.stream_ended,
+            errors: self.errors.clone(),
+            fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: Some(index),
+            resolving_refs: self.resolving_refs.clone(),
+            schemas: self.schemas.clone(),
+            object_evaluated: None,
+            array_unevaluated: None,
+            annotations: self.annotations.clone(),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: self.on_error.clone(),
+            observer: self.observer,
         }
     }
 
@@ -114,44 +255,334 @@ impl<'r> Context<'r> {
     pub fn with_root_schema_and_schemas(
         root_schema: &'r RootSchema,
         fail_fast: bool,
-        schemas: HashMap<String, Rc<RootSchema>>,
+        schemas: HashMap<String, Arc<RootSchema>>,
     ) -> Context<'r> {
         Context {
             root_schema: Some(root_schema),
             fail_fast,
-            schemas: Rc::new(RefCell::new(schemas)),
+            schemas: Shared::new(schemas),
             ..Default::default()
         }
     }
 
-    fn push_error(&self, error: ValidationError) {
+    /// Calls `callback` with each error and warning as soon as it is recorded, in addition to
+    /// collecting it in `errors`. Useful for reporting progress on large documents. The callback
+    /// must be `Send`, like the context.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::Mutex;
+    ///
+    /// use saphyr::LoadableYamlNode;
+    /// use yaml_schema::Context;
+    /// use yaml_schema::Validator;
+    ///
+    /// let root_schema = yaml_schema::loader::load_from_str("type: integer").unwrap();
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = seen.clone();
+    /// let context = Context::with_root_schema(&root_schema, false)
+    ///     .on_error(move |e| sink.lock().unwrap().push(e.error.clone()));
+    /// let docs = saphyr::MarkedYaml::load_from_str("foo").unwrap();
+    /// root_schema.validate(&context, &docs[0]).unwrap();
+    /// assert_eq!(seen.lock().unwrap().len(), 1);
+    /// ```
+    pub fn on_error(
+        mut self,
+        callback: impl FnMut(&ValidationError) + Send + 'static,
+    ) -> Context<'r> {
+        self.on_error = Some(ErrorCallback(Arc::new(Mutex::new(Box::new(callback)))));
+        self
+    }
+
+    /// Values keyed by their type for custom [`Validator`](crate::Validator)s to share state
+    /// without globals. All sub-contexts share the same values. Unrelated to the
+    /// [`ValidationOptions::x_extensions`] flag for `x-` keywords.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    ///
+    /// use yaml_schema::Context;
+    ///
+    /// let context = Context::new(false);
+    /// context.extensions().insert(HashSet::from(["prod".to_string()]));
+    /// let sub_context = context.get_sub_context();
+    /// let allowed = sub_context.extensions().get::<HashSet<String>>().unwrap();
+    /// assert!(allowed.contains("prod"));
+    /// ```
+    pub fn extensions(&self) -> &Extensions {
+        &self.extension_values
+    }
+
+    /// The checks performed so far, or `None` unless counting was enabled with
+    /// [`ValidationOptions::count_checks`](crate::ValidationOptions::count_checks)
+    pub fn check_counts(&self) -> Option<CheckCounts> {
+        self.check_counts.as_ref().map(|counts| *counts.borrow())
+    }
+
+    /// Records a check in the counts, if they're kept
+    pub(crate) fn count_check(&self, count: impl FnOnce(&mut CheckCounts)) {
+        if let Some(counts) = &self.check_counts {
+            count(&mut counts.borrow_mut());
+        }
+    }
+
+    fn notify(&self, error: &ValidationError) {
+        if let Some(ErrorCallback(callback)) = &self.on_error {
+            (callback.lock().unwrap_or_else(PoisonError::into_inner))(error);
+        }
+        if let Some(ObserverRef(observer)) = self.observer {
+            observer.on_error(error);
+        }
+    }
+
+    /// Reports a node about to be validated to the observer: the child `key` (a mapping key or
+    /// array index) of the current value, or with no key, the current value itself
+    pub fn observe_node(&self, key: Option<&str>, node: &saphyr::MarkedYaml) {
+        if let Some(ObserverRef(observer)) = self.observer {
+            let path = match key {
+                Some(key) if !self.current_path.is_empty() => format!("{}.{key}", self.path()),
+                Some(key) => key.to_string(),
+                None => self.path(),
+            };
+            observer.on_node(&path, NodeKind::of(node));
+        }
+    }
+
+    fn push_error(&self, mut error: ValidationError) {
+        error.suppressed = self
+            .options
+            .suppressions
+            .iter()
+            .any(|rule| rule.matches(error.keyword.as_deref(), &self.current_path));
+        if self.document_index.is_some() {
+            error.document_index = self.document_index;
+        }
+        error.schema_path_segments = self.schema_path.borrow().clone();
+        if let Some(keyword) = &error.keyword {
+            error.schema_path_segments.push(keyword.clone());
+        }
+        error.show_schema_path = self.options.verbose;
+        self.notify(&error);
         self.errors.borrow_mut().push(error);
     }
 
+    /// A diagnostic for the value at the current path, before [`push_error`](Self::push_error)
+    /// fills in what the context knows
+    fn new_error(
+        &self,
+        marked_yaml: Option<&saphyr::MarkedYaml>,
+        error: String,
+        severity: Severity,
+    ) -> ValidationError {
+        ValidationError {
+            path: self.path(),
+            marker: marked_yaml.map(|marked_yaml| marked_yaml.span.start),
+            error,
+            suggestion: None,
+            severity,
+            schema_fragment: None,
+            keyword: None,
+            suppressed: false,
+            document_index: None,
+            path_segments: self.current_path.clone(),
+            schema_path_segments: Vec::new(),
+            show_schema_path: false,
+        }
+    }
+
     pub fn add_doc_error<V: Into<String>>(&self, error: V) {
-        let path = self.path();
-        self.push_error(ValidationError {
-            path,
-            marker: None,
-            error: error.into(),
-        });
+        self.push_error(self.new_error(None, error.into(), Severity::Error));
     }
 
     /// Adds an error message to the current context, with the current path and with location marker
     pub fn add_error<V: Into<String>>(&self, marked_yaml: &saphyr::MarkedYaml, error: V) {
-        let path = self.path();
+        self.push_error(self.new_error(Some(marked_yaml), error.into(), Severity::Error));
+    }
+
+    /// Adds an error for a value that doesn't match the schema's `type`, at the schema path of
+    /// the `type` keyword
+    pub(crate) fn add_type_error<V: Into<String>>(
+        &self,
+        marked_yaml: &saphyr::MarkedYaml,
+        error: V,
+    ) {
+        self.with_schema_segment(&["type"], || self.add_error(marked_yaml, error));
+    }
+
+    /// Like [`add_error`](Self::add_error), but attaches a fix suggestion to the error
+    pub fn add_error_with_suggestion<V: Into<String>>(
+        &self,
+        marked_yaml: &saphyr::MarkedYaml,
+        error: V,
+        suggestion: FixSuggestion,
+    ) {
+        self.push_error(ValidationError {
+            suggestion: Some(suggestion),
+            ..self.new_error(Some(marked_yaml), error.into(), Severity::Error)
+        });
+    }
+
+    /// Adds an error produced by a single schema keyword. When schema fragments are enabled, the
+    /// error records the keyword and its value, e.g. `{minLength: 5}`.
+    pub fn add_keyword_error<V: Into<String>>(
+        &self,
+        marked_yaml: &saphyr::MarkedYaml,
+        error: V,
+        keyword: &str,
+        keyword_value: impl std::fmt::Display,
+    ) {
+        self.add_keyword_error_with_suggestion(marked_yaml, error, keyword, keyword_value, None);
+    }
+
+    /// Like [`add_keyword_error`](Self::add_keyword_error), but attaches a fix suggestion
+    pub fn add_keyword_error_with_suggestion<V: Into<String>>(
+        &self,
+        marked_yaml: &saphyr::MarkedYaml,
+        error: V,
+        keyword: &str,
+        keyword_value: impl std::fmt::Display,
+        suggestion: Option<FixSuggestion>,
+    ) {
         self.push_error(ValidationError {
-            path,
-            marker: Some(marked_yaml.span.start),
-            error: error.into(),
+            suggestion,
+            schema_fragment: self.keyword_fragment(keyword, keyword_value),
+            keyword: Some(keyword.to_string()),
+            ..self.new_error(Some(marked_yaml), error.into(), Severity::Error)
         });
     }
 
+    fn keyword_fragment(&self, keyword: &str, value: impl std::fmt::Display) -> Option<String> {
+        self.options
+            .schema_fragments
+            .then(|| format!("{{{keyword}: {value}}}"))
+    }
+
+    /// Adds a warning to the current context. Warnings are reported but don't fail validation.
+    pub fn add_warning<V: Into<String>>(&self, marked_yaml: &saphyr::MarkedYaml, warning: V) {
+        self.push_error(self.new_error(Some(marked_yaml), warning.into(), Severity::Warning));
+    }
+
+    /// Decides whether a branch of a combinator (`anyOf`, `oneOf`, `not`, `if`, ...) matched,
+    /// given the `result` of validating `value` against it in `sub_context`.
+    ///
+    /// Errors in the sub-context, a fail-fast signal, and any non-internal error (such as an
+    /// unresolvable `$ref`) all make the branch fail; the latter is also recorded as a warning.
+    /// Internal errors (see [`Error::is_internal`]) are propagated. The annotations of a matching
+    /// branch, other than of `not`, are added to this context's, so that every matching `anyOf`
+    /// branch contributes.
+    pub fn branch_matched(
+        &self,
+        keyword: &str,
+        value: &saphyr::MarkedYaml,
+        sub_context: &Context,
+        result: crate::Result<()>,
+    ) -> crate::Result<bool> {
+        match result {
+            Ok(()) | Err(Error::FailFast) => {
+                let matched = !sub_context.has_errors();
+                if matched && keyword != "not" {
+                    self.extend_annotations(sub_context);
+                }
+                Ok(matched)
+            }
+            Err(e) if e.is_internal() => Err(e),
+            Err(e) => {
+                self.add_warning(
+                    value,
+                    format!("A branch of `{keyword}` could not be evaluated: {e}"),
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// The root schema's `additionalProperties`, which applies to objects without their own when
+    /// [`ValidationOptions::recursive_additional_properties`] is on
+    pub fn inherited_additional_properties(&self) -> Option<&'r BooleanOrSchema> {
+        if !self.options.recursive_additional_properties {
+            return None;
+        }
+        match &self.root_schema?.schema {
+            YamlSchema::Subschema(subschema) => subschema
+                .object_schema
+                .as_ref()?
+                .additional_properties
+                .as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Runs `f` with `segment` appended to the schema path recorded on errors, e.g.
+    /// `["properties", "port"]` before validating a property against its schema. Errors added
+    /// with a keyword, such as by [`add_keyword_error`](Self::add_keyword_error), append it to
+    /// the schema path themselves.
+    ///
+    /// ```
+    /// use saphyr::LoadableYamlNode;
+    /// use yaml_schema::Context;
+    ///
+    /// let context = Context::default();
+    /// let docs = saphyr::MarkedYaml::load_from_str("80").unwrap();
+    /// context.with_schema_segment(&["properties", "port"], || {
+    ///     context.add_keyword_error(&docs[0], "Too small", "minimum", 1024)
+    /// });
+    /// assert_eq!(
+    ///     context.errors.borrow()[0].schema_path(),
+    ///     "#/properties/port/minimum"
+    /// );
+    /// ```
+    pub fn with_schema_segment<S: AsRef<str>, T>(&self, segment: &[S], f: impl FnOnce() -> T) -> T {
+        let len = self.schema_path.borrow().len();
+        self.schema_path
+            .borrow_mut()
+            .extend(segment.iter().map(|s| s.as_ref().to_string()));
+        let result = f();
+        self.schema_path.borrow_mut().truncate(len);
+        result
+    }
+
+    /// Returns the current path as a JSON Pointer, e.g. `/server/port`
+    pub fn pointer(&self) -> String {
+        path_to_pointer(&self.current_path)
+    }
+
     /// Appends all the errors to the current context
     pub fn extend_errors(&self, errors: Vec<ValidationError>) {
+        for error in &errors {
+            self.notify(error);
+        }
         self.errors.borrow_mut().extend(errors);
     }
 
+    /// Records an annotation of the schema being validated against at the current path, when
+    /// collecting annotations
+    pub fn add_annotation<V: Into<String>>(&self, keyword: &str, value: V) {
+        if let Some(annotations) = &self.annotations {
+            annotations.borrow_mut().push(Annotation {
+                path: self.path(),
+                keyword: keyword.to_string(),
+                value: value.into(),
+            });
+        }
+    }
+
+    /// Appends the annotations collected in `sub_context` to this context's
+    pub fn extend_annotations(&self, sub_context: &Context) {
+        if let (Some(annotations), Some(sub_annotations)) =
+            (&self.annotations, &sub_context.annotations)
+            && !Shared::ptr_eq(annotations, sub_annotations)
+        {
+            let collected = sub_annotations.borrow().clone();
+            annotations.borrow_mut().extend(collected);
+        }
+    }
+
+    /// Append an array index to the current path, e.g. so the third item's errors are reported
+    /// at `items.2` (`/items/2`)
+    pub fn append_index(&self, index: usize) -> Context<'r> {
+        self.append_path(index.to_string())
+    }
+
     /// Append a path to the current path
     pub fn append_path<V: Into<String>>(&self, path: V) -> Context<'r> {
         let mut new_path = self.current_path.clone();
@@ -162,12 +593,51 @@ impl<'r> Context<'r> {
             current_path: new_path,
             errors: self.errors.clone(),
             fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: self.document_index,
             stream_ended: self.stream_ended,
             stream_started: self.stream_started,
             resolving_refs: self.resolving_refs.clone(),
             schemas: self.schemas.clone(),
             object_evaluated: None,
             array_unevaluated: None,
+            annotations: self.annotations.clone(),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: self.on_error.clone(),
+            observer: self.observer,
+        }
+    }
+
+    /// A context that shares errors, caches and annotations with this one, but resolves local
+    /// `$ref`s against another root schema (e.g. an externally loaded schema document).
+    pub fn with_root<'x>(&self, root_schema: &'x RootSchema) -> Context<'x>
+    where
+        'r: 'x,
+    {
+        Context {
+            root_schema: Some(root_schema),
+            current_schema: self.current_schema,
+            current_path: self.current_path.clone(),
+            stream_started: self.stream_started,
+            stream_ended: self.stream_ended,
+            errors: self.errors.clone(),
+            fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: self.document_index,
+            resolving_refs: self.resolving_refs.clone(),
+            schemas: self.schemas.clone(),
+            object_evaluated: self.object_evaluated.clone(),
+            array_unevaluated: self.array_unevaluated.clone(),
+            annotations: self.annotations.clone(),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: self.on_error.clone(),
+            observer: self.observer,
         }
     }
 
@@ -190,16 +660,25 @@ impl<'r> Context<'r> {
             stream_ended: self.stream_ended,
             errors: self.errors.clone(),
             fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: self.document_index,
             resolving_refs: self.resolving_refs.clone(),
             schemas: self.schemas.clone(),
             object_evaluated,
             array_unevaluated: self.array_unevaluated.clone(),
+            annotations: self.annotations.clone(),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: self.on_error.clone(),
+            observer: self.observer,
         }
     }
 
     pub fn with_array_unevaluated(
         &self,
-        array_unevaluated: Option<Rc<RefCell<ArrayUnevaluatedAnnotations>>>,
+        array_unevaluated: Option<Shared<ArrayUnevaluatedAnnotations>>,
     ) -> Context<'r> {
         Context {
             root_schema: self.root_schema,
@@ -209,10 +688,19 @@ impl<'r> Context<'r> {
             stream_ended: self.stream_ended,
             errors: self.errors.clone(),
             fail_fast: self.fail_fast,
+            options: self.options.clone(),
+            yaml_version: self.yaml_version,
+            document_index: self.document_index,
             resolving_refs: self.resolving_refs.clone(),
             schemas: self.schemas.clone(),
             object_evaluated: self.object_evaluated.clone(),
             array_unevaluated,
+            annotations: self.annotations.clone(),
+            schema_path: self.schema_path.clone(),
+            extension_values: self.extension_values.clone(),
+            check_counts: self.check_counts.clone(),
+            on_error: self.on_error.clone(),
+            observer: self.observer,
         }
     }
 
@@ -225,6 +713,7 @@ impl<'r> Context<'r> {
 
     /// Mark a `(ref, value_position)` pair as currently being resolved.
     pub fn begin_resolving_ref(&self, ref_name: &str, value: &saphyr::MarkedYaml) {
+        self.count_check(|counts| counts.refs_resolved += 1);
         let key = (ref_name.to_string(), value.span.start.index());
         self.resolving_refs.borrow_mut().insert(key);
     }
@@ -0,0 +1,66 @@
+//! A type map for sharing state with custom validators, see [`Context::extensions`]
+//!
+//! [`Context::extensions`]: crate::Context::extensions
+
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::validation::Shared;
+
+type ExtensionMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+/// Values keyed by their type, shared by a [`Context`](crate::Context) and all of its
+/// sub-contexts. Cloning shares the same map.
+///
+/// Values must be `Send + Sync`, so state registered here can also be shared with other threads
+/// through the `Arc` returned by [`get`](Self::get). The map isn't created until the first
+/// [`insert`](Self::insert).
+#[derive(Clone, Default)]
+pub struct Extensions(Shared<Option<ExtensionMap>>);
+
+impl Extensions {
+    /// Stores `value`, returning the value of the same type it replaces, if any
+    pub fn insert<T: Any + Send + Sync>(&self, value: T) -> Option<Arc<T>> {
+        self.0
+            .borrow_mut()
+            .get_or_insert_with(HashMap::new)
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(|previous| previous.downcast().ok())
+    }
+
+    /// The value of type `T`, if one was inserted
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.0
+            .borrow()
+            .as_ref()?
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(|value| value.downcast().ok())
+    }
+
+    /// Removes and returns the value of type `T`, if any
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.0
+            .borrow_mut()
+            .as_mut()?
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+    }
+
+    /// Whether a value of type `T` was inserted
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.0
+            .borrow()
+            .as_ref()
+            .is_some_and(|map| map.contains_key(&TypeId::of::<T>()))
+    }
+}
+
+impl std::fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let len = self.0.borrow().as_ref().map_or(0, HashMap::len);
+        f.debug_struct("Extensions").field("len", &len).finish()
+    }
+}
@@ -12,6 +12,11 @@ use crate::schemas::StringFormat;
 /// Returns `None` if valid, or `Some(error_message)` if invalid.
 /// Unknown formats always pass (annotation-only).
 pub fn validate_format(format: &StringFormat, value: &str) -> Option<String> {
+    if *format == StringFormat::Json {
+        return serde_json::from_str::<serde_json::Value>(value)
+            .err()
+            .map(|e| format!("String is not valid JSON: {e}"));
+    }
     let valid = match format {
         StringFormat::DateTime => is_valid_date_time(value),
         StringFormat::Date => is_valid_date(value),
@@ -32,7 +37,7 @@ pub fn validate_format(format: &StringFormat, value: &str) -> Option<String> {
         StringFormat::JsonPointer => is_valid_json_pointer(value),
         StringFormat::RelativeJsonPointer => is_valid_relative_json_pointer(value),
         StringFormat::Regex => is_valid_regex(value),
-        StringFormat::Unknown(_) => true,
+        StringFormat::Json | StringFormat::Unknown(_) => true,
     };
 
     if valid {
@@ -172,17 +177,28 @@ fn is_valid_ipv6(value: &str) -> bool {
 
 // --- URI / URI-reference ---
 
+/// Characters that RFC 3986 never allows to appear literally in a URI. The `url` crate is
+/// lenient and percent-encodes some of these (e.g. spaces in paths), so reject them up front.
+fn has_disallowed_uri_chars(value: &str) -> bool {
+    value.chars().any(|c| {
+        c.is_whitespace()
+            || c.is_control()
+            || matches!(c, '\\' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`')
+    })
+}
+
+/// `uri`: an absolute URI, i.e. one with a scheme (RFC 3986 section 4.3)
 fn is_valid_uri(value: &str) -> bool {
-    url::Url::parse(value).is_ok()
+    !has_disallowed_uri_chars(value) && url::Url::parse(value).is_ok()
 }
 
+/// `uri-reference`: either an absolute URI or a relative reference
 fn is_valid_uri_reference(value: &str) -> bool {
     if is_valid_uri(value) {
         return true;
     }
-    // A relative reference: must not contain spaces, and either starts with
-    // a path segment, query, or fragment.
-    !value.contains(' ') && !value.is_empty()
+    // A relative reference: a path segment, query, or fragment without disallowed characters
+    !value.is_empty() && !has_disallowed_uri_chars(value)
 }
 
 // --- UUID (RFC 4122) ---
@@ -433,6 +449,17 @@ mod tests {
     fn test_invalid_uris() {
         assert!(!is_valid_uri("not a uri"));
         assert!(!is_valid_uri("://missing-scheme"));
+        assert!(!is_valid_uri("https://example.com/a b"));
+        assert!(!is_valid_uri("https://example.com/<tag>"));
+        assert!(!is_valid_uri("http://[::1"));
+    }
+
+    #[test]
+    fn test_uri_requires_scheme_but_uri_reference_does_not() {
+        assert!(!is_valid_uri("/path"));
+        assert!(is_valid_uri_reference("/path"));
+        assert!(is_valid_uri("https://a/b"));
+        assert!(is_valid_uri_reference("https://a/b"));
     }
 
     // --- uri-reference ---
@@ -548,6 +575,24 @@ mod tests {
         assert!(err.as_ref().is_some_and(|e| e.contains("date")));
     }
 
+    #[test]
+    fn test_validate_format_json() {
+        for value in [r#"{"a": [1, 2.5, null]}"#, "[]", "42", r#""text""#] {
+            assert_eq!(validate_format(&StringFormat::Json, value), None, "{value}");
+        }
+        assert_eq!(
+            validate_format(&StringFormat::Json, r#"{"a": 1,}"#),
+            Some("String is not valid JSON: trailing comma at line 1 column 9".to_string())
+        );
+        assert_eq!(
+            validate_format(&StringFormat::Json, ""),
+            Some(
+                "String is not valid JSON: EOF while parsing a value at line 1 column 0"
+                    .to_string()
+            )
+        );
+    }
+
     #[test]
     fn test_validate_format_unknown_always_passes() {
         let fmt = StringFormat::Unknown("my-custom-format".to_string());
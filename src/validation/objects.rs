@@ -7,13 +7,21 @@ use log::debug;
 use saphyr::Scalar;
 use saphyr::YamlData;
 
+use crate::ConstValue;
+use crate::Error;
 use crate::Result;
 use crate::Validator;
 use crate::YamlSchema;
+use crate::fixes::FixSuggestion;
+use crate::fixes::path_to_pointer;
 use crate::schemas::BooleanOrSchema;
 use crate::schemas::ObjectSchema;
-use crate::utils::{format_marker, format_yaml_data, scalar_to_string};
+use crate::utils::COMPLEX_KEY_MESSAGE;
+use crate::utils::{
+    format_const_like, format_marker, format_vec, format_yaml_data, scalar_to_string,
+};
 use crate::validation::Context;
+use crate::validation::ValidationError;
 
 impl Validator for ObjectSchema {
     /// Validate the object according to the schema rules
@@ -23,12 +31,9 @@ impl Validator for ObjectSchema {
         if let saphyr::YamlData::Mapping(mapping) = data {
             self.validate_object_mapping(context, value, mapping)
         } else {
-            let error_message = format!(
-                "[ObjectSchema] {} Expected an object, but got: {data:?}",
-                format_marker(&value.span.start)
-            );
+            let error_message = format!("Expected an object, but got: {}", format_const_like(data));
             debug!("{error_message}");
-            context.add_error(value, error_message);
+            context.add_type_error(value, error_message);
             Ok(())
         }
     }
@@ -43,11 +48,13 @@ pub fn try_validate_value_against_properties(
     let sub_context = context.append_path(key);
     if let Some(schema) = properties.get(key) {
         debug!("Validating property '{key}' with schema: {schema}");
-        let err_before = context.errors.borrow().len();
-        let result = schema.validate(&sub_context, value);
+        let err_before = context.error_count();
+        let result = context.with_schema_segment(&["properties", key], || {
+            schema.validate(&sub_context, value)
+        });
         return match result {
             Ok(()) => {
-                if context.errors.borrow().len() == err_before {
+                if context.error_count() == err_before {
                     context.record_evaluated_property(key);
                 }
                 Ok(true)
@@ -74,16 +81,20 @@ pub fn try_validate_value_against_additional_properties(
         BooleanOrSchema::Boolean(true) => { /* noop */ }
         // if additional_properties: false, then no additional properties are allowed
         BooleanOrSchema::Boolean(false) => {
-            context.add_error(
+            context.add_keyword_error(
                 value,
                 format!("Additional property '{key}' is not allowed!"),
+                "additionalProperties",
+                false,
             );
             // returning `false` signals fail fast
             return Ok(false);
         }
         // if additional_properties: a schema, then validate against it
         BooleanOrSchema::Schema(schema) => {
-            schema.validate(&sub_context, value)?;
+            context.with_schema_segment(&["additionalProperties"], || {
+                schema.validate(&sub_context, value)
+            })?;
         }
     }
     Ok(true)
@@ -96,15 +107,20 @@ impl ObjectSchema {
         object: &saphyr::MarkedYaml,
         mapping: &saphyr::AnnotatedMapping<'r, saphyr::MarkedYaml<'r>>,
     ) -> Result<()> {
+        let mut pattern_match_counts = vec![
+            0usize;
+            self.pattern_properties
+                .as_ref()
+                .map_or(0, |pattern_properties| pattern_properties
+                    .len())
+        ];
         for (k, value) in mapping {
             let key_string = match &k.data {
                 saphyr::YamlData::Value(scalar) => scalar_to_string(scalar),
-                v => {
-                    return Err(expected_scalar!(
-                        "[{}] Expected a scalar key, got: {:?}",
-                        format_marker(&k.span.start),
-                        v
-                    ));
+                _ => {
+                    context.add_error(k, COMPLEX_KEY_MESSAGE);
+                    fail_fast!(context);
+                    continue;
                 }
             };
             let span = &k.span;
@@ -123,6 +139,7 @@ impl ObjectSchema {
             if key_string == "$schema" {
                 continue;
             }
+            context.observe_node(Some(&key_string), value);
 
             // `properties` and `patternProperties` both apply when they match (JSON Schema 2020-12).
             let covered_by_properties = if let Some(properties) = &self.properties {
@@ -134,16 +151,20 @@ impl ObjectSchema {
             let mut matched_pattern_property = false;
             if let Some(pattern_properties) = &self.pattern_properties {
                 let pattern_context = context.append_path(&key_string);
-                let err_before_patterns = context.errors.borrow().len();
-                for pp in pattern_properties {
+                let err_before_patterns = context.error_count();
+                for (pp, count) in pattern_properties.iter().zip(&mut pattern_match_counts) {
                     log::debug!("pattern: {}", pp.regex.as_str());
+                    context.count_check(|counts| counts.regex_matches += 1);
                     if pp.regex.is_match(key_string.as_ref()) {
                         matched_pattern_property = true;
-                        pp.schema.validate(&pattern_context, value)?;
+                        *count += 1;
+                        context.with_schema_segment(
+                            &["patternProperties", pp.regex.as_str()],
+                            || pp.schema.validate(&pattern_context, value),
+                        )?;
                     }
                 }
-                if matched_pattern_property && context.errors.borrow().len() == err_before_patterns
-                {
+                if matched_pattern_property && context.error_count() == err_before_patterns {
                     context.record_evaluated_property(&key_string);
                 }
             }
@@ -152,21 +173,32 @@ impl ObjectSchema {
             // no `patternProperties` regex (JSON Schema 2020-12).
             if !covered_by_properties
                 && !matched_pattern_property
-                && let Some(additional_properties) = &self.additional_properties
+                && let Some(additional_properties) = self
+                    .additional_properties
+                    .as_ref()
+                    .or_else(|| context.inherited_additional_properties())
             {
-                let err_before_add = context.errors.borrow().len();
+                let err_before_add = context.error_count();
                 try_validate_value_against_additional_properties(
                     context,
                     &key_string,
                     value,
                     additional_properties,
                 )?;
-                if context.errors.borrow().len() == err_before_add {
+                if context.error_count() == err_before_add {
                     context.record_evaluated_property(&key_string);
                 }
             }
             // propertyNames: validate each mapping key against the subschema.
-            if let Some(property_names) = &self.property_names {
+            if let Some(YamlSchema::BooleanLiteral(false)) = &self.property_names {
+                context.append_path(&key_string).add_keyword_error(
+                    k,
+                    format!("Property name '{key_string}' is not allowed by propertyNames: false"),
+                    "propertyNames",
+                    false,
+                );
+                fail_fast!(context)
+            } else if let Some(property_names) = &self.property_names {
                 let names_context = context.append_path(&key_string);
                 let key_to_validate = if property_names_validates_string_projection(property_names)
                 {
@@ -174,7 +206,9 @@ impl ObjectSchema {
                 } else {
                     k.clone()
                 };
-                property_names.validate(&names_context, &key_to_validate)?;
+                context.with_schema_segment(&["propertyNames"], || {
+                    property_names.validate(&names_context, &key_to_validate)
+                })?;
             }
         }
 
@@ -186,22 +220,69 @@ impl ObjectSchema {
                     .filter_map(|k| k.data.as_str())
                     .any(|s| s == required_property)
                 {
-                    context.add_error(
+                    let suggestion = self.default_for_property(required_property).map(|default| {
+                        let pointer = path_to_pointer(&[required_property]);
+                        FixSuggestion::add(
+                            format!("{}{pointer}", context.pointer()),
+                            default.clone(),
+                        )
+                    });
+                    context.add_keyword_error_with_suggestion(
                         object,
                         format!("Required property '{required_property}' is missing!"),
+                        "required",
+                        format_vec(required),
+                        suggestion,
                     );
                     fail_fast!(context)
                 }
             }
         }
 
+        // Validate the `x-min-matches` / `x-max-matches` extensions on patternProperties
+        if context.options.x_extensions
+            && let Some(pattern_properties) = &self.pattern_properties
+        {
+            for (pp, count) in pattern_properties.iter().zip(pattern_match_counts) {
+                let pattern = pp.regex.as_str();
+                if let Some(min_matches) = pp.min_matches
+                    && count < min_matches
+                {
+                    context.with_schema_segment(&["patternProperties", pattern, "x-min-matches"], || {
+                        context.add_error(
+                            object,
+                            format!(
+                                "Too few properties match pattern '{pattern}': found {count}, minimum is {min_matches}"
+                            ),
+                        )
+                    });
+                    fail_fast!(context)
+                }
+                if let Some(max_matches) = pp.max_matches
+                    && count > max_matches
+                {
+                    context.with_schema_segment(&["patternProperties", pattern, "x-max-matches"], || {
+                        context.add_error(
+                            object,
+                            format!(
+                                "Too many properties match pattern '{pattern}': found {count}, maximum is {max_matches}"
+                            ),
+                        )
+                    });
+                    fail_fast!(context)
+                }
+            }
+        }
+
         // Validate minProperties
         if let Some(min_properties) = &self.min_properties
             && mapping.len() < *min_properties
         {
-            context.add_error(
+            context.add_keyword_error(
                 object,
                 format!("Object has too few properties! Minimum is {min_properties}!"),
+                "minProperties",
+                min_properties,
             );
             fail_fast!(context)
         }
@@ -209,29 +290,28 @@ impl ObjectSchema {
         if let Some(max_properties) = &self.max_properties
             && mapping.len() > *max_properties
         {
-            context.add_error(
+            context.add_keyword_error(
                 object,
                 format!("Object has too many properties! Maximum is {max_properties}!"),
+                "maxProperties",
+                max_properties,
             );
             fail_fast!(context)
         }
 
         // dependentRequired / dependentSchemas (JSON Schema 2020-12): after per-property and required/min/max.
         if self.dependent_required.is_some() || self.dependent_schemas.is_some() {
-            let keys = Self::instance_property_keys(mapping)?;
+            let keys = Self::instance_property_keys(mapping);
             if let Some(dr) = &self.dependent_required {
                 for (trigger, deps) in dr {
                     if keys.contains(trigger) {
                         for dep in deps {
                             if !keys.contains(dep) {
-                                context.add_error(
+                                context.add_keyword_error(
                                     object,
-                                    format!(
-                                        "{} When property '{}' is present, property '{}' is required by dependentRequired",
-                                        format_marker(&object.span.start),
-                                        trigger,
-                                        dep
-                                    ),
+                                    format!("Property '{trigger}' requires property '{dep}'"),
+                                    "dependentRequired",
+                                    format!("{{{trigger}: {}}}", format_vec(deps)),
                                 );
                                 fail_fast!(context)
                             }
@@ -242,7 +322,28 @@ impl ObjectSchema {
             if let Some(ds) = &self.dependent_schemas {
                 for (trigger, subschema) in ds {
                     if keys.contains(trigger) {
-                        subschema.validate(context, object)?;
+                        let sub_context = context.get_sub_context();
+                        let result = context
+                            .with_schema_segment(&["dependentSchemas", trigger], || {
+                                subschema.validate(&sub_context, object)
+                            });
+                        let mut errors = sub_context.errors.take();
+                        for error in &mut errors {
+                            error.error = format!(
+                                "{} (from dependentSchemas, as property '{trigger}' is present)",
+                                error.error
+                            );
+                        }
+                        let failed = errors.iter().any(ValidationError::is_error);
+                        context.extend_errors(errors);
+                        context.extend_annotations(&sub_context);
+                        match result {
+                            Ok(()) | Err(Error::FailFast) => {}
+                            Err(e) => return Err(e),
+                        }
+                        if failed {
+                            fail_fast!(context)
+                        }
                     }
                 }
             }
@@ -251,25 +352,26 @@ impl ObjectSchema {
         Ok(())
     }
 
-    /// Property names present on the instance mapping (scalar keys only, same rules as the main validation loop).
+    /// The `default` declared by the schema of a property in `properties`, if any
+    fn default_for_property(&self, name: &str) -> Option<&ConstValue> {
+        match self.properties.as_ref()?.get(name)? {
+            YamlSchema::Subschema(subschema) => subschema.metadata_and_annotations.default.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Property names present on the instance mapping (scalar keys only, same rules as the main
+    /// validation loop, which reports complex keys).
     fn instance_property_keys<'r>(
         mapping: &saphyr::AnnotatedMapping<'r, saphyr::MarkedYaml<'r>>,
-    ) -> Result<HashSet<String>> {
-        let mut keys = HashSet::new();
-        for (k, _) in mapping {
-            let key_string = match &k.data {
-                saphyr::YamlData::Value(scalar) => scalar_to_string(scalar),
-                v => {
-                    return Err(expected_scalar!(
-                        "[{}] Expected a scalar key, got: {:?}",
-                        format_marker(&k.span.start),
-                        v
-                    ));
-                }
-            };
-            keys.insert(key_string);
-        }
-        Ok(keys)
+    ) -> HashSet<String> {
+        mapping
+            .keys()
+            .filter_map(|k| match &k.data {
+                saphyr::YamlData::Value(scalar) => Some(scalar_to_string(scalar)),
+                _ => None,
+            })
+            .collect()
     }
 }
 
@@ -307,6 +409,7 @@ mod tests {
     use crate::schemas::NumberSchema;
     use crate::schemas::StringSchema;
     use hashlink::LinkedHashMap;
+    use saphyr::LoadableYamlNode;
 
     use super::*;
 
@@ -345,6 +448,161 @@ mod tests {
         assert_eq!(first_error.error, "Expected a string, but got: 42 (int)");
     }
 
+    #[test]
+    fn test_properties_continue_after_failure_unless_fail_fast() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+              age:
+                type: integer
+                minimum: 0
+            "#,
+        )
+        .unwrap();
+        let value = "age: -1\nname: 42";
+        let context = engine::Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors = context.errors.borrow();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["age", "name"]);
+
+        let docs = saphyr::MarkedYaml::load_from_str(value).unwrap();
+        let context = Context::with_root_schema(&root_schema, true);
+        let result = root_schema.validate(&context, docs.first().unwrap());
+        assert!(result.is_ok(), "{result:?}");
+        assert_eq!(context.errors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_nested_object_errors_stop_only_in_fail_fast_mode() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              server:
+                type: object
+                properties:
+                  port:
+                    type: integer
+                  workers:
+                    type: integer
+              replicas:
+                type: integer
+            "#,
+        )
+        .unwrap();
+        let value = "server:\n  port: x\n  workers: y\nreplicas: z";
+        let context = engine::Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors = context.errors.borrow();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["server.port", "server.workers", "replicas"]);
+
+        let context = engine::Engine::evaluate(&root_schema, value, true).unwrap();
+        let errors = context.errors.borrow();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["server.port"]);
+    }
+
+    #[test]
+    fn test_top_level_sequence_is_not_an_object() {
+        let root_schema = loader::load_from_str("type: object").unwrap();
+        let context = engine::Engine::evaluate(&root_schema, "- a: 1", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "Expected an object, but got: [{\"a\": 1}]");
+    }
+
+    /// Validates `value` against an object schema with the given `additionalProperties`
+    fn additional_properties_errors(additional_properties: &str, value: &str) -> Vec<String> {
+        let schema = format!(
+            "type: object\nproperties:\n  name:\n    type: string\nadditionalProperties: {additional_properties}\n$defs:\n  count:\n    type: integer"
+        );
+        let root_schema = loader::load_from_str(&schema).unwrap();
+        let context = engine::Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors = context.errors.borrow();
+        errors.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_additional_properties_boolean_schema_and_ref() {
+        for additional_properties in [
+            "true",
+            "false",
+            "{type: integer}",
+            "{$ref: '#/$defs/count'}",
+        ] {
+            assert!(
+                additional_properties_errors(additional_properties, "name: a").is_empty(),
+                "{additional_properties}"
+            );
+        }
+        assert!(additional_properties_errors("true", "name: a\nextra: x").is_empty());
+        assert_eq!(
+            additional_properties_errors("false", "name: a\nextra: 1"),
+            vec!["[2:8] .: Additional property 'extra' is not allowed!"]
+        );
+        // A `$ref` behaves the same as the schema it refers to
+        for additional_properties in ["{type: integer}", "{$ref: '#/$defs/count'}"] {
+            assert!(
+                additional_properties_errors(additional_properties, "name: a\nextra: 1").is_empty(),
+                "{additional_properties}"
+            );
+            assert_eq!(
+                additional_properties_errors(additional_properties, "name: a\nextra: x"),
+                vec!["[2:8] .extra: Expected a number, but got: \"x\" (string)"],
+                "{additional_properties}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_complex_key_is_reported_and_skipped() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              name:
+                type: string
+              port:
+                type: integer
+            required: [name, port]
+            additionalProperties: false
+            dependentRequired:
+              name: [port]
+            "#,
+        )
+        .unwrap();
+        let value = "name: 1
+? [a, b]
+: x
+port: p
+";
+        let context = engine::Engine::evaluate(&root_schema, value, false).unwrap();
+        let errors: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "[1:7] .name: Expected a string, but got: 1 (int)",
+                "[2:3] .: Complex mapping keys are not supported by this schema validator",
+                "[4:7] .port: Expected a number, but got: \"p\" (string)",
+            ]
+        );
+
+        let context = engine::Engine::evaluate(&root_schema, "? {a: 1}\n: x\n", true).unwrap();
+        assert_eq!(
+            context.errors.borrow().len(),
+            1,
+            "fail-fast stops at the complex key"
+        );
+    }
+
     #[test]
     fn dependent_required_validation() {
         let yaml = r#"
@@ -369,6 +627,67 @@ mod tests {
 
         let bad = engine::Engine::evaluate(&root_schema, "credit_card: \"4111\"", false).unwrap();
         assert!(bad.has_errors());
+        let errors = bad.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error,
+            "Property 'credit_card' requires property 'billing_address'"
+        );
+    }
+
+    #[test]
+    fn dependent_keyword_errors_are_pathed_at_the_object() {
+        let root_schema = loader::load_from_str(
+            r#"
+            properties:
+              payment:
+                type: object
+                dependentRequired:
+                  credit_card:
+                    - billing_address
+                dependentSchemas:
+                  credit_card:
+                    type: object
+                    required:
+                      - cvv
+            "#,
+        )
+        .unwrap();
+        let context =
+            engine::Engine::evaluate(&root_schema, "payment:\n  credit_card: \"4111\"\n", false)
+                .unwrap();
+        let errors = context.errors.borrow();
+        let rendered: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "[2:3] .payment: Property 'credit_card' requires property 'billing_address'",
+                "[2:3] .payment: Required property 'cvv' is missing! (from dependentSchemas, as property 'credit_card' is present)",
+            ]
+        );
+        assert_eq!(errors[0].keyword.as_deref(), Some("dependentRequired"));
+    }
+
+    #[test]
+    fn dependent_required_respects_fail_fast() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            dependentRequired:
+              credit_card:
+                - billing_address
+                - cvv
+            "#,
+        )
+        .unwrap();
+        let context = engine::Engine::evaluate(&root_schema, "credit_card: x", false).unwrap();
+        assert_eq!(context.errors.borrow().len(), 2);
+
+        let options = crate::ValidationOptions::default().fail_fast(true);
+        let context =
+            engine::Engine::evaluate_with_options(&root_schema, "credit_card: x", &options)
+                .unwrap();
+        assert_eq!(context.errors.borrow().len(), 1);
     }
 
     #[test]
@@ -398,4 +717,341 @@ mod tests {
         let bad = engine::Engine::evaluate(&root_schema, "credit_card: \"4111\"", false).unwrap();
         assert!(bad.has_errors());
     }
+
+    #[test]
+    fn dependent_schemas_with_required_and_properties() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              credit_card:
+                type: string
+            dependentSchemas:
+              credit_card:
+                properties:
+                  billing_address:
+                    type: string
+                    minLength: 5
+                required:
+                  - billing_address
+            "#,
+        )
+        .unwrap();
+        let context = engine::Engine::evaluate(
+            &root_schema,
+            "credit_card: \"4111\"\nbilling_address: 1 Main St",
+            false,
+        )
+        .unwrap();
+        assert!(!context.has_errors());
+        let context = engine::Engine::evaluate(&root_schema, "billing_address: x", false).unwrap();
+        assert!(!context.has_errors());
+
+        let context =
+            engine::Engine::evaluate(&root_schema, "credit_card: \"4111\"", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "");
+        assert!(
+            errors[0]
+                .error
+                .ends_with("(from dependentSchemas, as property 'credit_card' is present)"),
+            "{}",
+            errors[0].error
+        );
+        drop(errors);
+
+        let context = engine::Engine::evaluate(
+            &root_schema,
+            "credit_card: \"4111\"\nbilling_address: x",
+            false,
+        )
+        .unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "billing_address");
+        assert!(
+            errors[0].error.contains("'credit_card'"),
+            "{}",
+            errors[0].error
+        );
+    }
+
+    #[test]
+    fn dependent_schemas_card_number_required_for_card_type() {
+        let yaml = r#"
+        type: object
+        properties:
+          type:
+            type: string
+          card_number:
+            type: string
+        dependentSchemas:
+          type:
+            if:
+              properties:
+                type:
+                  const: card
+            then:
+              type: object
+              required:
+                - card_number
+        "#;
+        let root_schema = loader::load_from_str(yaml).unwrap();
+        for ok in [
+            "type: card\ncard_number: \"4111\"",
+            "type: bank",
+            "card_number: \"4111\"",
+        ] {
+            let context = engine::Engine::evaluate(&root_schema, ok, false).unwrap();
+            assert!(!context.has_errors(), "{ok}");
+        }
+
+        let context = engine::Engine::evaluate(&root_schema, "type: card", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "");
+        assert_eq!(
+            errors[0].error,
+            "Required property 'card_number' is missing! (from dependentSchemas, as property 'type' is present)"
+        );
+    }
+
+    const WORKERS_SCHEMA: &str = r#"
+        type: object
+        properties:
+          name:
+            type: string
+        patternProperties:
+          "^worker-":
+            type: integer
+            x-min-matches: 1
+            x-max-matches: 3
+        additionalProperties: false
+        "#;
+
+    fn validate_workers(yaml: &str, x_extensions: bool) -> Vec<String> {
+        let root_schema = loader::load_from_str(WORKERS_SCHEMA).unwrap();
+        let options = crate::ValidationOptions::default().x_extensions(x_extensions);
+        let context = engine::Engine::evaluate_with_options(&root_schema, yaml, &options).unwrap();
+        context
+            .errors
+            .borrow()
+            .iter()
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_pattern_properties_match_limits() {
+        assert!(validate_workers("worker-1: 1\nworker-2: 2", true).is_empty());
+        assert!(validate_workers("worker-1: 1\nworker-2: 2\nworker-3: 3", true).is_empty());
+        assert_eq!(
+            validate_workers(
+                "name: pool\nworker-1: 1\nworker-2: 2\nworker-3: 3\nworker-4: 4",
+                true
+            ),
+            vec!["[1:1] .: Too many properties match pattern '^worker-': found 4, maximum is 3"]
+        );
+        assert_eq!(
+            validate_workers("name: pool", true),
+            vec!["[1:1] .: Too few properties match pattern '^worker-': found 0, minimum is 1"]
+        );
+    }
+
+    #[test]
+    fn test_pattern_properties_match_limits_with_additional_properties_false() {
+        let errors = validate_workers(
+            "worker-1: 1\nworker-2: 2\nworker-3: 3\nworker-4: 4\nother: 5",
+            true,
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Additional property 'other' is not allowed!"));
+        assert!(errors[1].contains("found 4, maximum is 3"));
+    }
+
+    #[test]
+    fn test_pattern_properties_match_limits_ignored_without_extensions() {
+        assert!(
+            validate_workers("worker-1: 1\nworker-2: 2\nworker-3: 3\nworker-4: 4", false)
+                .is_empty()
+        );
+        assert!(validate_workers("name: pool", false).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_properties_match_limit_must_be_non_negative_integer() {
+        let err = loader::load_from_str(
+            r#"
+            type: object
+            patternProperties:
+              "^worker-":
+                x-max-matches: -1
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'x-max-matches' expects a non-negative integer"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_schema_fragments_for_object_keywords() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              port:
+                type: integer
+                maximum: 65535
+            required:
+              - name
+              - port
+            additionalProperties: false
+            "#,
+        )
+        .unwrap();
+        let options = crate::ValidationOptions::default().schema_fragments(true);
+        let context =
+            engine::Engine::evaluate_with_options(&root_schema, "port: 70000\nextra: 1", &options)
+                .unwrap();
+        let fragments: Vec<Option<String>> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(|e| e.schema_fragment.clone())
+            .collect();
+        assert_eq!(
+            fragments,
+            vec![
+                Some("{maximum: 65535}".to_string()),
+                Some("{additionalProperties: false}".to_string()),
+                Some("{required: [name, port]}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suppress_additional_properties_under_subtree() {
+        let root_schema = loader::load_from_str(
+            r##"
+            type: object
+            $defs:
+              strict:
+                type: object
+                properties:
+                  name:
+                    type: string
+                additionalProperties: false
+            properties:
+              legacy:
+                type: object
+                additionalProperties:
+                  $ref: "#/$defs/strict"
+              current:
+                $ref: "#/$defs/strict"
+            "##,
+        )
+        .unwrap();
+        let options =
+            crate::ValidationOptions::default().suppress("additionalProperties", "/legacy/**");
+
+        let context = engine::Engine::evaluate_with_options(
+            &root_schema,
+            "legacy:\n  a:\n    name: x\n    extra: 1\ncurrent:\n  name: y",
+            &options,
+        )
+        .unwrap();
+        assert!(!context.has_errors());
+        {
+            let errors = context.errors.borrow();
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].suppressed);
+            assert_eq!(errors[0].keyword.as_deref(), Some("additionalProperties"));
+            assert_eq!(errors[0].path, "legacy.a");
+            assert!(errors[0].to_string().starts_with("suppressed: "));
+        }
+        assert!(context.into_result().is_ok());
+
+        // The same error elsewhere, and other errors under the subtree, still fail
+        let context = engine::Engine::evaluate_with_options(
+            &root_schema,
+            "legacy:\n  a:\n    name: 1\n    extra: 1\ncurrent:\n  name: y\n  extra: 1",
+            &options,
+        )
+        .unwrap();
+        assert!(context.has_errors());
+        let errors = context.errors.borrow();
+        let failing: Vec<(&str, &str)> = errors
+            .iter()
+            .filter(|e| e.is_error())
+            .map(|e| (e.path.as_str(), e.error.as_str()))
+            .collect();
+        assert_eq!(
+            failing,
+            vec![
+                ("legacy.a.name", "Expected a string, but got: 1 (int)"),
+                ("current", "Additional property 'extra' is not allowed!"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_additional_properties() {
+        let root_schema = loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              server:
+                type: object
+                properties:
+                  host:
+                    type: string
+              labels:
+                type: object
+                additionalProperties: true
+            additionalProperties: false
+            "#,
+        )
+        .unwrap();
+        let value = "server:\n  host: a\n  extra: 1\nlabels:\n  team: b";
+
+        let context = engine::Engine::evaluate(&root_schema, value, false).unwrap();
+        assert!(!context.has_errors());
+
+        let options = crate::ValidationOptions::default().recursive_additional_properties(true);
+        let context = engine::Engine::evaluate_with_options(&root_schema, value, &options).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "server");
+        assert_eq!(
+            errors[0].error,
+            "Additional property 'extra' is not allowed!"
+        );
+    }
+
+    #[test]
+    fn test_flow_style_errors_point_at_each_value() {
+        let root_schema = loader::load_from_str(
+            "{type: object, properties: {a: {type: string}, b: {type: string}}, additionalProperties: false}",
+        )
+        .unwrap();
+        let context = engine::Engine::evaluate(&root_schema, "{a: 1, b: 2, c: 3}", false).unwrap();
+        let errors: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "[1:5] .a: Expected a string, but got: 1 (int)",
+                "[1:11] .b: Expected a string, but got: 2 (int)",
+                "[1:17] .: Additional property 'c' is not allowed!",
+            ]
+        );
+    }
 }
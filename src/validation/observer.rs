@@ -0,0 +1,70 @@
+//! Hooks for following a validation as it runs, e.g. to drive a progress indicator
+
+use saphyr::MarkedYaml;
+use saphyr::YamlData;
+
+use crate::validation::ValidationError;
+
+/// The kind of an instance node reported to [`ValidationObserver::on_node`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Mapping,
+    Sequence,
+    Scalar,
+}
+
+impl NodeKind {
+    pub(crate) fn of(node: &MarkedYaml) -> NodeKind {
+        match &node.data {
+            YamlData::Mapping(_) => NodeKind::Mapping,
+            YamlData::Sequence(_) => NodeKind::Sequence,
+            _ => NodeKind::Scalar,
+        }
+    }
+}
+
+/// Counts of the diagnostics recorded for a document, passed to
+/// [`ValidationObserver::on_document_done`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationSummary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub suppressed: usize,
+}
+
+impl ValidationSummary {
+    pub(crate) fn of(errors: &[ValidationError]) -> ValidationSummary {
+        let mut summary = ValidationSummary::default();
+        for error in errors {
+            if error.suppressed {
+                summary.suppressed += 1;
+            } else if error.is_error() {
+                summary.errors += 1;
+            } else {
+                summary.warnings += 1;
+            }
+        }
+        summary
+    }
+}
+
+/// Receives events while a document is validated, see
+/// [`Engine::evaluate_with_observer`](crate::Engine::evaluate_with_observer).
+///
+/// Observers only see immutable views of the document and diagnostics, so they can't change the
+/// outcome of a validation; use interior mutability to keep counts. They must be `Sync`, like the
+/// [`Context`](crate::Context) holding them, so use atomics or a `Mutex` rather than a `Cell`. Like
+/// [`Context::on_error`](crate::Context::on_error), observers don't see nodes and errors from
+/// combinator branches that are discarded.
+pub trait ValidationObserver {
+    /// Called for the document root, and when an object or array validator enters a child node.
+    /// `path` is the same as [`ValidationError::path`], e.g. `servers.0.port`. A node validated
+    /// against several subschemas (e.g. through `allOf`) may be reported more than once.
+    fn on_node(&self, _path: &str, _kind: NodeKind) {}
+
+    /// Called with each error and warning as it is recorded
+    fn on_error(&self, _error: &ValidationError) {}
+
+    /// Called once the document has been validated
+    fn on_document_done(&self, _summary: ValidationSummary) {}
+}
@@ -0,0 +1,204 @@
+//! Options controlling how a YAML document is validated
+
+use crate::utils::glob_matches;
+
+/// Options for [`Engine::evaluate_with_options`](crate::Engine::evaluate_with_options)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationOptions {
+    /// Stop at the first validation error
+    pub fail_fast: bool,
+    /// Allow `$ref`s to `http`/`https` URLs to be fetched over the network. Off by default so
+    /// validation never makes surprise network calls; each URL is fetched at most once per run.
+    pub allow_remote_refs: bool,
+    /// Enforce non-standard `x-` extension keywords, such as `x-max-matches` in
+    /// `patternProperties`. When off, extension keywords are ignored.
+    pub x_extensions: bool,
+    /// Record the failing keyword and its schema value, e.g. `{minLength: 5}`, on
+    /// [`ValidationError::schema_fragment`](crate::validation::ValidationError::schema_fragment)
+    pub schema_fragments: bool,
+    /// Show the location of the schema rule that produced each error, e.g.
+    /// `#/properties/port/maximum`, in the error's `Display` output. The location is always
+    /// available from
+    /// [`ValidationError::schema_path`](crate::validation::ValidationError::schema_path).
+    pub verbose: bool,
+    /// Validate null values, such as an empty `name:`, as the empty string `""` against string
+    /// schemas
+    pub null_as_empty_string: bool,
+    /// Check that strings with `contentEncoding: base64` decode, and that those with
+    /// `contentMediaType: application/json` hold JSON. Off by default, as JSON Schema treats
+    /// both keywords as annotations.
+    pub content_assertions: bool,
+    /// Match strings against the string members of every `enum` regardless of ASCII case, so
+    /// `enum: [Red, Green]` accepts `red`, for systems that normalize casing
+    pub case_insensitive_enums: bool,
+    /// Apply the root schema's `additionalProperties` to nested objects that don't set their own,
+    /// e.g. so `additionalProperties: false` at the root rejects unknown keys at any depth
+    pub recursive_additional_properties: bool,
+    /// Errors matching any of these rules are recorded as suppressed instead of failing validation
+    pub suppressions: Vec<Suppression>,
+    /// Read plain scalars the YAML 1.1 way, so values such as `on`, `off`, `yes` and `no` are
+    /// booleans. Documents with a `%YAML` directive are read as the version they declare.
+    pub yaml_1_1: bool,
+    /// Validate every document of a YAML stream, not only the first. Errors record their
+    /// document on [`ValidationError::document_index`](crate::validation::ValidationError::document_index).
+    /// Empty documents, such as after a trailing `---`, are skipped.
+    pub all_documents: bool,
+    /// Collect the `title` and `description` of the schemas the document validates against in
+    /// [`Context::annotations`](crate::Context::annotations)
+    pub collect_annotations: bool,
+    /// Count the schemas applied, regular expressions matched and `$ref`s resolved, available
+    /// afterwards from [`Context::check_counts`](crate::Context::check_counts). Useful to find
+    /// out why a schema is slow.
+    pub count_checks: bool,
+    /// Fail to validate input from a reader, see
+    /// [`Engine::evaluate_reader_with_options`](crate::Engine::evaluate_reader_with_options),
+    /// that is larger than this many bytes, without reading the rest of it
+    pub max_input_bytes: Option<u64>,
+}
+
+impl ValidationOptions {
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    pub fn allow_remote_refs(mut self, allow_remote_refs: bool) -> Self {
+        self.allow_remote_refs = allow_remote_refs;
+        self
+    }
+
+    pub fn x_extensions(mut self, x_extensions: bool) -> Self {
+        self.x_extensions = x_extensions;
+        self
+    }
+
+    pub fn schema_fragments(mut self, schema_fragments: bool) -> Self {
+        self.schema_fragments = schema_fragments;
+        self
+    }
+
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    pub fn null_as_empty_string(mut self, null_as_empty_string: bool) -> Self {
+        self.null_as_empty_string = null_as_empty_string;
+        self
+    }
+
+    pub fn content_assertions(mut self, content_assertions: bool) -> Self {
+        self.content_assertions = content_assertions;
+        self
+    }
+
+    pub fn case_insensitive_enums(mut self, case_insensitive_enums: bool) -> Self {
+        self.case_insensitive_enums = case_insensitive_enums;
+        self
+    }
+
+    pub fn recursive_additional_properties(
+        mut self,
+        recursive_additional_properties: bool,
+    ) -> Self {
+        self.recursive_additional_properties = recursive_additional_properties;
+        self
+    }
+
+    pub fn yaml_1_1(mut self, yaml_1_1: bool) -> Self {
+        self.yaml_1_1 = yaml_1_1;
+        self
+    }
+
+    pub fn all_documents(mut self, all_documents: bool) -> Self {
+        self.all_documents = all_documents;
+        self
+    }
+
+    pub fn collect_annotations(mut self, collect_annotations: bool) -> Self {
+        self.collect_annotations = collect_annotations;
+        self
+    }
+
+    pub fn count_checks(mut self, count_checks: bool) -> Self {
+        self.count_checks = count_checks;
+        self
+    }
+
+    pub fn max_input_bytes(mut self, max_input_bytes: u64) -> Self {
+        self.max_input_bytes = Some(max_input_bytes);
+        self
+    }
+
+    /// Suppress errors with the given code (or `*` for any) at instance paths matching `path`,
+    /// see [`Suppression`]
+    pub fn suppress<C: Into<String>, P: Into<String>>(mut self, code: C, path: P) -> Self {
+        self.suppressions.push(Suppression::new(code, path));
+        self
+    }
+}
+
+/// A rule that suppresses matching validation errors, e.g. to phase in a stricter schema over a
+/// large legacy document. Suppressed errors are still recorded, with
+/// [`ValidationError::suppressed`](crate::validation::ValidationError::suppressed) set, but don't
+/// count towards [`Context::has_errors`](crate::Context::has_errors).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    /// The error code, i.e. the schema keyword that produced the error such as
+    /// `additionalProperties`, or `*` for any error
+    pub code: String,
+    /// A JSON Pointer style glob over instance path segments, matched like
+    /// [`DirOptions`](crate::DirOptions) globs: `*` and `?` match within one segment and `**`
+    /// any number of segments, e.g. `/legacy/**` or `/servers/*/port`
+    pub path: String,
+}
+
+impl Suppression {
+    pub fn new<C: Into<String>, P: Into<String>>(code: C, path: P) -> Suppression {
+        Suppression {
+            code: code.into(),
+            path: path.into(),
+        }
+    }
+
+    /// Whether this rule applies to an error with the given code at the given instance path
+    pub fn matches<S: AsRef<str>>(&self, code: Option<&str>, path: &[S]) -> bool {
+        if self.code != "*" && code != Some(self.code.as_str()) {
+            return false;
+        }
+        let pattern: Vec<String> = self
+            .path
+            .strip_prefix('/')
+            .unwrap_or(&self.path)
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.replace("~1", "/").replace("~0", "~"))
+            .collect();
+        glob_matches(&pattern, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suppression_glob_matching() {
+        let rule = Suppression::new("*", "/legacy/**");
+        assert!(rule.matches(None, &["legacy"]));
+        assert!(rule.matches(None, &["legacy", "a", "b"]));
+        assert!(!rule.matches(None, &["current", "legacy"]));
+
+        let rule = Suppression::new("minimum", "/servers/*/port");
+        assert!(rule.matches(Some("minimum"), &["servers", "0", "port"]));
+        assert!(!rule.matches(Some("maximum"), &["servers", "0", "port"]));
+        assert!(!rule.matches(None, &["servers", "0", "port"]));
+        assert!(!rule.matches(Some("minimum"), &["servers", "port"]));
+        assert!(!rule.matches(Some("minimum"), &["servers", "0", "1", "port"]));
+
+        let rule = Suppression::new("*", "/**/id");
+        assert!(rule.matches(None, &["id"]));
+        assert!(rule.matches(None, &["a", "b", "id"]));
+        assert!(!rule.matches(None, &["a", "id", "b"]));
+    }
+}
@@ -0,0 +1,48 @@
+//! A serializable summary of a validation, see [`Engine::evaluate_to_report`]
+//!
+//! [`Engine::evaluate_to_report`]: crate::Engine::evaluate_to_report
+
+use serde::Serialize;
+
+use crate::validation::Context;
+use crate::validation::ValidationError;
+use crate::validation::ValidationSummary;
+
+/// The outcome of a validation: whether it passed, the number of diagnostics of each kind, and
+/// every error and warning in the order they were recorded.
+///
+/// Serializes as `{"valid": ..., "errors": ..., "warnings": ..., "suppressed": ...,
+/// "diagnostics": [...]}`, with each diagnostic serialized as a [`ValidationError`].
+#[derive(Debug, Serialize)]
+pub struct Report {
+    /// Whether there are no unsuppressed errors. Warnings don't make a document invalid.
+    pub valid: bool,
+    /// The number of unsuppressed errors
+    pub errors: usize,
+    /// The number of warnings
+    pub warnings: usize,
+    /// The number of suppressed errors
+    pub suppressed: usize,
+    /// All errors and warnings, including suppressed ones
+    pub diagnostics: Vec<ValidationError>,
+}
+
+impl Report {
+    /// Takes the errors and warnings recorded in `context`
+    pub fn from_context(context: Context) -> Report {
+        let diagnostics = std::mem::take(&mut *context.errors.borrow_mut());
+        let summary = ValidationSummary::of(&diagnostics);
+        Report {
+            valid: summary.errors == 0,
+            errors: summary.errors,
+            warnings: summary.warnings,
+            suppressed: summary.suppressed,
+            diagnostics,
+        }
+    }
+
+    /// This report as a JSON object
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
@@ -0,0 +1,59 @@
+//! State shared between the contexts of a validation, and between threads
+
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+use std::sync::RwLockWriteGuard;
+
+/// A value shared by cloning, which can be borrowed like a `RefCell` but is `Send` and `Sync`
+/// when the value is, so a [`Context`](crate::Context) can be moved to or shared with other
+/// threads.
+///
+/// Unlike a `RefCell`, borrowing mutably while the value is borrowed blocks instead of panicking.
+#[derive(Default)]
+pub struct Shared<T>(Arc<RwLock<T>>);
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Shared(Arc::new(RwLock::new(value)))
+    }
+
+    /// Borrows the value. A panic while it was borrowed mutably doesn't make it unusable.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Borrows the value mutably
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Takes the value, leaving `Default::default()` in its place
+    pub fn take(&self) -> T
+    where
+        T: Default,
+    {
+        std::mem::take(&mut *self.borrow_mut())
+    }
+
+    /// Whether both share the same value
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(Arc::clone(&self.0))
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.try_read() {
+            Ok(value) => value.fmt(f),
+            Err(_) => f.write_str("<borrowed>"),
+        }
+    }
+}
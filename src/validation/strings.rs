@@ -7,31 +7,85 @@ use crate::Validator;
 use crate::schemas::StringFormat;
 use crate::schemas::StringSchema;
 use crate::utils::humanize_yaml_data;
+use crate::validation::content;
 use crate::validation::formats;
 
 impl Validator for StringSchema {
     fn validate(&self, context: &Context, value: &saphyr::MarkedYaml) -> Result<()> {
-        let errors = self.do_validate(value);
-        if !errors.is_empty() {
-            for error in errors {
-                context.add_error(value, error);
+        // The `x-trim` extension only applies when extensions are enabled
+        let trim = self.trim && context.options.x_extensions;
+        let errors = self.do_validate(
+            value,
+            trim,
+            context.options.null_as_empty_string,
+            context.options.content_assertions,
+        );
+        // `pattern` isn't matched against strings that fail `minLength` or `maxLength`
+        if self.pattern.is_some()
+            && match &value.data {
+                saphyr::YamlData::Value(saphyr::Scalar::String(_)) => true,
+                saphyr::YamlData::Value(saphyr::Scalar::Null) => {
+                    context.options.null_as_empty_string
+                }
+                _ => false,
             }
+            && !errors.iter().any(StringError::is_length_error)
+        {
+            context.count_check(|counts| counts.regex_matches += 1);
+        }
+        for error in errors {
+            context.add_keyword_error(value, error.message, error.keyword, error.keyword_value);
+        }
+        if trim
+            && let saphyr::YamlData::Value(saphyr::Scalar::String(s)) = &value.data
+            && s.trim() != s
+        {
+            context.add_warning(
+                value,
+                format!(
+                    "String {:?} was validated as {:?} (x-trim)",
+                    s.as_ref(),
+                    s.trim()
+                ),
+            );
         }
         Ok(())
     }
 }
 
+/// A string validation failure, with the keyword (and its value) that produced it
+pub(crate) struct StringError {
+    pub keyword: &'static str,
+    pub keyword_value: String,
+    pub message: String,
+}
+
+impl StringError {
+    fn is_length_error(&self) -> bool {
+        matches!(self.keyword, "minLength" | "maxLength")
+    }
+}
+
 impl StringSchema {
-    fn do_validate(&self, value: &saphyr::MarkedYaml) -> Vec<String> {
+    fn do_validate(
+        &self,
+        value: &saphyr::MarkedYaml,
+        trim: bool,
+        null_as_empty_string: bool,
+        content_assertions: bool,
+    ) -> Vec<StringError> {
         debug!("do_validate: {:?}", value.data);
         let mut errors = Vec::new();
 
-        if let saphyr::YamlData::Value(scalar) = &value.data
-            && let saphyr::Scalar::String(s) = scalar
-        {
-            // TODO: add enum validation
+        let string = match &value.data {
+            saphyr::YamlData::Value(saphyr::Scalar::String(s)) if trim => Some(s.trim()),
+            saphyr::YamlData::Value(saphyr::Scalar::String(s)) => Some(s.as_ref()),
+            saphyr::YamlData::Value(saphyr::Scalar::Null) if null_as_empty_string => Some(""),
+            _ => None,
+        };
+        if let Some(s) = string {
+            // `enum` is validated by the enclosing Subschema, for values of any type
             let enum_strings = None;
-            debug!("enum_strings: {enum_strings:?}");
             validate_string(
                 &mut errors,
                 self.min_length,
@@ -41,19 +95,51 @@ impl StringSchema {
                 enum_strings.as_ref(),
                 s,
             );
+            if content_assertions
+                && let Some((keyword, message)) = content::validate_content(
+                    self.content_encoding.as_deref(),
+                    self.content_media_type.as_deref(),
+                    s,
+                )
+            {
+                let keyword_value = if keyword == "contentEncoding" {
+                    &self.content_encoding
+                } else {
+                    &self.content_media_type
+                };
+                errors.push(StringError {
+                    keyword,
+                    keyword_value: keyword_value.clone().unwrap_or_default(),
+                    message,
+                });
+            }
         } else {
-            errors.push(format!(
-                "Expected a string, but got: {}",
-                humanize_yaml_data(&value.data)
-            ));
+            let message = match &value.data {
+                // An empty value such as `name:` is null, which is rarely what was meant
+                saphyr::YamlData::Value(saphyr::Scalar::Null)
+                    if value.span.start.index() == value.span.end.index() =>
+                {
+                    "Property is empty (null); use '' for an empty string or make the property optional"
+                        .to_string()
+                }
+                saphyr::YamlData::Value(saphyr::Scalar::Null) => {
+                    "Expected a string, but got: null".to_string()
+                }
+                data => format!("Expected a string, but got: {}", humanize_yaml_data(data)),
+            };
+            errors.push(StringError {
+                keyword: "type",
+                keyword_value: "string".to_string(),
+                message,
+            });
         }
         errors
     }
 }
 
 /// Just trying to isolate the actual validation into a function that doesn't take a context
-pub fn validate_string(
-    errors: &mut Vec<String>,
+pub(crate) fn validate_string(
+    errors: &mut Vec<StringError>,
     min_length: Option<usize>,
     max_length: Option<usize>,
     pattern: Option<&Regex>,
@@ -69,31 +155,55 @@ pub fn validate_string(
         && let Some(min_length) = min_length
         && n < min_length
     {
-        errors.push(format!("String is too short! (min length: {min_length})"));
+        errors.push(StringError {
+            keyword: "minLength",
+            keyword_value: min_length.to_string(),
+            message: format!("String is too short! (min length: {min_length})"),
+        });
     }
     if let Some(n) = char_len
         && let Some(max_length) = max_length
         && n > max_length
     {
-        errors.push(format!("String is too long! (max length: {max_length})"));
+        errors.push(StringError {
+            keyword: "maxLength",
+            keyword_value: max_length.to_string(),
+            message: format!("String is too long! (max length: {max_length})"),
+        });
     }
+    // A string of the wrong length is invalid regardless of its pattern, so don't spend time on a
+    // potentially expensive regex match (e.g. for a very long string)
+    let length_ok = !errors.iter().any(StringError::is_length_error);
     if let Some(regex) = pattern
+        && length_ok
         && !regex.is_match(str_value)
     {
-        errors.push(format!(
-            "String does not match regular expression {}!",
-            regex.as_str()
-        ));
+        errors.push(StringError {
+            keyword: "pattern",
+            keyword_value: format!("{:?}", regex.as_str()),
+            message: format!(
+                "String does not match regular expression {}!",
+                regex.as_str()
+            ),
+        });
     }
     if let Some(fmt) = format
         && let Some(err) = formats::validate_format(fmt, str_value)
     {
-        errors.push(err);
+        errors.push(StringError {
+            keyword: "format",
+            keyword_value: fmt.to_string(),
+            message: err,
+        });
     }
     if let Some(enum_values) = r#enum
         && !enum_values.contains(&str_value.to_string())
     {
-        errors.push(format!("String is not in enum: {enum_values:?}"));
+        errors.push(StringError {
+            keyword: "enum",
+            keyword_value: format!("[{}]", enum_values.join(", ")),
+            message: format!("String is not in enum: {enum_values:?}"),
+        });
     }
 }
 
@@ -142,7 +252,7 @@ mod tests {
         validate_string(&mut errors, Some(5), None, None, None, None, "hell");
         assert!(!errors.is_empty());
         assert_eq!(
-            errors.first().unwrap(),
+            errors.first().unwrap().message,
             "String is too short! (min length: 5)"
         );
     }
@@ -169,9 +279,20 @@ mod tests {
         let mut errors = Vec::new();
         validate_string(&mut errors, Some(4), None, None, None, None, greek);
         assert_eq!(
-            errors.first().map(|s| s.as_str()),
+            errors.first().map(|e| e.message.as_str()),
             Some("String is too short! (min length: 4)")
         );
+
+        // Precomposed "é" is one scalar (2 bytes), "e" plus a combining acute accent is two
+        let mut errors = Vec::new();
+        validate_string(&mut errors, Some(4), Some(4), None, None, None, "café");
+        assert!(errors.is_empty());
+        let mut errors = Vec::new();
+        validate_string(&mut errors, None, Some(4), None, None, None, "cafe\u{301}");
+        assert_eq!(
+            errors.first().map(|e| e.message.as_str()),
+            Some("String is too long! (max length: 4)")
+        );
     }
 
     #[test]
@@ -221,7 +342,46 @@ mod tests {
             "not-an-email",
         );
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("email"));
+        assert!(errors[0].message.contains("email"));
+    }
+
+    #[test]
+    fn test_engine_validate_content_encoding() {
+        let root_schema = crate::loader::load_from_str(
+            "type: string\ncontentEncoding: base64\ncontentMediaType: application/json",
+        )
+        .unwrap();
+        // Annotations only, by default
+        let context = Engine::evaluate(&root_schema, "not base64!", false).unwrap();
+        assert!(!context.has_errors());
+
+        let options = crate::ValidationOptions::default().content_assertions(true);
+        // `{"port": 8080}`
+        let context =
+            Engine::evaluate_with_options(&root_schema, "eyJwb3J0IjogODA4MH0=", &options).unwrap();
+        assert!(!context.has_errors());
+
+        let context = Engine::evaluate_with_options(&root_schema, "not base64!", &options).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "[1:1] .: String could not be decoded as base64"
+        );
+        assert_eq!(errors[0].keyword.as_deref(), Some("contentEncoding"));
+        drop(errors);
+
+        // `hello`
+        let context = Engine::evaluate_with_options(&root_schema, "aGVsbG8=", &options).unwrap();
+        let errors = context.errors.borrow();
+        assert!(
+            errors[0]
+                .error
+                .starts_with("Decoded content is not valid JSON: "),
+            "{}",
+            errors[0].error
+        );
+        assert_eq!(errors[0].keyword.as_deref(), Some("contentMediaType"));
     }
 
     #[test]
@@ -238,6 +398,30 @@ mod tests {
         assert!(context.has_errors());
     }
 
+    #[test]
+    fn test_engine_validate_json_format() {
+        let root_schema = crate::loader::load_from_str(
+            "type: object\nproperties:\n  config:\n    type: string\n    format: json",
+        )
+        .unwrap();
+        let context = Engine::evaluate(
+            &root_schema,
+            "config: '{\"retries\": 3, \"hosts\": [\"a\"]}'",
+            false,
+        )
+        .unwrap();
+        assert!(!context.has_errors());
+
+        let context = Engine::evaluate(&root_schema, "config: '{\"retries\": 3'", false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "config");
+        assert_eq!(
+            errors[0].error,
+            "String is not valid JSON: EOF while parsing an object at line 1 column 13"
+        );
+    }
+
     #[test]
     fn test_validate_string_unknown_format_always_passes() {
         let mut errors = Vec::new();
@@ -245,4 +429,160 @@ mod tests {
         validate_string(&mut errors, None, None, None, Some(&fmt), None, "anything");
         assert!(errors.is_empty());
     }
+
+    #[test]
+    fn test_pattern_skipped_on_length_error() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: string
+            maxLength: 8
+            pattern: "^(a+)+$"
+            "#,
+        )
+        .unwrap();
+        let options = crate::ValidationOptions::default().count_checks(true);
+        let value = format!("{}!", "a".repeat(64));
+        let context = Engine::evaluate_with_options(&root_schema, &value, &options).unwrap();
+        let errors: Vec<String> = context
+            .errors
+            .borrow()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(errors, vec!["[1:1] .: String is too long! (max length: 8)"]);
+        assert_eq!(context.check_counts().unwrap().regex_matches, 0);
+
+        let context = Engine::evaluate_with_options(&root_schema, "aab", &options).unwrap();
+        assert_eq!(
+            context.errors.borrow()[0].error,
+            "String does not match regular expression ^(a+)+$!"
+        );
+        assert_eq!(context.check_counts().unwrap().regex_matches, 1);
+    }
+
+    #[test]
+    fn test_invalid_pattern_fails_to_load() {
+        let err = crate::loader::load_from_str("type: string\npattern: \"[a-z\"").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.starts_with("Invalid regular expression: [2, 9] pattern '[a-z':"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_schema_fragment_on_errors() {
+        let root_schema = crate::loader::load_from_str("type: string\nminLength: 5").unwrap();
+        let options = crate::ValidationOptions::default().schema_fragments(true);
+        let context = Engine::evaluate_with_options(&root_schema, "abc", &options).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].schema_fragment.as_deref(), Some("{minLength: 5}"));
+        drop(errors);
+
+        let context = Engine::evaluate(&root_schema, "abc", false).unwrap();
+        assert_eq!(context.errors.borrow()[0].schema_fragment, None);
+    }
+
+    #[test]
+    fn test_explicit_core_schema_tags() {
+        let string_schema = crate::loader::load_from_str("type: string").unwrap();
+        let integer_schema = crate::loader::load_from_str("type: integer").unwrap();
+
+        let context = Engine::evaluate(&string_schema, "!!str 123", false).unwrap();
+        assert!(!context.has_errors());
+        let context = Engine::evaluate(&integer_schema, "!!str 123", false).unwrap();
+        assert!(context.has_errors());
+
+        let boolean_schema = crate::loader::load_from_str("type: boolean").unwrap();
+        let context = Engine::evaluate(&string_schema, "!!str true", false).unwrap();
+        assert!(!context.has_errors());
+        let context = Engine::evaluate(&boolean_schema, "!!str true", false).unwrap();
+        assert!(context.has_errors());
+
+        // A value that doesn't parse as its declared core schema type is neither
+        let context = Engine::evaluate(&integer_schema, "!!int foo", false).unwrap();
+        assert!(context.has_errors());
+        let context = Engine::evaluate(&string_schema, "!!int foo", false).unwrap();
+        assert!(context.has_errors());
+    }
+
+    #[test]
+    fn test_x_trim_extension() {
+        let root_schema =
+            crate::loader::load_from_str("type: string\nminLength: 2\nmaxLength: 2\nx-trim: true")
+                .unwrap();
+        let options = crate::ValidationOptions::default().x_extensions(true);
+        let context = Engine::evaluate_with_options(&root_schema, r#""  hi  ""#, &options).unwrap();
+        assert!(!context.has_errors());
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity, crate::validation::Severity::Warning);
+        assert_eq!(
+            errors[0].error,
+            r#"String "  hi  " was validated as "hi" (x-trim)"#
+        );
+        drop(errors);
+
+        let context = Engine::evaluate(&root_schema, r#""  hi  ""#, false).unwrap();
+        let errors = context.errors.borrow();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, "String is too long! (max length: 2)");
+    }
+
+    #[test]
+    fn test_x_trim_warning_leaves_property_evaluated() {
+        let root_schema = crate::loader::load_from_str(
+            r#"
+            type: object
+            properties:
+              a:
+                type: string
+                x-trim: true
+                minLength: 2
+            unevaluatedProperties: false
+            "#,
+        )
+        .unwrap();
+        let options = crate::ValidationOptions::default().x_extensions(true);
+        let context =
+            Engine::evaluate_with_options(&root_schema, r#"a: "  hi  ""#, &options).unwrap();
+        assert!(!context.has_errors(), "{:?}", context.errors.borrow());
+        assert_eq!(context.errors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_null_where_string_expected() {
+        let root_schema = crate::loader::load_from_str(
+            "type: object\nproperties:\n  name:\n    type: string\n    minLength: 0",
+        )
+        .unwrap();
+        let error_for = |document: &str, options: &crate::ValidationOptions| {
+            let context = Engine::evaluate_with_options(&root_schema, document, options).unwrap();
+            let errors = context.errors.borrow();
+            errors.first().map(|error| error.error.clone())
+        };
+
+        let options = crate::ValidationOptions::default();
+        assert_eq!(
+            error_for("name:", &options).as_deref(),
+            Some(
+                "Property is empty (null); use '' for an empty string or make the property optional"
+            )
+        );
+        assert_eq!(error_for(r#"name: """#, &options), None);
+        assert_eq!(
+            error_for("name: ~", &options).as_deref(),
+            Some("Expected a string, but got: null")
+        );
+        assert_eq!(
+            error_for("name: null", &options).as_deref(),
+            Some("Expected a string, but got: null")
+        );
+
+        let options = crate::ValidationOptions::default().null_as_empty_string(true);
+        for document in ["name:", r#"name: """#, "name: ~", "name: null"] {
+            assert_eq!(error_for(document, &options), None, "{document}");
+        }
+    }
 }
@@ -172,3 +172,45 @@ properties:
         "Expected validation error for invalid color"
     );
 }
+
+#[test]
+fn test_external_ref_cycle_across_files_is_reported() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let dir = temp.path();
+
+    std::fs::write(dir.join("a.yaml"), "$ref: \"./b.yaml\"\n").expect("write a.yaml");
+    std::fs::write(dir.join("b.yaml"), "$ref: \"./a.yaml\"\n").expect("write b.yaml");
+
+    let schema_path = dir.join("a.yaml");
+    let root_schema =
+        loader::load_file(schema_path.to_str().expect("path to str")).expect("load schema");
+
+    let context = Engine::evaluate(&root_schema, "value", false).expect("evaluate");
+    let errors = context.errors.borrow();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].error, "Circular $ref detected: ./b.yaml");
+}
+
+#[test]
+fn test_external_ref_to_missing_file_is_a_clear_error() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let dir = temp.path();
+
+    let schema_yaml = r##"
+type: object
+properties:
+  id:
+    $ref: "./missing.yaml#/$defs/Id"
+"##;
+    let schema_path = dir.join("schema.yaml");
+    std::fs::write(&schema_path, schema_yaml).expect("write schema.yaml");
+    let root_schema =
+        loader::load_file(schema_path.to_str().expect("path to str")).expect("load schema");
+
+    let err = Engine::evaluate(&root_schema, "id: x", false).expect_err("missing file");
+    let message = err.to_string();
+    assert!(
+        message.contains("Failed to load $ref ./missing.yaml#/$defs/Id from file://"),
+        "{message}"
+    );
+}
@@ -8,12 +8,12 @@ use cucumber::when;
 use log::debug;
 use log::error;
 use serde_json::Value;
-use std::cell::RefCell;
-use std::rc::Rc;
 use yaml_schema::Engine;
 use yaml_schema::Result;
 use yaml_schema::RootSchema;
+use yaml_schema::ValidationOptions;
 use yaml_schema::loader;
+use yaml_schema::validation::Shared;
 use yaml_schema::validation::ValidationError;
 
 #[derive(Debug, Default)]
@@ -27,7 +27,7 @@ struct CommandOutput {
 pub struct FeaturesWorld {
     root_schema: Option<RootSchema>,
     yaml_schema_error: Option<yaml_schema::Error>,
-    errors: Option<Rc<RefCell<Vec<ValidationError>>>>,
+    errors: Option<Shared<Vec<ValidationError>>>,
     command_output: Option<CommandOutput>,
 }
 
@@ -45,10 +45,12 @@ async fn a_yaml_schema(world: &mut FeaturesWorld, step: &Step) {
 }
 
 fn evaluate(world: &mut FeaturesWorld, s: &str) -> Result<bool> {
-    let context = Engine::evaluate(
+    // Feature scenarios include `$ref`s to remote URLs
+    let options = ValidationOptions::default().allow_remote_refs(true);
+    let context = Engine::evaluate_with_options(
         world.root_schema.as_ref().expect("No root schema"),
         s,
-        false,
+        &options,
     )?;
     world.errors = Some(context.errors.clone());
     for error in context.errors.borrow().iter() {
@@ -179,7 +181,14 @@ async fn stdout_json_two_validation_errors(world: &mut FeaturesWorld) {
     );
     for entry in arr {
         let obj = entry.as_object().expect("each error should be an object");
-        for key in ["index", "line", "col", "path", "error"] {
+        for key in [
+            "path",
+            "instance_path",
+            "schema_path",
+            "message",
+            "line",
+            "column",
+        ] {
             assert!(obj.contains_key(key), "missing key {key} in {obj:?}");
         }
     }
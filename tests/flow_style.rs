@@ -0,0 +1,172 @@
+//! Loads the schemas of the feature scenarios rewritten in YAML flow style (e.g.
+//! `{type: object, properties: {a: {type: string}}}`), and checks that they load to the same
+//! schema and validate the scenarios' documents, in block and flow style, the same way.
+
+use std::fs;
+use std::path::Path;
+
+use saphyr::LoadableYamlNode;
+use saphyr::MarkedYaml;
+use saphyr::Scalar;
+use saphyr::YamlData;
+use yaml_schema::Engine;
+use yaml_schema::RootSchema;
+use yaml_schema::loader;
+
+/// Render `node` in flow style, on a single line
+fn to_flow(node: &MarkedYaml) -> String {
+    match &node.data {
+        YamlData::Value(scalar) => scalar_to_flow(scalar),
+        YamlData::Sequence(items) => {
+            let items: Vec<String> = items.iter().map(to_flow).collect();
+            format!("[{}]", items.join(", "))
+        }
+        YamlData::Mapping(mapping) => {
+            let entries: Vec<String> = mapping
+                .iter()
+                .map(|(key, value)| format!("{}: {}", to_flow(key), to_flow(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        other => panic!("Unexpected YAML node: {other:?}"),
+    }
+}
+
+fn scalar_to_flow(scalar: &Scalar) -> String {
+    match scalar {
+        Scalar::Null => "null".to_string(),
+        Scalar::Boolean(b) => b.to_string(),
+        Scalar::Integer(i) => i.to_string(),
+        Scalar::FloatingPoint(f) if f.is_nan() => ".nan".to_string(),
+        Scalar::FloatingPoint(f) if f.is_infinite() => if f.is_sign_positive() {
+            ".inf"
+        } else {
+            "-.inf"
+        }
+        .to_string(),
+        // `{:?}` keeps the fraction, e.g. `1.0`, so the value still loads as a float
+        Scalar::FloatingPoint(f) => format!("{:?}", f.into_inner()),
+        Scalar::String(s) => {
+            let plain = s.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+                && matches!(
+                    MarkedYaml::value_from_str(s).data,
+                    YamlData::Value(Scalar::String(_))
+                );
+            if plain {
+                s.to_string()
+            } else {
+                serde_json::to_string(s.as_ref()).expect("Unable to quote string")
+            }
+        }
+    }
+}
+
+fn flow_document(text: &str) -> Option<String> {
+    let docs = MarkedYaml::load_from_str(text).ok()?;
+    match docs.as_slice() {
+        [doc] => Some(to_flow(doc)),
+        _ => None,
+    }
+}
+
+/// Whether `value` is accepted, and the paths of the errors
+fn evaluate(root_schema: &RootSchema, value: &str) -> (bool, Vec<String>) {
+    let context = Engine::evaluate(root_schema, value, false).expect("Evaluation failed");
+    let errors = context.errors.borrow();
+    (
+        !context.has_errors(),
+        errors.iter().map(|e| e.path.clone()).collect(),
+    )
+}
+
+/// The steps of `feature` that have a docstring, as `(scenario, step, docstring)`, where `step`
+/// omits the keyword, e.g. `it should accept:`
+fn docstring_steps(feature: &str) -> Vec<(String, String, String)> {
+    let mut steps = Vec::new();
+    let mut scenario = String::new();
+    let mut step = String::new();
+    let mut lines = feature.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if let Some(name) = trimmed.strip_prefix("Scenario:") {
+            scenario = name.trim().to_string();
+        } else if let Some((keyword, rest)) = trimmed.split_once(' ')
+            && matches!(keyword, "Given" | "When" | "Then" | "And" | "But")
+        {
+            step = rest.to_string();
+        } else if trimmed.starts_with("```") {
+            // Like Gherkin, strip the indentation of the opening fence from the docstring
+            let indent = line.len() - trimmed.len();
+            let mut docstring = String::new();
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                docstring.push_str(line.get(indent..).unwrap_or(line.trim_start()));
+                docstring.push('\n');
+            }
+            steps.push((scenario.clone(), step.clone(), docstring));
+        }
+    }
+    steps
+}
+
+#[test]
+fn feature_schemas_in_flow_style() {
+    let mut schemas = 0;
+    let mut documents = 0;
+    let features = Path::new(env!("CARGO_MANIFEST_DIR")).join("features");
+    let mut paths: Vec<_> = fs::read_dir(features)
+        .expect("Unable to read features")
+        .map(|entry| entry.expect("Unable to read features").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "feature"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let feature = fs::read_to_string(&path).expect("Unable to read feature");
+        let mut schemas_under_test = None;
+        for (scenario, step, docstring) in docstring_steps(&feature) {
+            let name = format!("{}: {scenario}", path.display());
+            if step == "a YAML schema:" {
+                schemas_under_test = None;
+                // Skip schemas that don't load, or are several documents
+                let (Ok(block), Some(flow_text)) =
+                    (loader::load_from_str(&docstring), flow_document(&docstring))
+                else {
+                    continue;
+                };
+                assert!(!flow_text.contains('\n'), "{name}: {flow_text}");
+                let flow = loader::load_from_str(&flow_text)
+                    .unwrap_or_else(|e| panic!("{name}: {flow_text}\n{e}"));
+                assert_eq!(block, flow, "{name}: {flow_text}");
+                schemas += 1;
+                // Scenarios with remote `$ref`s need the network
+                if !docstring.contains("http") {
+                    schemas_under_test = Some((block, flow));
+                }
+            } else if let Some((block, flow)) = &schemas_under_test
+                && let Some(accept) = match step.as_str() {
+                    "it should accept:" => Some(true),
+                    "it should NOT accept:" => Some(false),
+                    _ => None,
+                }
+            {
+                let expected = evaluate(block, &docstring);
+                assert_eq!(expected.0, accept, "{name}: {docstring}");
+                assert_eq!(evaluate(flow, &docstring), expected, "{name}: {docstring}");
+                if let Some(flow_value) = flow_document(&docstring) {
+                    assert_eq!(
+                        evaluate(block, &flow_value),
+                        expected,
+                        "{name}: {flow_value}"
+                    );
+                    documents += 1;
+                }
+            }
+        }
+    }
+    assert!(schemas > 100, "{schemas}");
+    assert!(documents > 200, "{documents}");
+}
@@ -0,0 +1,120 @@
+//! Runs the cases in `tests/fixtures/json-schema-test-suite` through [`Engine::evaluate`].
+//!
+//! The fixtures use the format of the official JSON Schema test suite: each file holds a list
+//! of groups, each group a schema and the instances that should (or should not) validate
+//! against it. JSON is valid YAML, so schemas and instances are serialized back to JSON and
+//! loaded as YAML.
+//!
+//! Known failures are listed in `known-failures.txt`. The test fails if any other case fails,
+//! if a known failure starts passing, or if the list grows beyond [`MAX_KNOWN_FAILURES`].
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use yaml_schema::Engine;
+use yaml_schema::loader;
+
+const FIXTURES_DIR: &str = "tests/fixtures/json-schema-test-suite";
+
+/// The maximum number of entries allowed in `known-failures.txt`.
+const MAX_KNOWN_FAILURES: usize = 10;
+
+/// Runs a single case, returning whether the outcome matched the expected validity.
+fn run_case(schema: &Value, data: &Value, valid: bool) -> Result<(), String> {
+    let schema_str = serde_json::to_string(schema).map_err(|e| e.to_string())?;
+    let data_str = serde_json::to_string(data).map_err(|e| e.to_string())?;
+    let root_schema =
+        loader::load_from_str(&schema_str).map_err(|e| format!("failed to load schema: {e}"))?;
+    let context = Engine::evaluate(&root_schema, &data_str, false)
+        .map_err(|e| format!("failed to evaluate: {e}"))?;
+    let errors = context.errors.borrow();
+    match (valid, context.has_errors()) {
+        (true, true) => Err(format!(
+            "expected valid, got: {}",
+            errors
+                .iter()
+                .filter(|e| e.is_error())
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        )),
+        (false, false) => Err("expected invalid, got no errors".to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn load_known_failures() -> BTreeSet<String> {
+    let contents = fs::read_to_string(Path::new(FIXTURES_DIR).join("known-failures.txt"))
+        .expect("read known-failures.txt");
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn json_schema_test_suite() {
+    let known_failures = load_known_failures();
+    assert!(
+        known_failures.len() <= MAX_KNOWN_FAILURES,
+        "known-failures.txt has {} entries, at most {MAX_KNOWN_FAILURES} are allowed",
+        known_failures.len()
+    );
+
+    let mut paths: Vec<_> = fs::read_dir(FIXTURES_DIR)
+        .expect("read fixtures dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures found in {FIXTURES_DIR}");
+
+    let mut seen = BTreeSet::new();
+    let mut unexpected_failures = Vec::new();
+    let mut unexpected_passes = Vec::new();
+    for path in &paths {
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("file name");
+        let contents = fs::read_to_string(path).expect("read fixture");
+        let groups: Vec<Value> = serde_json::from_str(&contents).expect("parse fixture");
+        for group in &groups {
+            let group_description = group["description"].as_str().expect("group description");
+            for case in group["tests"].as_array().expect("tests") {
+                let description = case["description"].as_str().expect("test description");
+                let valid = case["valid"].as_bool().expect("valid");
+                let id = format!("{file_name}: {group_description}: {description}");
+                seen.insert(id.clone());
+                match run_case(&group["schema"], &case["data"], valid) {
+                    Ok(()) if known_failures.contains(&id) => unexpected_passes.push(id),
+                    Err(reason) if !known_failures.contains(&id) => {
+                        unexpected_failures.push(format!("{id}\n    {reason}"))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let stale: Vec<_> = known_failures.difference(&seen).collect();
+    assert!(
+        stale.is_empty(),
+        "known-failures.txt lists cases that don't exist:\n{stale:#?}"
+    );
+    assert!(
+        unexpected_passes.is_empty(),
+        "known failures now pass, remove them from known-failures.txt:\n{}",
+        unexpected_passes.join("\n")
+    );
+    assert!(
+        unexpected_failures.is_empty(),
+        "{} case(s) failed:\n{}",
+        unexpected_failures.len(),
+        unexpected_failures.join("\n")
+    );
+}
@@ -0,0 +1,73 @@
+//! Checks that a schema can be shared between threads to validate documents in parallel, and
+//! that a [`Context`] can be sent to and shared with other threads.
+
+use rayon::prelude::*;
+use yaml_schema::Context;
+use yaml_schema::Engine;
+use yaml_schema::RootSchema;
+use yaml_schema::YamlSchema;
+use yaml_schema::loader;
+use yaml_schema::validation::ValidationError;
+
+fn assert_send<T: Send>() {}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn schemas_and_contexts_are_send_and_sync() {
+    assert_send::<RootSchema>();
+    assert_sync::<RootSchema>();
+    assert_send::<YamlSchema>();
+    assert_sync::<YamlSchema>();
+    assert_send::<Context>();
+    assert_sync::<Context>();
+    assert_send::<ValidationError>();
+    assert_sync::<ValidationError>();
+}
+
+#[test]
+fn validates_documents_in_parallel() {
+    let root_schema = loader::load_from_str(
+        r#"
+        type: object
+        properties:
+          id:
+            type: integer
+            maximum: 89
+        required: [id]
+        "#,
+    )
+    .unwrap();
+    let documents: Vec<String> = (0..100).map(|id| format!("id: {id}")).collect();
+    let failures: Vec<(usize, Vec<String>)> = documents
+        .par_iter()
+        .enumerate()
+        .map(|(index, document)| {
+            let context = Engine::evaluate(&root_schema, document, false).unwrap();
+            let errors = context.errors.borrow();
+            (index, errors.iter().map(ToString::to_string).collect())
+        })
+        .filter(|(_, errors): &(usize, Vec<String>)| !errors.is_empty())
+        .collect();
+    assert_eq!(failures.len(), 10);
+    for (index, errors) in failures {
+        assert!(index >= 90, "{index}");
+        assert_eq!(
+            errors,
+            vec!["[1:5] .id: Number must be less than or equal to 89"]
+        );
+    }
+}
+
+#[test]
+fn context_moves_to_another_thread() {
+    let root_schema = loader::load_from_str("type: string").unwrap();
+    let context = Engine::evaluate(&root_schema, "42", false).unwrap();
+    let errors = std::thread::scope(|scope| {
+        scope
+            .spawn(move || context.errors.borrow().len())
+            .join()
+            .unwrap()
+    });
+    assert_eq!(errors, 1);
+}
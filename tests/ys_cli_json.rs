@@ -1,6 +1,10 @@
 use assert_cmd::Command;
 use serde_json::Value;
 use std::fs;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::thread;
 use tempfile::tempdir;
 
 #[test]
@@ -48,7 +52,9 @@ b: 2
     );
     for entry in arr {
         assert!(entry.get("path").is_some());
-        assert!(entry.get("error").is_some());
+        assert!(entry.get("message").is_some());
+        assert_eq!(entry["severity"], "error");
+        assert_eq!(entry["suppressed"], false);
     }
 }
 
@@ -93,6 +99,282 @@ b: 2
     );
     for entry in arr {
         assert!(entry.get("path").is_some());
-        assert!(entry.get("error").is_some());
+        assert!(entry.get("message").is_some());
     }
 }
+
+#[test]
+fn data_file_passed_as_schema_warns() {
+    let dir = tempdir().expect("tempdir");
+    let schema_path = dir.path().join("data.yaml");
+    let instance_path = dir.path().join("instance.yaml");
+    fs::write(&schema_path, "name: John\nage: 42\n").expect("write data");
+    fs::write(&instance_path, "anything: goes\n").expect("write instance");
+
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "-f",
+            schema_path.to_str().expect("utf8 path"),
+            instance_path.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("run ys");
+
+    assert!(output.status.success(), "the data file accepts anything");
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(
+        stderr.contains("warning: ") && stderr.contains("doesn't look like a schema"),
+        "{stderr}"
+    );
+
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "--json",
+            "-f",
+            schema_path.to_str().expect("utf8 path"),
+            instance_path.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("run ys");
+    let warning: Value = serde_json::from_slice(&output.stderr).expect("stderr is JSON");
+    assert!(
+        warning["warning"]
+            .as_str()
+            .is_some_and(|w| w.contains("doesn't look like a schema")),
+        "{warning}"
+    );
+}
+
+#[test]
+fn warnings_are_shown_when_validation_passes() {
+    let dir = tempdir().expect("tempdir");
+    let schema_path = dir.path().join("schema.yaml");
+    let instance_path = dir.path().join("instance.yaml");
+    fs::write(
+        &schema_path,
+        r#"anyOf:
+  - $ref: "missing.yaml"
+  - type: string
+"#,
+    )
+    .expect("write schema");
+    fs::write(&instance_path, "hello\n").expect("write instance");
+
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "-f",
+            schema_path.to_str().expect("utf8 path"),
+            instance_path.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("run ys");
+
+    assert!(output.status.success(), "warnings don't fail validation");
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(
+        stderr.starts_with("warning: ") && stderr.contains("could not be evaluated"),
+        "{stderr}"
+    );
+
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "--json",
+            "-f",
+            schema_path.to_str().expect("utf8 path"),
+            instance_path.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("run ys");
+    assert!(output.status.success(), "warnings don't fail validation");
+    assert!(output.stdout.is_empty());
+    let warning: Value = serde_json::from_slice(&output.stderr).expect("stderr is JSON");
+    assert_eq!(warning["warning"]["severity"], "warning", "{warning}");
+}
+
+#[test]
+fn unknown_keys_warn_or_error() {
+    let dir = tempdir().expect("tempdir");
+    let schema_path = dir.path().join("schema.yaml");
+    let instance_path = dir.path().join("instance.yaml");
+    fs::write(&schema_path, "type: integer\nminmum: 3\n").expect("write schema");
+    fs::write(&instance_path, "1\n").expect("write instance");
+    let run = |unknown_keys: &str| {
+        Command::cargo_bin("ys")
+            .expect("ys binary")
+            .args([
+                "--unknown-keys",
+                unknown_keys,
+                "-f",
+                schema_path.to_str().expect("utf8 path"),
+                instance_path.to_str().expect("utf8 path"),
+            ])
+            .output()
+            .expect("run ys")
+    };
+
+    let output = run("ignore");
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+
+    let output = run("warn");
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("utf8 stderr");
+    assert!(
+        stderr.contains("Unknown keyword 'minmum' (did you mean 'minimum'?)"),
+        "{stderr}"
+    );
+
+    let output = run("error");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn all_documents_errors_carry_document_index() {
+    let dir = tempdir().expect("tempdir");
+    let schema_path = dir.path().join("schema.yaml");
+    let instance_path = dir.path().join("instance.yaml");
+    fs::write(
+        &schema_path,
+        "type: object\nproperties:\n  a:\n    type: string\n",
+    )
+    .expect("write schema");
+    fs::write(&instance_path, "a: 1\n---\na: ok\n---\na: 3\n").expect("write instance");
+
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "--json",
+            "--all-documents",
+            "-f",
+            schema_path.to_str().expect("utf8 path"),
+            instance_path.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("run ys");
+    assert_eq!(output.status.code(), Some(1));
+    let errors: Value = serde_json::from_slice(&output.stdout).expect("JSON on stdout");
+    let attributed: Vec<(u64, u64)> = errors
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|e| {
+            (
+                e["document"].as_u64().expect("document"),
+                e["line"].as_u64().expect("line"),
+            )
+        })
+        .collect();
+    assert_eq!(attributed, vec![(0, 1), (2, 5)]);
+}
+
+#[test]
+fn schema_url_download_failure_exits_with_network_error_code() {
+    let dir = tempdir().expect("tempdir");
+    let instance_path = dir.path().join("instance.yaml");
+    fs::write(&instance_path, "a: 1\n").expect("write instance");
+
+    // Nothing listens on the discard port, so the connection is refused
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "--json",
+            "--schema-url",
+            "http://127.0.0.1:9/schema.yaml",
+            "--timeout",
+            "5",
+            instance_path.to_str().expect("utf8 path"),
+        ])
+        .output()
+        .expect("run ys");
+    assert_eq!(output.status.code(), Some(3));
+    let error: Value = serde_json::from_slice(&output.stderr).expect("JSON on stderr");
+    assert!(
+        error["error"]
+            .as_str()
+            .expect("error")
+            .starts_with("Failed to load schema from URL http://127.0.0.1:9/schema.yaml: "),
+        "{error}"
+    );
+}
+
+/// Serves `body` over HTTP to a single request, returning the URL to request it from
+fn serve_once(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!(
+        "http://{}/schema.yaml",
+        listener.local_addr().expect("address")
+    );
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept");
+        let mut request = [0; 4096];
+        let _ = stream.read(&mut request).expect("read request");
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream
+            .write_all(response.as_bytes())
+            .expect("write response");
+    });
+    url
+}
+
+#[test]
+fn schema_url_applies_loader_options() {
+    let dir = tempdir().expect("tempdir");
+    let instance_path = dir.path().join("instance.yaml");
+    fs::write(&instance_path, "5\n").expect("write instance");
+    let schema = "type: integer\nminmum: 1\n";
+
+    let run = |unknown_keys: &str| {
+        Command::cargo_bin("ys")
+            .expect("ys binary")
+            .args([
+                "--schema-url",
+                &serve_once(schema),
+                "--unknown-keys",
+                unknown_keys,
+                instance_path.to_str().expect("utf8 path"),
+            ])
+            .output()
+            .expect("run ys")
+    };
+
+    let output = run("warn");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(
+        stderr.starts_with("warning: http://127.0.0.1:") && stderr.contains("minmum"),
+        "{stderr}"
+    );
+
+    let output = run("error");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{stderr}");
+    assert!(stderr.contains("minmum"), "{stderr}");
+}
+
+#[test]
+#[ignore = "requires internet access"]
+fn schema_url_validates_against_downloaded_schema() {
+    let output = Command::cargo_bin("ys")
+        .expect("ys binary")
+        .args([
+            "--schema-url",
+            "https://yaml-schema.net/yaml-schema.yaml",
+            "--timeout",
+            "10",
+            "yaml-schema.yaml",
+        ])
+        .output()
+        .expect("run ys");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}